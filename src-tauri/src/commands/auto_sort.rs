@@ -0,0 +1,506 @@
+use crate::commands::file_ops::{fan_out_file, move_file};
+use crate::config::options::{FanOutMode, MatchSource};
+use crate::config::settings::{load_settings, OperationMode, Settings};
+use crate::matching::{collect_targets, match_targets, MatchItem, MatchRule, MatchingConfig};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::{info, warn};
+
+/// 自動仕分けの移動予定
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedMove {
+    pub source_path: String,
+    /// 分別先キー（"0"〜"9"）
+    pub key: String,
+    pub destination: String,
+    pub canonical: String,
+    pub rule: MatchRule,
+    pub source: MatchSource,
+    /// 複数の分別先に確定した場合の追加の分別先キー（fanOut 設定時のみ）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_keys: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_destinations: Vec<String>,
+}
+
+/// スキップ理由
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SkipReason {
+    /// どのリストにもマッチしない
+    Unmatched,
+    /// 候補のみで確定マッチがない
+    CandidateOnly,
+    /// 複数の分別先で確定マッチした
+    Ambiguous,
+    /// 移動に失敗した
+    MoveFailed,
+}
+
+/// 移動しなかったファイル
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: SkipReason,
+    /// 関係する分別先キー（曖昧・候補の場合）
+    pub keys: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 自動仕分け計画（確認後に run_auto_sort に渡す）
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoSortPlan {
+    pub moves: Vec<PlannedMove>,
+    pub skipped: Vec<SkippedFile>,
+    /// 追加の分別先への配置方法
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fan_out: Option<FanOutMode>,
+}
+
+/// 実行済みの移動（Undo履歴に積むための情報を含む）
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoSortMove {
+    pub source_path: String,
+    pub source_folder: String,
+    pub dest_path: String,
+    pub key: String,
+    /// 追加の分別先に作成したリンク・コピー（Undo時にまとめて削除）
+    pub extra_paths: Vec<String>,
+}
+
+/// 自動仕分け結果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoSortSummary {
+    pub moved: Vec<AutoSortMove>,
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// 確定マッチした分別先（キー・分別先フォルダ・最上位のマッチ）と、候補のみの分別先キーを集める
+fn match_slots(
+    path: &str,
+    settings: &Settings,
+    config: &MatchingConfig,
+) -> (Vec<(String, String, MatchItem)>, Vec<String>) {
+    let mut confirmed_slots = Vec::new();
+    let mut candidate_keys = Vec::new();
+    let targets = collect_targets(path, &settings.matching_sources);
+
+    for (key, word_list) in &settings.word_lists {
+        let Some(word_list) = word_list else {
+            continue;
+        };
+        let Some(Some(destination)) = settings.destinations.get(key) else {
+            continue;
+        };
+
+        let result = match_targets(path, &targets, word_list, config);
+        if let Some(item) = result
+            .confirmed
+            .into_iter()
+            .max_by(|a, b| a.score.total_cmp(&b.score))
+        {
+            confirmed_slots.push((key.clone(), destination.clone(), item));
+        } else if !result.candidates.is_empty() {
+            candidate_keys.push(key.clone());
+        }
+    }
+    (confirmed_slots, candidate_keys)
+}
+
+/// 主分別先以外に確定マッチした分別先フォルダ（手動で仕分けた場合の追加の分別先）
+pub(crate) fn extra_destinations(
+    path: &str,
+    key: &str,
+    settings: &Settings,
+    config: &MatchingConfig,
+) -> Vec<String> {
+    let primary = settings.destinations.get(key).cloned().flatten();
+    let mut folders: Vec<String> = Vec::new();
+    for (slot_key, destination, _) in match_slots(path, settings, config).0 {
+        if slot_key != key
+            && Some(&destination) != primary.as_ref()
+            && !folders.contains(&destination)
+        {
+            folders.push(destination);
+        }
+    }
+    folders
+}
+
+/// 1ファイル分の計画を立てる
+fn plan_file(
+    path: &str,
+    settings: &Settings,
+    config: &MatchingConfig,
+) -> Result<PlannedMove, SkippedFile> {
+    let (mut confirmed_slots, candidate_keys) = match_slots(path, settings, config);
+
+    let skipped = |reason: SkipReason, mut keys: Vec<String>| {
+        keys.sort();
+        SkippedFile {
+            path: path.to_string(),
+            reason,
+            keys,
+            error: None,
+        }
+    };
+
+    match confirmed_slots.len() {
+        0 if candidate_keys.is_empty() => Err(skipped(SkipReason::Unmatched, Vec::new())),
+        0 => Err(skipped(SkipReason::CandidateOnly, candidate_keys)),
+        1 => {
+            let (key, destination, item) = confirmed_slots.remove(0);
+            Ok(PlannedMove {
+                source_path: path.to_string(),
+                key,
+                destination,
+                canonical: item.canonical,
+                rule: item.rule,
+                source: item.source,
+                extra_keys: Vec::new(),
+                extra_destinations: Vec::new(),
+            })
+        }
+        // 振り分け設定時はスコア最上位を主分別先とし、残りを追加の分別先にする
+        _ if settings.fan_out.is_some() => {
+            confirmed_slots
+                .sort_by(|a, b| b.2.score.total_cmp(&a.2.score).then_with(|| a.0.cmp(&b.0)));
+            let (key, destination, item) = confirmed_slots.remove(0);
+            let (extra_keys, extra_destinations) = confirmed_slots
+                .into_iter()
+                .filter(|(_, extra, _)| *extra != destination)
+                .map(|(key, extra, _)| (key, extra))
+                .unzip();
+            Ok(PlannedMove {
+                source_path: path.to_string(),
+                key,
+                destination,
+                canonical: item.canonical,
+                rule: item.rule,
+                source: item.source,
+                extra_keys,
+                extra_destinations,
+            })
+        }
+        _ => Err(skipped(
+            SkipReason::Ambiguous,
+            confirmed_slots.into_iter().map(|(key, _, _)| key).collect(),
+        )),
+    }
+}
+
+/// 確定マッチが1つの分別先に絞れるファイルだけを移動予定にする
+pub(crate) fn build_plan(
+    paths: &[String],
+    settings: &Settings,
+    config: &MatchingConfig,
+) -> AutoSortPlan {
+    let mut plan = AutoSortPlan {
+        fan_out: settings.fan_out,
+        ..Default::default()
+    };
+
+    for path in paths {
+        match plan_file(path, settings, config) {
+            Ok(planned) => plan.moves.push(planned),
+            Err(skipped) => plan.skipped.push(skipped),
+        }
+    }
+    plan
+}
+
+/// 自動仕分け計画を作成する（ファイルは移動しない）
+#[tauri::command]
+pub fn plan_auto_sort(paths: Vec<String>, config_path: String) -> Result<AutoSortPlan, String> {
+    let settings = load_settings(config_path)?;
+    // タグモードはフォルダ構成を変えないための設定なので、ファイルを移動する自動仕分けは行わない
+    if settings.operation_mode == OperationMode::Tag {
+        return Err("Auto-sort moves files and is unavailable in tag mode".to_string());
+    }
+    let plan = build_plan(&paths, &settings, &MatchingConfig::default());
+
+    info!(
+        "自動仕分け計画: 移動{}件 / スキップ{}件",
+        plan.moves.len(),
+        plan.skipped.len()
+    );
+    Ok(plan)
+}
+
+/// 手動で仕分けるファイルの追加の分別先を求める（fanOut 設定時のみ。自動仕分けと同じ照合を使う）
+#[tauri::command]
+pub fn plan_fan_out(path: String, key: String, config_path: String) -> Result<Vec<String>, String> {
+    let settings = load_settings(config_path)?;
+    if settings.fan_out.is_none() {
+        return Ok(Vec::new());
+    }
+    Ok(extra_destinations(
+        &path,
+        &key,
+        &settings,
+        &MatchingConfig::default(),
+    ))
+}
+
+/// 確認済みの自動仕分け計画を実行する
+#[tauri::command]
+pub fn run_auto_sort(plan: AutoSortPlan) -> AutoSortSummary {
+    let mut moved = Vec::new();
+    let mut skipped = plan.skipped;
+
+    for planned in plan.moves {
+        let source_folder = Path::new(&planned.source_path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let result = match plan.fan_out {
+            Some(mode) if !planned.extra_destinations.is_empty() => fan_out_file(
+                planned.source_path.clone(),
+                planned.destination.clone(),
+                planned.extra_destinations.clone(),
+                mode,
+            )
+            .map(|placed| (placed.dest_path, placed.extra_paths)),
+            _ => move_file(planned.source_path.clone(), planned.destination.clone())
+                .map(|dest_path| (dest_path, Vec::new())),
+        };
+
+        match result {
+            Ok((dest_path, extra_paths)) => moved.push(AutoSortMove {
+                source_path: planned.source_path,
+                source_folder,
+                dest_path,
+                key: planned.key,
+                extra_paths,
+            }),
+            Err(e) => {
+                warn!("自動仕分けの移動に失敗: {}: {}", planned.source_path, e);
+                skipped.push(SkippedFile {
+                    path: planned.source_path,
+                    reason: SkipReason::MoveFailed,
+                    keys: vec![planned.key],
+                    error: Some(e),
+                });
+            }
+        }
+    }
+
+    info!(
+        "自動仕分け完了: 移動{}件 / スキップ{}件",
+        moved.len(),
+        skipped.len()
+    );
+    AutoSortSummary { moved, skipped }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::{save_settings, WordList, WordListEntry};
+    use crate::matching::normalize::normalize;
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    fn word_list(canonicals: &[&str]) -> WordList {
+        WordList {
+            file_name: "list.csv".to_string(),
+            source_path: None,
+            entries: canonicals
+                .iter()
+                .map(|canonical| WordListEntry {
+                    entry_type: "character".to_string(),
+                    canonical: canonical.to_string(),
+                    aliases: vec![],
+                    canonical_normalized: normalize(canonical),
+                    aliases_normalized: vec![],
+                    search_keys_normalized: vec![normalize(canonical)],
+                    derived_keys_normalized: vec![],
+                    work_id: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn settings(dest1: &str, dest2: &str) -> Settings {
+        let mut settings = Settings::default();
+        settings
+            .destinations
+            .insert("1".to_string(), Some(dest1.to_string()));
+        settings
+            .destinations
+            .insert("2".to_string(), Some(dest2.to_string()));
+        settings
+            .word_lists
+            .insert("1".to_string(), Some(word_list(&["Aria Starlight"])));
+        settings
+            .word_lists
+            .insert("2".to_string(), Some(word_list(&["Leon Blaze"])));
+        settings
+    }
+
+    #[test]
+    fn test_build_plan_classifies_files() {
+        let settings = settings("/dest/1", "/dest/2");
+        let paths = vec![
+            "/inbox/aria_starlight_01.jpg".to_string(),
+            "/inbox/aria_starlight_leon_blaze.jpg".to_string(),
+            "/inbox/IMG_0001.jpg".to_string(),
+        ];
+
+        let plan = build_plan(&paths, &settings, &MatchingConfig::default());
+
+        assert_eq!(plan.moves.len(), 1);
+        assert_eq!(plan.moves[0].key, "1");
+        assert_eq!(plan.moves[0].destination, "/dest/1");
+        assert_eq!(plan.skipped.len(), 2);
+        assert_eq!(plan.skipped[0].reason, SkipReason::Ambiguous);
+        assert_eq!(plan.skipped[0].keys, vec!["1", "2"]);
+        assert_eq!(plan.skipped[1].reason, SkipReason::Unmatched);
+    }
+
+    #[test]
+    fn test_extra_destinations_for_manual_sort() {
+        let mut settings = settings("/dest/1", "/dest/2");
+        settings
+            .destinations
+            .insert("3".to_string(), Some("/dest/1".to_string()));
+        settings
+            .word_lists
+            .insert("3".to_string(), Some(word_list(&["Leon Blaze"])));
+        let path = "/inbox/aria_starlight_leon_blaze.jpg";
+
+        // 押したキー以外に確定した分別先（主分別先と同じフォルダは除く）
+        let from_1 = extra_destinations(path, "1", &settings, &MatchingConfig::default());
+        let from_2 = extra_destinations(path, "2", &settings, &MatchingConfig::default());
+        let unmatched = extra_destinations(
+            "/inbox/IMG_0001.jpg",
+            "1",
+            &settings,
+            &MatchingConfig::default(),
+        );
+
+        assert_eq!(from_1, vec!["/dest/2"]);
+        assert_eq!(from_2, vec!["/dest/1"]);
+        assert!(unmatched.is_empty());
+    }
+
+    #[test]
+    fn test_plan_auto_sort_refuses_tag_mode() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.json").to_string_lossy().to_string();
+        let mut settings = settings("/dest/1", "/dest/2");
+        settings.operation_mode = OperationMode::Tag;
+        save_settings(settings, config_path.clone()).unwrap();
+
+        let result = plan_auto_sort(
+            vec!["/inbox/aria_starlight_01.jpg".to_string()],
+            config_path,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_run_auto_sort_moves_and_reports() {
+        // Arrange: ソースと分別先を用意
+        let src_dir = tempdir().unwrap();
+        let dest1 = tempdir().unwrap();
+        let dest2 = tempdir().unwrap();
+
+        let aria = src_dir.path().join("aria_starlight_01.jpg");
+        let other = src_dir.path().join("IMG_0001.jpg");
+        File::create(&aria).unwrap();
+        File::create(&other).unwrap();
+        // 分別先に同名ファイルがある場合は連番付きになる
+        File::create(dest1.path().join("aria_starlight_01.jpg")).unwrap();
+
+        let settings = settings(
+            &dest1.path().to_string_lossy(),
+            &dest2.path().to_string_lossy(),
+        );
+        let paths = vec![
+            aria.to_string_lossy().to_string(),
+            other.to_string_lossy().to_string(),
+        ];
+
+        // Act: 計画を作成して実行
+        let plan = build_plan(&paths, &settings, &MatchingConfig::default());
+        let summary = run_auto_sort(plan);
+
+        // Assert: 確定分のみ移動し、残りはスキップ理由付きで返る
+        assert_eq!(summary.moved.len(), 1);
+        assert!(summary.moved[0]
+            .dest_path
+            .ends_with("aria_starlight_01_1.jpg"));
+        assert_eq!(
+            summary.moved[0].source_folder,
+            src_dir.path().to_string_lossy()
+        );
+        assert!(!aria.exists());
+        assert!(other.exists());
+        assert_eq!(summary.skipped.len(), 1);
+        assert_eq!(summary.skipped[0].reason, SkipReason::Unmatched);
+    }
+
+    #[test]
+    fn test_run_auto_sort_reports_move_failure() {
+        let plan = AutoSortPlan {
+            moves: vec![PlannedMove {
+                source_path: "/nonexistent/aria.jpg".to_string(),
+                key: "1".to_string(),
+                destination: "/dest/1".to_string(),
+                canonical: "Aria Starlight".to_string(),
+                rule: MatchRule::B,
+                source: MatchSource::FileName,
+                extra_keys: vec![],
+                extra_destinations: vec![],
+            }],
+            skipped: vec![],
+            fan_out: None,
+        };
+
+        let summary = run_auto_sort(plan);
+
+        assert!(summary.moved.is_empty());
+        assert_eq!(summary.skipped[0].reason, SkipReason::MoveFailed);
+        assert!(summary.skipped[0].error.is_some());
+    }
+
+    #[test]
+    fn test_run_auto_sort_fan_out() {
+        // Arrange: 2つの分別先に確定するファイル
+        let src_dir = tempdir().unwrap();
+        let dest1 = tempdir().unwrap();
+        let dest2 = tempdir().unwrap();
+
+        let both = src_dir.path().join("aria_starlight_leon_blaze.jpg");
+        File::create(&both).unwrap();
+
+        let mut settings = settings(
+            &dest1.path().to_string_lossy(),
+            &dest2.path().to_string_lossy(),
+        );
+        settings.fan_out = Some(FanOutMode::Copy);
+        let paths = vec![both.to_string_lossy().to_string()];
+
+        // Act
+        let plan = build_plan(&paths, &settings, &MatchingConfig::default());
+        let summary = run_auto_sort(plan.clone());
+
+        // Assert: 主分別先へ移動し、もう一方にはコピー
+        assert_eq!(plan.moves.len(), 1);
+        assert_eq!(plan.moves[0].extra_keys.len(), 1);
+        assert!(plan.skipped.is_empty());
+        assert_eq!(summary.moved[0].extra_paths.len(), 1);
+        assert!(!both.exists());
+        assert!(dest1.path().join("aria_starlight_leon_blaze.jpg").exists());
+        assert!(dest2.path().join("aria_starlight_leon_blaze.jpg").exists());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::config::settings::{load_settings, Settings};
+use crate::matching::classifier::{
+    extract_features, load_model, model_path, save_model, ClassifierModel,
+};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::fs;
+use std::sync::Mutex;
+use tracing::{debug, info};
+
+/// モデルファイルの読み書きを直列化するためのロック
+static MODEL_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// 学習結果に基づく分別先の提案
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LearnedSuggestion {
+    pub key: String,
+    pub destination: String,
+    /// 確信度（0.0〜1.0）
+    pub confidence: f64,
+}
+
+/// モデルを読み込んで更新し、保存する
+fn update_model(
+    config_path: &str,
+    update: impl FnOnce(&mut ClassifierModel),
+) -> Result<(), String> {
+    let _guard = MODEL_LOCK.lock().map_err(|e| e.to_string())?;
+    let model_file = model_path(config_path);
+    let mut model = load_model(&model_file)?;
+    update(&mut model);
+    save_model(&model_file, &model)
+}
+
+/// 分別結果を学習する（移動完了後のパスを渡す）
+#[tauri::command]
+pub fn train_classifier(path: String, key: String, config_path: String) -> Result<(), String> {
+    let settings = load_settings(config_path.clone())?;
+    let features = extract_features(&path, &settings.matching_sources);
+    update_model(&config_path, |model| model.train(&features, &key))?;
+
+    debug!("分類器学習: {} -> {} ({}特徴量)", path, key, features.len());
+    Ok(())
+}
+
+/// 学習した分別結果を取り消す（Undo 時に、戻す前のパスを渡す）
+#[tauri::command]
+pub fn untrain_classifier(path: String, key: String, config_path: String) -> Result<(), String> {
+    let settings = load_settings(config_path.clone())?;
+    let features = extract_features(&path, &settings.matching_sources);
+    update_model(&config_path, |model| model.forget(&features, &key))?;
+
+    debug!("分類器学習取り消し: {} -> {}", path, key);
+    Ok(())
+}
+
+/// 学習結果から分別先の提案を確信度順に返す（分別先が未設定のキーは除く）
+pub(crate) fn learned_suggestions(
+    path: &str,
+    config_path: &str,
+    settings: &Settings,
+) -> Result<Vec<LearnedSuggestion>, String> {
+    let features = extract_features(path, &settings.matching_sources);
+    let model = {
+        let _guard = MODEL_LOCK.lock().map_err(|e| e.to_string())?;
+        load_model(&model_path(config_path))?
+    };
+
+    Ok(model
+        .predict(&features)
+        .into_iter()
+        .filter_map(|(key, confidence)| {
+            let destination = settings.destinations.get(&key)?.clone()?;
+            Some(LearnedSuggestion {
+                key,
+                destination,
+                confidence,
+            })
+        })
+        .collect())
+}
+
+/// 学習結果から分別先の提案を確信度順に返す
+#[tauri::command]
+pub fn suggest_learned_destination(
+    path: String,
+    config_path: String,
+) -> Result<Vec<LearnedSuggestion>, String> {
+    let settings = load_settings(config_path.clone())?;
+    let suggestions = learned_suggestions(&path, &config_path, &settings)?;
+
+    debug!("学習による分別先提案: {} - {}件", path, suggestions.len());
+    Ok(suggestions)
+}
+
+/// 学習結果をリセットする
+#[tauri::command]
+pub fn reset_classifier(config_path: String) -> Result<(), String> {
+    let _guard = MODEL_LOCK.lock().map_err(|e| e.to_string())?;
+    let model_file = model_path(&config_path);
+    if model_file.exists() {
+        fs::remove_file(&model_file).map_err(|e| e.to_string())?;
+    }
+
+    info!("分類器リセット: {:?}", model_file);
+    Ok(())
+}
+
+/// 学習結果を JSON としてエクスポートする
+#[tauri::command]
+pub fn export_classifier(config_path: String, export_path: String) -> Result<(), String> {
+    let _guard = MODEL_LOCK.lock().map_err(|e| e.to_string())?;
+    let model = load_model(&model_path(&config_path))?;
+    save_model(std::path::Path::new(&export_path), &model)?;
+
+    info!(
+        "分類器エクスポート: {} ({}件学習済み)",
+        export_path,
+        model.total_documents()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::{save_settings, Settings};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_train_suggest_reset() {
+        // Arrange
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.json").to_string_lossy().to_string();
+        let mut settings = Settings::default();
+        settings
+            .destinations
+            .insert("1".to_string(), Some("/dest/aria".to_string()));
+        settings
+            .destinations
+            .insert("2".to_string(), Some("/dest/leon".to_string()));
+        save_settings(settings, config_path.clone()).unwrap();
+
+        // Act: 学習
+        for (path, key) in [
+            ("/dest/aria/aria_001.png", "1"),
+            ("/dest/aria/aria_002.png", "1"),
+            ("/dest/leon/leon_001.png", "2"),
+        ] {
+            train_classifier(path.to_string(), key.to_string(), config_path.clone()).unwrap();
+        }
+        let suggestions =
+            suggest_learned_destination("/src/aria_003.png".to_string(), config_path.clone())
+                .unwrap();
+
+        // Assert
+        assert_eq!(suggestions[0].key, "1");
+        assert_eq!(suggestions[0].destination, "/dest/aria");
+
+        // Act: 誤った分別を学習してから Undo で取り消す
+        let before =
+            suggest_learned_destination("/src/leon_002.png".to_string(), config_path.clone())
+                .unwrap();
+        train_classifier(
+            "/dest/aria/leon_002.png".to_string(),
+            "1".to_string(),
+            config_path.clone(),
+        )
+        .unwrap();
+        untrain_classifier(
+            "/dest/aria/leon_002.png".to_string(),
+            "1".to_string(),
+            config_path.clone(),
+        )
+        .unwrap();
+
+        // Assert: 学習前と同じ提案に戻る
+        let after =
+            suggest_learned_destination("/src/leon_002.png".to_string(), config_path.clone())
+                .unwrap();
+        assert_eq!(after[0].key, "2");
+        assert_eq!(after[0].confidence, before[0].confidence);
+
+        // Act: エクスポート後にリセット
+        let export_path = dir.path().join("export.json");
+        export_classifier(
+            config_path.clone(),
+            export_path.to_string_lossy().to_string(),
+        )
+        .unwrap();
+        reset_classifier(config_path.clone()).unwrap();
+
+        // Assert
+        assert!(export_path.exists());
+        assert!(
+            suggest_learned_destination("/src/aria_003.png".to_string(), config_path)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -1,8 +1,12 @@
+use crate::commands::thumbnail::record_moves;
+use crate::config::options::{ColorLabel, FanOutMode, ScanOptions, ScanSort};
+use crate::metadata::xattrs;
+use crate::metadata::xmp;
 use serde::Serialize;
 use std::fs;
 use std::path::Path;
 use std::time::UNIX_EPOCH;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 #[derive(Debug, Serialize)]
 pub struct ImageInfo {
@@ -12,6 +16,23 @@ pub struct ImageInfo {
     pub size: Option<u64>,
     #[serde(rename = "modifiedAt", skip_serializing_if = "Option::is_none")]
     pub modified_at: Option<i64>,
+    /// 評価（0〜5、XMP の xmp:Rating）。絞り込み・並べ替えに使う場合のみ読み込む
+    /// （それ以外は表示時に read_rating_label で読み込む）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<u8>,
+    /// 色ラベル（XMP の xmp:Label）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<ColorLabel>,
+}
+
+/// 複数分別先への配置結果（1回のUndoでまとめて戻す）
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FanOutResult {
+    /// 主分別先へ移動したファイルのパス
+    pub dest_path: String,
+    /// 追加の分別先に作成したリンク・コピーのパス
+    pub extra_paths: Vec<String>,
 }
 
 const SUPPORTED_EXTENSIONS: &[&str] = &[
@@ -72,6 +93,7 @@ pub fn move_file(src: String, dest_folder: String) -> Result<String, String> {
         e.to_string()
     })?;
 
+    record_moves(&[(&src, &dest_path)]);
     info!("ファイル移動完了: {} -> {}", src, dest_path);
     Ok(dest_path)
 }
@@ -100,13 +122,123 @@ pub fn undo_move(current_path: String, original_folder: String) -> Result<String
         e.to_string()
     })?;
 
+    record_moves(&[(&current_path, &dest_path)]);
     info!("Undo移動完了: {} -> {}", current_path, dest_path);
     Ok(dest_path)
 }
 
+/// 移動済みのファイルを追加の分別先にリンクまたはコピーする
+fn place_extra(src: &str, folder: &str, mode: FanOutMode) -> Result<String, String> {
+    let file_name = Path::new(src)
+        .file_name()
+        .ok_or("Invalid file name")?
+        .to_string_lossy()
+        .to_string();
+    let dest_path = generate_unique_path(folder, &file_name)?;
+
+    if mode == FanOutMode::HardLink {
+        match fs::hard_link(src, &dest_path) {
+            Ok(()) => return Ok(dest_path),
+            Err(e) => warn!(
+                "ハードリンク作成に失敗、コピーします: {} -> {}: {}",
+                src, dest_path, e
+            ),
+        }
+    }
+    fs::copy(src, &dest_path).map_err(|e| e.to_string())?;
+    Ok(dest_path)
+}
+
+/// ファイルを主分別先に移動し、他の分別先にはリンクまたはコピーを作成する
+/// 途中で失敗した場合は作成済みの配置を削除して元に戻す
+#[tauri::command]
+pub fn fan_out_file(
+    src: String,
+    dest_folder: String,
+    extra_folders: Vec<String>,
+    mode: FanOutMode,
+) -> Result<FanOutResult, String> {
+    debug!(
+        "複数分別先への配置開始: {} -> {} (+{}件)",
+        src,
+        dest_folder,
+        extra_folders.len()
+    );
+
+    let dest_path = move_file(src.clone(), dest_folder.clone())?;
+    let mut extra_paths = Vec::new();
+
+    for folder in &extra_folders {
+        if *folder == dest_folder {
+            continue;
+        }
+        match place_extra(&dest_path, folder, mode) {
+            Ok(path) => extra_paths.push(path),
+            Err(e) => {
+                error!(
+                    "追加の分別先への配置エラー: {} -> {}: {}",
+                    dest_path, folder, e
+                );
+                for path in &extra_paths {
+                    let _ = fs::remove_file(path);
+                }
+                match fs::rename(&dest_path, &src) {
+                    Ok(()) => record_moves(&[(&dest_path, &src)]),
+                    Err(rollback) => error!(
+                        "配置の取り消しに失敗: {} -> {}: {}",
+                        dest_path, src, rollback
+                    ),
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    info!(
+        "複数分別先への配置完了: {} -> {} (+{}件)",
+        src,
+        dest_path,
+        extra_paths.len()
+    );
+    Ok(FanOutResult {
+        dest_path,
+        extra_paths,
+    })
+}
+
+/// 複数分別先への配置を元に戻す（追加分を削除してから主分別先のファイルを戻す）
+#[tauri::command]
+pub fn undo_fan_out(
+    current_path: String,
+    original_folder: String,
+    extra_paths: Vec<String>,
+) -> Result<String, String> {
+    debug!(
+        "Undo配置開始: {} (+{}件) -> {}",
+        current_path,
+        extra_paths.len(),
+        original_folder
+    );
+
+    // 主分別先のファイルがない場合は追加分も残す
+    if !Path::new(&current_path).exists() {
+        error!("Undoファイルが見つかりません: {}", current_path);
+        return Err(format!("File not found: {}", current_path));
+    }
+
+    for path in &extra_paths {
+        match fs::remove_file(path) {
+            Ok(()) => debug!("追加配置を削除: {}", path),
+            Err(e) => warn!("追加配置の削除に失敗: {}: {}", path, e),
+        }
+    }
+
+    undo_move(current_path, original_folder)
+}
+
 /// 指定フォルダ内の画像ファイルをスキャンして返す
 #[tauri::command]
-pub fn scan_images(path: String) -> Result<Vec<ImageInfo>, String> {
+pub fn scan_images(path: String, options: Option<ScanOptions>) -> Result<Vec<ImageInfo>, String> {
     debug!("画像スキャン開始: {}", path);
 
     let dir = Path::new(&path);
@@ -140,6 +272,14 @@ pub fn scan_images(path: String) -> Result<Vec<ImageInfo>, String> {
         if let Some(ext) = file_path.extension() {
             let ext_lower = ext.to_string_lossy().to_lowercase();
             if SUPPORTED_EXTENSIONS.contains(&ext_lower.as_str()) {
+                // 拡張属性のタグで処理済みのファイルを除外
+                if let Some(o) = options.as_ref().filter(|o| o.needs_tags()) {
+                    let tags = xattrs::read_tags(&file_path).unwrap_or_default();
+                    if o.excludes(tags.is_sorted(), &tags.tags) {
+                        continue;
+                    }
+                }
+
                 // メタデータを取得
                 let (size, modified_at) = match fs::metadata(&file_path) {
                     Ok(meta) => {
@@ -154,11 +294,25 @@ pub fn scan_images(path: String) -> Result<Vec<ImageInfo>, String> {
                     Err(_) => (None, None),
                 };
 
+                // 評価・色ラベルを使う場合のみ取得して絞り込む
+                let rating = match &options {
+                    Some(o) if o.needs_rating() => {
+                        let rating = xmp::read_rating_label(&file_path);
+                        if !o.accepts(rating.rating, rating.label) {
+                            continue;
+                        }
+                        Some(rating)
+                    }
+                    _ => None,
+                };
+
                 images.push(ImageInfo {
                     path: file_path.to_string_lossy().to_string(),
                     name: file_name,
                     size,
                     modified_at,
+                    rating: rating.map(|r| r.rating),
+                    label: rating.and_then(|r| r.label),
                 });
             }
         }
@@ -166,6 +320,12 @@ pub fn scan_images(path: String) -> Result<Vec<ImageInfo>, String> {
 
     // 自然順ソート
     images.sort_by(|a, b| natord::compare(&a.name, &b.name));
+    match options.map(|o| o.sort_by).unwrap_or_default() {
+        ScanSort::Name => {}
+        ScanSort::Rating => images.sort_by_key(|image| std::cmp::Reverse(image.rating)),
+        // None < Some のため、ラベルなしを最後にする
+        ScanSort::Label => images.sort_by_key(|image| (image.label.is_none(), image.label)),
+    }
 
     info!("画像スキャン完了: {} - {}枚の画像を検出", path, images.len());
     Ok(images)
@@ -174,6 +334,7 @@ pub fn scan_images(path: String) -> Result<Vec<ImageInfo>, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::options::XmpLocation;
     use std::fs::File;
     use tempfile::tempdir;
 
@@ -188,7 +349,7 @@ mod tests {
         File::create(dir.path().join("image3.gif")).unwrap();
 
         // Act: scan_images を実行
-        let result = scan_images(dir.path().to_string_lossy().to_string());
+        let result = scan_images(dir.path().to_string_lossy().to_string(), None);
 
         // Assert: 3つの画像が見つかる
         assert!(result.is_ok());
@@ -201,7 +362,7 @@ mod tests {
         let dir = tempdir().unwrap();
 
         // Act: scan_images を実行
-        let result = scan_images(dir.path().to_string_lossy().to_string());
+        let result = scan_images(dir.path().to_string_lossy().to_string(), None);
 
         // Assert: 空のVecが返る
         assert!(result.is_ok());
@@ -218,7 +379,7 @@ mod tests {
         File::create(dir.path().join("data.txt")).unwrap();
 
         // Act: scan_images を実行
-        let result = scan_images(dir.path().to_string_lossy().to_string());
+        let result = scan_images(dir.path().to_string_lossy().to_string(), None);
 
         // Assert: jpgのみ検出
         assert!(result.is_ok());
@@ -235,7 +396,7 @@ mod tests {
         File::create(dir.path().join("image3.GIF")).unwrap();
 
         // Act: scan_images を実行
-        let result = scan_images(dir.path().to_string_lossy().to_string());
+        let result = scan_images(dir.path().to_string_lossy().to_string(), None);
 
         // Assert: 全て検出される
         assert!(result.is_ok());
@@ -252,7 +413,7 @@ mod tests {
         File::create(dir.path().join("img1.jpg")).unwrap();
 
         // Act: scan_images を実行
-        let result = scan_images(dir.path().to_string_lossy().to_string()).unwrap();
+        let result = scan_images(dir.path().to_string_lossy().to_string(), None).unwrap();
 
         // Assert: 自然順でソートされる (img1, img2, img10)
         assert_eq!(result[0].name, "img1.jpg");
@@ -269,7 +430,7 @@ mod tests {
         File::create(dir.path().join(".hidden.jpg")).unwrap();
 
         // Act: scan_images を実行
-        let result = scan_images(dir.path().to_string_lossy().to_string());
+        let result = scan_images(dir.path().to_string_lossy().to_string(), None);
 
         // Assert: 隠しファイルはスキップ
         assert!(result.is_ok());
@@ -291,7 +452,7 @@ mod tests {
         symlink(&target, &link).unwrap();
 
         // Act: scan_images を実行
-        let result = scan_images(dir.path().to_string_lossy().to_string());
+        let result = scan_images(dir.path().to_string_lossy().to_string(), None);
 
         // Assert: シンボリックリンクはスキップされ、通常ファイルのみ
         assert!(result.is_ok());
@@ -424,7 +585,7 @@ mod tests {
         File::create(dir.path().join("✨キラキラ✨.gif")).unwrap();
 
         // Act: scan_images を実行
-        let result = scan_images(dir.path().to_string_lossy().to_string());
+        let result = scan_images(dir.path().to_string_lossy().to_string(), None);
 
         // Assert: 全て検出される
         assert!(result.is_ok());
@@ -539,7 +700,7 @@ mod tests {
         File::create(dir.path().join("【重要】ファイル.gif")).unwrap();
 
         // Act: scan_images を実行
-        let result = scan_images(dir.path().to_string_lossy().to_string());
+        let result = scan_images(dir.path().to_string_lossy().to_string(), None);
 
         // Assert: 全て正しく検出
         assert!(result.is_ok());
@@ -551,4 +712,202 @@ mod tests {
         assert!(names.contains(&"café.png"));
         assert!(names.contains(&"【重要】ファイル.gif"));
     }
+
+    // ===== fan_out_file tests =====
+
+    #[test]
+    fn test_fan_out_file_hard_link() {
+        // Arrange: ソースと3つの分別先
+        let src_dir = tempdir().unwrap();
+        let dest1 = tempdir().unwrap();
+        let dest2 = tempdir().unwrap();
+        let dest3 = tempdir().unwrap();
+
+        let src_path = src_dir.path().join("test.jpg");
+        fs::write(&src_path, b"image").unwrap();
+
+        // Act: 主分別先へ移動し、残りにハードリンク
+        let result = fan_out_file(
+            src_path.to_string_lossy().to_string(),
+            dest1.path().to_string_lossy().to_string(),
+            vec![
+                dest2.path().to_string_lossy().to_string(),
+                dest3.path().to_string_lossy().to_string(),
+            ],
+            FanOutMode::HardLink,
+        )
+        .unwrap();
+
+        // Assert: 全ての分別先に同じ内容のファイルがある
+        assert!(!src_path.exists());
+        assert!(dest1.path().join("test.jpg").exists());
+        assert_eq!(result.extra_paths.len(), 2);
+        for path in &result.extra_paths {
+            assert_eq!(fs::read(path).unwrap(), b"image");
+        }
+    }
+
+    #[test]
+    fn test_fan_out_file_rolls_back_on_failure() {
+        // Arrange: 追加の分別先が存在しない
+        let src_dir = tempdir().unwrap();
+        let dest1 = tempdir().unwrap();
+        let dest2 = tempdir().unwrap();
+
+        let src_path = src_dir.path().join("test.jpg");
+        File::create(&src_path).unwrap();
+
+        // Act
+        let result = fan_out_file(
+            src_path.to_string_lossy().to_string(),
+            dest1.path().to_string_lossy().to_string(),
+            vec![
+                dest2.path().to_string_lossy().to_string(),
+                "/nonexistent/folder".to_string(),
+            ],
+            FanOutMode::Copy,
+        );
+
+        // Assert: エラーになり、元の状態に戻る
+        assert!(result.is_err());
+        assert!(src_path.exists());
+        assert!(!dest1.path().join("test.jpg").exists());
+        assert!(!dest2.path().join("test.jpg").exists());
+    }
+
+    #[test]
+    fn test_undo_fan_out() {
+        // Arrange: コピーで配置済み
+        let src_dir = tempdir().unwrap();
+        let dest1 = tempdir().unwrap();
+        let dest2 = tempdir().unwrap();
+
+        let src_path = src_dir.path().join("test.jpg");
+        File::create(&src_path).unwrap();
+        let placed = fan_out_file(
+            src_path.to_string_lossy().to_string(),
+            dest1.path().to_string_lossy().to_string(),
+            vec![dest2.path().to_string_lossy().to_string()],
+            FanOutMode::Copy,
+        )
+        .unwrap();
+
+        // Act: まとめて元に戻す
+        let result = undo_fan_out(
+            placed.dest_path,
+            src_dir.path().to_string_lossy().to_string(),
+            placed.extra_paths,
+        );
+
+        // Assert: 元の場所に戻り、追加分は削除される
+        assert!(result.is_ok());
+        assert!(src_path.exists());
+        assert!(!dest1.path().join("test.jpg").exists());
+        assert!(!dest2.path().join("test.jpg").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_images_hides_tagged_files() {
+        // Arrange: 仕分け済み・タグ付き・未処理のファイル
+        let dir = tempdir().unwrap();
+        let sorted = dir.path().join("sorted.jpg");
+        let tagged = dir.path().join("tagged.jpg");
+        File::create(&sorted).unwrap();
+        File::create(&tagged).unwrap();
+        File::create(dir.path().join("new.jpg")).unwrap();
+
+        let sorted_attrs = [("key".to_string(), "1".to_string())].into_iter().collect();
+        if xattrs::write_tags(&sorted, &[], &sorted_attrs).is_err() {
+            // 拡張属性に対応していないファイルシステム
+            return;
+        }
+        xattrs::write_tags(&tagged, &["done".to_string()], &Default::default()).unwrap();
+
+        // Act
+        let options = ScanOptions {
+            hide_sorted: true,
+            exclude_tags: vec!["done".to_string()],
+            ..Default::default()
+        };
+        let all = scan_images(dir.path().to_string_lossy().to_string(), None).unwrap();
+        let filtered =
+            scan_images(dir.path().to_string_lossy().to_string(), Some(options)).unwrap();
+
+        // Assert: 未処理のファイルのみ残る
+        assert_eq!(all.len(), 3);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "new.jpg");
+    }
+
+    #[test]
+    fn test_scan_images_filters_and_sorts_by_rating() {
+        // Arrange: 評価・色ラベルをサイドカーに持つファイル
+        let dir = tempdir().unwrap();
+        for (name, rating, label) in [
+            ("a.jpg", 2, Some(ColorLabel::Red)),
+            ("b.jpg", 5, None),
+            ("c.jpg", 4, Some(ColorLabel::Green)),
+            ("d.jpg", 0, Some(ColorLabel::Red)),
+        ] {
+            let path = dir.path().join(name);
+            File::create(&path).unwrap();
+            xmp::write_rating(&path, rating, XmpLocation::Sidecar).unwrap();
+            xmp::write_label(&path, label, XmpLocation::Sidecar).unwrap();
+        }
+        let scan = |options: ScanOptions| {
+            scan_images(dir.path().to_string_lossy().to_string(), Some(options))
+                .unwrap()
+                .into_iter()
+                .map(|image| image.name)
+                .collect::<Vec<_>>()
+        };
+
+        // Act
+        let by_rating = scan(ScanOptions {
+            min_rating: 2,
+            sort_by: ScanSort::Rating,
+            ..Default::default()
+        });
+        let by_label = scan(ScanOptions {
+            sort_by: ScanSort::Label,
+            ..Default::default()
+        });
+        let red = scan(ScanOptions {
+            labels: vec![ColorLabel::Red],
+            ..Default::default()
+        });
+
+        // Assert
+        assert_eq!(by_rating, vec!["b.jpg", "c.jpg", "a.jpg"]);
+        assert_eq!(by_label, vec!["a.jpg", "d.jpg", "c.jpg", "b.jpg"]);
+        assert_eq!(red, vec!["a.jpg", "d.jpg"]);
+    }
+
+    #[test]
+    fn test_scan_images_skips_rating_when_unused() {
+        // Arrange: 評価をサイドカーに持つファイル
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.jpg");
+        File::create(&path).unwrap();
+        xmp::write_rating(&path, 3, XmpLocation::Sidecar).unwrap();
+        let dir_path = dir.path().to_string_lossy().to_string();
+
+        // Act
+        let plain = scan_images(dir_path.clone(), None).unwrap();
+        let by_name = scan_images(dir_path.clone(), Some(ScanOptions::default())).unwrap();
+        let filtered = scan_images(
+            dir_path,
+            Some(ScanOptions {
+                min_rating: 1,
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        // Assert: 絞り込み・並べ替えに使わない場合は読み込まない
+        assert_eq!(plain[0].rating, None);
+        assert_eq!(by_name[0].rating, None);
+        assert_eq!(filtered[0].rating, Some(3));
+    }
 }
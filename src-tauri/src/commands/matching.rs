@@ -0,0 +1,471 @@
+use crate::commands::classifier::{learned_suggestions, LearnedSuggestion};
+use crate::config::options::MatchSource;
+use crate::config::settings::{load_settings, Settings, WordList};
+use crate::matching::explain::{self, MatchExplanation};
+use crate::matching::lint::{self, LintIssue, MergeResult, MergeStrategy};
+use crate::matching::mining::{self, AliasMiningReport, MiningConfig};
+use crate::matching::word_list::{append_aliases_to_csv, load_word_list_file, AliasAddition};
+use crate::matching::{collect_targets, match_targets, MatchItem, MatchRule, MatchingConfig};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use tracing::{debug, error, info, warn};
+
+/// 学習による提案を単語リストの候補より上位にする確信度の下限
+const MIN_LEARNED_CONFIDENCE: f64 = 0.3;
+
+/// 提案の根拠
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SuggestionOrigin {
+    /// 単語リストによるマッチング
+    WordList,
+    /// 分別操作からの学習
+    Learned,
+}
+
+/// 単語リストでマッチした内容
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestionMatch {
+    pub entity_id: String,
+    pub canonical: String,
+    pub matched_key: String,
+    pub rule: MatchRule,
+    pub score: f64,
+    pub source: MatchSource,
+    /// 派生キー（ローマ字・読み等）による一致か
+    pub derived: bool,
+}
+
+/// 分別先の提案
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DestinationSuggestion {
+    /// 分別先キー（"0"〜"9"）
+    pub key: String,
+    pub destination: String,
+    pub origin: SuggestionOrigin,
+    /// 確定マッチによる提案か（false の場合は候補のみ、または学習による提案）
+    pub confirmed: bool,
+    /// 学習による提案の確信度（0.0〜1.0）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// 単語リストによる提案のマッチ内容
+    #[serde(flatten)]
+    pub matched: Option<SuggestionMatch>,
+}
+
+impl DestinationSuggestion {
+    fn new(key: &str, destination: &str, item: MatchItem, confirmed: bool) -> Self {
+        Self {
+            key: key.to_string(),
+            destination: destination.to_string(),
+            origin: SuggestionOrigin::WordList,
+            confirmed,
+            confidence: None,
+            matched: Some(SuggestionMatch {
+                entity_id: item.entity_id,
+                canonical: item.canonical,
+                matched_key: item.matched_key,
+                rule: item.rule,
+                score: item.score,
+                source: item.source,
+                derived: item.derived,
+            }),
+        }
+    }
+
+    fn learned(suggestion: LearnedSuggestion) -> Self {
+        Self {
+            key: suggestion.key,
+            destination: suggestion.destination,
+            origin: SuggestionOrigin::Learned,
+            confirmed: false,
+            confidence: Some(suggestion.confidence),
+            matched: None,
+        }
+    }
+
+    /// 並び順の段階（確定マッチ → 確信度の高い学習 → 候補 → その他の学習）
+    fn rank(&self) -> u8 {
+        match (self.origin, self.confirmed) {
+            (SuggestionOrigin::WordList, true) => 0,
+            (SuggestionOrigin::Learned, _)
+                if self.confidence.unwrap_or(0.0) >= MIN_LEARNED_CONFIDENCE =>
+            {
+                1
+            }
+            (SuggestionOrigin::WordList, false) => 2,
+            (SuggestionOrigin::Learned, _) => 3,
+        }
+    }
+
+    /// 同じ段階内での並び順に使う値
+    fn weight(&self) -> f64 {
+        self.matched
+            .as_ref()
+            .map(|m| m.score)
+            .or(self.confidence)
+            .unwrap_or(0.0)
+    }
+}
+
+/// 各分別先の単語リストでマッチングし、提案をランク順に返す
+pub(crate) fn suggest_destinations(
+    path: &str,
+    settings: &Settings,
+    config: &MatchingConfig,
+) -> Vec<DestinationSuggestion> {
+    let mut suggestions = Vec::new();
+    let targets = collect_targets(path, &settings.matching_sources);
+
+    for (key, word_list) in &settings.word_lists {
+        let Some(word_list) = word_list else {
+            continue;
+        };
+        // 分別先が未設定のキーは提案しない
+        let Some(Some(destination)) = settings.destinations.get(key) else {
+            continue;
+        };
+
+        let result = match_targets(path, &targets, word_list, config);
+
+        // 確定マッチを優先し、なければ最上位の候補を使う
+        let best_confirmed = result
+            .confirmed
+            .into_iter()
+            .max_by(|a, b| a.score.total_cmp(&b.score));
+        if let Some(item) = best_confirmed {
+            suggestions.push(DestinationSuggestion::new(key, destination, item, true));
+        } else if let Some(item) = result.candidates.into_iter().next() {
+            suggestions.push(DestinationSuggestion::new(key, destination, item, false));
+        }
+    }
+
+    sort_suggestions(suggestions)
+}
+
+/// 段階 → スコア・確信度の降順 → キー順に並べ、同じキーは上位の提案のみ残す
+fn sort_suggestions(mut suggestions: Vec<DestinationSuggestion>) -> Vec<DestinationSuggestion> {
+    suggestions.sort_by(|a, b| {
+        a.rank()
+            .cmp(&b.rank())
+            .then_with(|| b.weight().total_cmp(&a.weight()))
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    let mut seen = HashSet::new();
+    suggestions.retain(|s| seen.insert(s.key.clone()));
+    suggestions
+}
+
+/// 単語リストによる提案に学習による提案を合わせて並べ直す
+pub(crate) fn merge_learned(
+    suggestions: Vec<DestinationSuggestion>,
+    learned: Vec<LearnedSuggestion>,
+) -> Vec<DestinationSuggestion> {
+    sort_suggestions(
+        suggestions
+            .into_iter()
+            .chain(learned.into_iter().map(DestinationSuggestion::learned))
+            .collect(),
+    )
+}
+
+/// ファイルに対する分別先の提案を返す（単語リストと学習結果の両方）
+#[tauri::command]
+pub fn suggest_destination(
+    path: String,
+    config_path: String,
+) -> Result<Vec<DestinationSuggestion>, String> {
+    let settings = load_settings(config_path.clone())?;
+    let suggestions = suggest_destinations(&path, &settings, &MatchingConfig::default());
+    // 学習結果が読めなくても単語リストによる提案は返す
+    let learned = learned_suggestions(&path, &config_path, &settings).unwrap_or_else(|e| {
+        warn!("学習による分別先提案に失敗: {}", e);
+        Vec::new()
+    });
+    let suggestions = merge_learned(suggestions, learned);
+
+    debug!("分別先提案: {} - {}件", path, suggestions.len());
+    Ok(suggestions)
+}
+
+/// 単語リストCSVを読み込み、正規化・派生キー生成を行う
+#[tauri::command]
+pub fn import_word_list(path: String) -> Result<WordList, String> {
+    let word_list = load_word_list_file(&path)?;
+    debug!(
+        "単語リスト読み込み: {} - {}件",
+        path,
+        word_list.entries.len()
+    );
+    Ok(word_list)
+}
+
+/// 未マッチのファイル名から単語リストへの別名追加候補を抽出する
+#[tauri::command]
+pub fn mine_aliases(paths: Vec<String>, config_path: String) -> Result<AliasMiningReport, String> {
+    let settings = load_settings(config_path)?;
+    let word_lists: BTreeMap<String, _> = settings
+        .word_lists
+        .iter()
+        .filter_map(|(key, list)| list.as_ref().map(|list| (key.clone(), list)))
+        .collect();
+
+    let report = mining::mine_aliases(&paths, &word_lists, &MiningConfig::default());
+
+    debug!(
+        "別名候補抽出: {}件中 提案{}件 / 頻出語{}件",
+        paths.len(),
+        report.proposals.len(),
+        report.frequent_terms.len()
+    );
+    Ok(report)
+}
+
+/// 確定した別名候補を単語リストの読み込み元CSVに追記し、読み込み直した単語リストを返す
+#[tauri::command]
+pub fn accept_aliases(
+    key: String,
+    aliases: Vec<AliasAddition>,
+    config_path: String,
+) -> Result<WordList, String> {
+    let settings = load_settings(config_path)?;
+    let Some(Some(word_list)) = settings.word_lists.get(&key) else {
+        return Err(format!("Word list not set: {}", key));
+    };
+    let Some(path) = word_list.source_path.as_deref() else {
+        return Err(format!(
+            "Word list has no source file: {}",
+            word_list.file_name
+        ));
+    };
+
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let (updated, added) = append_aliases_to_csv(&content, &aliases);
+    if added > 0 {
+        fs::write(path, updated).map_err(|e| {
+            error!("単語リストの書き込みエラー: {}: {}", path, e);
+            e.to_string()
+        })?;
+    }
+
+    info!("別名を単語リストに追加: {} - {}件", path, added);
+    load_word_list_file(path)
+}
+
+/// 単語リストを検査する（重複・キー衝突・短すぎるキー・空の別名・所属作品なし）
+#[tauri::command]
+pub fn lint_word_list(word_list: WordList) -> Result<Vec<LintIssue>, String> {
+    let issues = lint::lint_word_list(&word_list, &MatchingConfig::default());
+    debug!(
+        "単語リスト検査: {} - {}件",
+        word_list.file_name,
+        issues.len()
+    );
+    Ok(issues)
+}
+
+/// 2つの単語リストを統合する
+#[tauri::command]
+pub fn merge_word_lists(
+    base: WordList,
+    incoming: WordList,
+    strategy: MergeStrategy,
+) -> Result<MergeResult, String> {
+    let result = lint::merge_word_lists(&base, &incoming, strategy);
+    debug!(
+        "単語リスト統合: {} + {} - {}件 (衝突{}件)",
+        base.file_name,
+        incoming.file_name,
+        result.word_list.entries.len(),
+        result.conflicts.len()
+    );
+    Ok(result)
+}
+
+/// 単語リスト内の重複エントリを統合する
+#[tauri::command]
+pub fn dedupe_word_list(word_list: WordList) -> Result<MergeResult, String> {
+    let empty = WordList {
+        entries: Vec::new(),
+        ..word_list.clone()
+    };
+    Ok(lint::merge_word_lists(
+        &word_list,
+        &empty,
+        MergeStrategy::UnionAliases,
+    ))
+}
+
+/// 指定スロットの単語リストでのマッチング過程を返す（デバッグ用）
+#[tauri::command]
+pub fn explain_match(
+    path: String,
+    key: String,
+    config_path: String,
+) -> Result<MatchExplanation, String> {
+    let settings = load_settings(config_path)?;
+    let Some(Some(word_list)) = settings.word_lists.get(&key) else {
+        return Err(format!("Word list not set: {}", key));
+    };
+
+    let targets = collect_targets(&path, &settings.matching_sources);
+    let explanation =
+        explain::explain_match(&path, &targets, word_list, &MatchingConfig::default());
+    debug!(
+        "マッチング説明: {} - ターゲット{}件 / 除外{}件",
+        path,
+        explanation.targets.len(),
+        explanation.dropped.len()
+    );
+    Ok(explanation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::{save_settings, WordList, WordListEntry};
+    use crate::matching::normalize::normalize;
+    use tempfile::tempdir;
+
+    fn word_list(entries: &[(&str, &str)]) -> WordList {
+        WordList {
+            file_name: "list.csv".to_string(),
+            source_path: None,
+            entries: entries
+                .iter()
+                .map(|(entry_type, canonical)| WordListEntry {
+                    entry_type: entry_type.to_string(),
+                    canonical: canonical.to_string(),
+                    aliases: vec![],
+                    canonical_normalized: normalize(canonical),
+                    aliases_normalized: vec![],
+                    search_keys_normalized: vec![normalize(canonical)],
+                    derived_keys_normalized: vec![],
+                    work_id: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn settings_with_lists() -> Settings {
+        let mut settings = Settings::default();
+        settings
+            .destinations
+            .insert("1".to_string(), Some("/dest/aria".to_string()));
+        settings
+            .destinations
+            .insert("2".to_string(), Some("/dest/leon".to_string()));
+        settings.word_lists.insert(
+            "1".to_string(),
+            Some(word_list(&[("character", "Aria Starlight")])),
+        );
+        settings.word_lists.insert(
+            "2".to_string(),
+            Some(word_list(&[("character", "Leon Blaze")])),
+        );
+        // 分別先が未設定のスロット
+        settings.word_lists.insert(
+            "3".to_string(),
+            Some(word_list(&[("character", "Leon Blaze")])),
+        );
+        settings
+    }
+
+    #[test]
+    fn test_suggest_destinations_ranks_confirmed() {
+        let settings = settings_with_lists();
+
+        let suggestions = suggest_destinations(
+            "/inbox/leon_blaze_01.jpg",
+            &settings,
+            &MatchingConfig::default(),
+        );
+
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].key, "2");
+        assert_eq!(suggestions[0].destination, "/dest/leon");
+        assert!(suggestions[0].confirmed);
+        assert_eq!(suggestions[0].origin, SuggestionOrigin::WordList);
+        let matched = suggestions[0].matched.as_ref().unwrap();
+        assert_eq!(matched.canonical, "Leon Blaze");
+        assert_eq!(matched.rule, MatchRule::B);
+        assert_eq!(matched.source, MatchSource::FileName);
+    }
+
+    #[test]
+    fn test_merge_learned_ranks_after_confirmed() {
+        // Arrange: 単語リストでは 2 に確定、学習では 1 と 2 を提案
+        let settings = settings_with_lists();
+        let suggestions = suggest_destinations(
+            "/inbox/leon_blaze_01.jpg",
+            &settings,
+            &MatchingConfig::default(),
+        );
+        let learned = |key: &str, confidence| LearnedSuggestion {
+            key: key.to_string(),
+            destination: format!("/dest/{}", key),
+            confidence,
+        };
+
+        // Act
+        let merged = merge_learned(suggestions, vec![learned("1", 0.8), learned("2", 0.2)]);
+
+        // Assert: 確定マッチが先、同じキーの学習による提案は除く
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].key, "2");
+        assert_eq!(merged[0].origin, SuggestionOrigin::WordList);
+        assert_eq!(merged[1].key, "1");
+        assert_eq!(merged[1].origin, SuggestionOrigin::Learned);
+        assert_eq!(merged[1].confidence, Some(0.8));
+    }
+
+    #[test]
+    fn test_suggest_destinations_no_match() {
+        let settings = settings_with_lists();
+
+        let suggestions =
+            suggest_destinations("/inbox/IMG_0001.jpg", &settings, &MatchingConfig::default());
+
+        assert!(suggestions.is_empty());
+    }
+
+    #[test]
+    fn test_accept_aliases_rewrites_source_csv() {
+        // Arrange: 読み込み元CSVのある単語リストを設定
+        let dir = tempdir().unwrap();
+        let csv_path = dir.path().join("list.csv");
+        fs::write(&csv_path, "アリア,Aria Starlight\n").unwrap();
+        let csv_path = csv_path.to_string_lossy().to_string();
+        let mut settings = Settings::default();
+        settings.word_lists.insert(
+            "1".to_string(),
+            Some(load_word_list_file(&csv_path).unwrap()),
+        );
+        settings
+            .word_lists
+            .insert("2".to_string(), Some(word_list(&[])));
+        let config_path = dir.path().join("config.json");
+        let config_path = config_path.to_string_lossy().to_string();
+        save_settings(settings, config_path.clone()).unwrap();
+        let aliases = vec![AliasAddition {
+            entry_type: "character".to_string(),
+            canonical: "アリア".to_string(),
+            alias: "aria star".to_string(),
+        }];
+
+        // Act
+        let list = accept_aliases("1".to_string(), aliases.clone(), config_path.clone()).unwrap();
+
+        // Assert
+        assert_eq!(list.entries[0].aliases, vec!["Aria Starlight", "aria star"]);
+        assert_eq!(
+            fs::read_to_string(&csv_path).unwrap(),
+            "アリア,Aria Starlight,,aria star\n"
+        );
+        // 読み込み元CSVのない単語リストには追加できない
+        assert!(accept_aliases("2".to_string(), aliases, config_path).is_err());
+    }
+}
@@ -0,0 +1,32 @@
+use crate::metadata::ai::{read_ai_metadata as read_ai, AiMetadata};
+use crate::metadata::exif::{read_exif_metadata, ExifMetadata};
+use std::path::Path;
+use tracing::debug;
+
+/// 画像生成メタデータ（プロンプト・モデル・シード等）を読み込む
+/// 生成メタデータを持たない画像の場合は None を返す
+#[tauri::command]
+pub fn read_ai_metadata(path: String) -> Result<Option<AiMetadata>, String> {
+    let file_path = Path::new(&path);
+    if !file_path.exists() {
+        return Err(format!("File not found: {}", path));
+    }
+
+    let meta = read_ai(file_path);
+    debug!("生成メタデータ読み込み: {} - {}", path, meta.is_some());
+    Ok(meta)
+}
+
+/// 撮影情報（カメラ・レンズ・露出・撮影日時・GPS 等）を読み込む
+/// EXIF を持たない画像の場合は None を返す
+#[tauri::command]
+pub fn read_metadata(path: String) -> Result<Option<ExifMetadata>, String> {
+    let file_path = Path::new(&path);
+    if !file_path.exists() {
+        return Err(format!("File not found: {}", path));
+    }
+
+    let meta = read_exif_metadata(file_path);
+    debug!("撮影情報読み込み: {} - {}", path, meta.is_some());
+    Ok(meta)
+}
@@ -1,3 +1,9 @@
+pub mod auto_sort;
+pub mod classifier;
 pub mod file_ops;
+pub mod matching;
+pub mod metadata;
+pub mod tagging;
 pub mod watcher;
+pub mod word_list_watcher;
 pub mod thumbnail;
@@ -0,0 +1,325 @@
+use crate::config::options::{ColorLabel, XmpLocation};
+use crate::config::settings::{load_settings, Settings};
+use crate::matching::{collect_targets, match_targets, MatchingConfig};
+use crate::metadata::xattrs::{self, FileTags, TagChange};
+use crate::metadata::xmp::{self, RatingLabel};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+
+/// タグ付けの結果（Undo履歴に積むための情報を含む）
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TagResult {
+    pub path: String,
+    /// 分別先キー（"0"〜"9"）
+    pub key: String,
+    /// 実際に追加したキーワード（Undo時はこれだけを取り除く）
+    pub keywords: Vec<String>,
+    pub location: XmpLocation,
+    /// サイドカーファイルを新規作成したか
+    pub sidecar_created: bool,
+}
+
+/// 評価・色ラベルの書き込み結果
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingResult {
+    pub path: String,
+    /// 書き込み後の評価（0〜5）
+    pub rating: u8,
+    /// 書き込み後の色ラベル
+    pub label: Option<ColorLabel>,
+    /// 実際の書き込み先（埋め込みに対応していない形式はサイドカー）
+    pub location: XmpLocation,
+}
+
+/// 分別先キーのラベル（未設定なら分別先フォルダ名）
+fn slot_label(key: &str, settings: &Settings) -> Option<String> {
+    if let Some(label) = settings
+        .tag_labels
+        .get(key)
+        .filter(|l| !l.trim().is_empty())
+    {
+        return Some(label.trim().to_string());
+    }
+    let destination = settings.destinations.get(key)?.as_ref()?;
+    Path::new(&destination.replace('\\', "/"))
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+}
+
+/// 書き込むキーワード（ラベル + 単語リストで確定した項目名）
+pub(crate) fn tag_keywords(
+    path: &str,
+    key: &str,
+    settings: &Settings,
+    config: &MatchingConfig,
+) -> Result<Vec<String>, String> {
+    let label = slot_label(key, settings).ok_or_else(|| format!("Tag label not set: {}", key))?;
+    let mut keywords = vec![label];
+
+    if let Some(Some(word_list)) = settings.word_lists.get(key) {
+        let targets = collect_targets(path, &settings.matching_sources);
+        let result = match_targets(path, &targets, word_list, config);
+        for item in result.confirmed {
+            if !keywords.contains(&item.canonical) {
+                keywords.push(item.canonical);
+            }
+        }
+    }
+    Ok(keywords)
+}
+
+/// ファイルを移動せず、分別先キーのラベルと確定した項目名を XMP キーワードとして書き込む
+#[tauri::command]
+pub fn tag_file(path: String, key: String, config_path: String) -> Result<TagResult, String> {
+    if !Path::new(&path).exists() {
+        error!("タグ付け対象が見つかりません: {}", path);
+        return Err(format!("File not found: {}", path));
+    }
+
+    let settings = load_settings(config_path)?;
+    let keywords = tag_keywords(&path, &key, &settings, &MatchingConfig::default())?;
+    let change =
+        xmp::add_keywords(Path::new(&path), &keywords, settings.tag_location).map_err(|e| {
+            error!("タグ書き込みエラー: {}: {}", path, e);
+            e.to_string()
+        })?;
+
+    info!("タグ付け完了: {} - {:?}", path, change.keywords);
+    Ok(TagResult {
+        path,
+        key,
+        keywords: change.keywords,
+        location: change.location,
+        sidecar_created: change.sidecar_created,
+    })
+}
+
+/// タグ付けを元に戻す（追加したキーワードのみ取り除く）
+#[tauri::command]
+pub fn untag_file(
+    path: String,
+    keywords: Vec<String>,
+    location: XmpLocation,
+    sidecar_created: bool,
+) -> Result<Vec<String>, String> {
+    let removed = xmp::remove_keywords(Path::new(&path), &keywords, location, sidecar_created)
+        .map_err(|e| {
+            error!("タグ削除エラー: {}: {}", path, e);
+            e.to_string()
+        })?;
+
+    info!("タグ付けを元に戻しました: {} - {:?}", path, removed);
+    Ok(removed)
+}
+
+/// 拡張属性のタグを読み込む（user.xdg.tags / user.picsort.*）
+#[tauri::command]
+pub fn read_file_tags(path: String) -> Result<FileTags, String> {
+    xattrs::read_tags(Path::new(&path)).map_err(|e| e.to_string())
+}
+
+/// 拡張属性にタグを追加し、picSort 属性を設定する（実際に追加したものと上書き前の値を返す）
+#[tauri::command]
+pub fn write_file_tags(
+    path: String,
+    tags: Vec<String>,
+    attributes: BTreeMap<String, String>,
+) -> Result<TagChange, String> {
+    xattrs::write_tags(Path::new(&path), &tags, &attributes).map_err(|e| {
+        error!("拡張属性の書き込みエラー: {}: {}", path, e);
+        e.to_string()
+    })
+}
+
+/// 拡張属性からタグと picSort 属性を取り除く（previous にある属性は以前の値に戻し、残った属性を返す）
+#[tauri::command]
+pub fn remove_file_tags(
+    path: String,
+    tags: Vec<String>,
+    attributes: Vec<String>,
+    previous: Option<BTreeMap<String, String>>,
+) -> Result<FileTags, String> {
+    let previous = previous.unwrap_or_default();
+    xattrs::remove_tags(Path::new(&path), &tags, &attributes, &previous).map_err(|e| {
+        error!("拡張属性の削除エラー: {}: {}", path, e);
+        e.to_string()
+    })
+}
+
+/// 移動したファイルに仕分け結果を拡張属性として記録する
+/// タグはラベルと確定した項目名、属性は分別先キー・分別先・分別元・仕分け日時
+#[tauri::command]
+pub fn record_sort_tags(
+    path: String,
+    key: String,
+    source_folder: String,
+    config_path: String,
+) -> Result<TagChange, String> {
+    let settings = load_settings(config_path)?;
+    let tags = tag_keywords(&path, &key, &settings, &MatchingConfig::default()).unwrap_or_default();
+
+    let mut attributes = BTreeMap::new();
+    if let Some(Some(destination)) = settings.destinations.get(&key) {
+        attributes.insert("destination".to_string(), destination.clone());
+    }
+    attributes.insert("source".to_string(), source_folder);
+    let sorted_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    attributes.insert("sorted_at".to_string(), sorted_at.to_string());
+    attributes.insert("key".to_string(), key);
+
+    let written = write_file_tags(path.clone(), tags, attributes)?;
+    info!("仕分け結果を拡張属性に記録: {} - {:?}", path, written.tags);
+    Ok(written)
+}
+
+/// 評価・色ラベルを読み込む（スキャン時に読み込まなかったファイルの表示用）
+#[tauri::command]
+pub fn read_rating_label(path: String) -> RatingLabel {
+    xmp::read_rating_label(Path::new(&path))
+}
+
+/// 書き込み後の評価・色ラベルを読み直して結果を返す
+fn rating_result(path: String, location: XmpLocation) -> RatingResult {
+    let current = xmp::read_rating_label(Path::new(&path));
+    RatingResult {
+        path,
+        rating: current.rating,
+        label: current.label,
+        location,
+    }
+}
+
+/// 評価（0〜5、0 は未評価）を XMP の xmp:Rating に書き込む
+#[tauri::command]
+pub fn set_rating(path: String, rating: u8, location: XmpLocation) -> Result<RatingResult, String> {
+    if rating > 5 {
+        return Err(format!("Invalid rating: {}", rating));
+    }
+    if !Path::new(&path).exists() {
+        error!("評価の対象が見つかりません: {}", path);
+        return Err(format!("File not found: {}", path));
+    }
+
+    let location = xmp::write_rating(Path::new(&path), rating, location).map_err(|e| {
+        error!("評価の書き込みエラー: {}: {}", path, e);
+        e.to_string()
+    })?;
+
+    info!("評価を設定: {} - {}", path, rating);
+    Ok(rating_result(path, location))
+}
+
+/// 色ラベルを XMP の xmp:Label に書き込む（None の場合は取り除く）
+#[tauri::command]
+pub fn set_color_label(
+    path: String,
+    label: Option<ColorLabel>,
+    location: XmpLocation,
+) -> Result<RatingResult, String> {
+    if !Path::new(&path).exists() {
+        error!("色ラベルの対象が見つかりません: {}", path);
+        return Err(format!("File not found: {}", path));
+    }
+
+    let location = xmp::write_label(Path::new(&path), label, location).map_err(|e| {
+        error!("色ラベルの書き込みエラー: {}: {}", path, e);
+        e.to_string()
+    })?;
+
+    info!("色ラベルを設定: {} - {:?}", path, label);
+    Ok(rating_result(path, location))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::save_settings;
+    use crate::matching::word_list::parse_word_list_csv;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_tag_file_and_undo() {
+        // Arrange: 分別先と単語リストを設定
+        let dir = tempdir().unwrap();
+        let image = dir.path().join("aria_starlight_01.gif");
+        fs::write(&image, b"GIF89a").unwrap();
+
+        let mut settings = Settings::default();
+        settings
+            .destinations
+            .insert("1".to_string(), Some("/archive/Stellar".to_string()));
+        settings.word_lists.insert(
+            "1".to_string(),
+            Some(parse_word_list_csv("アリア,Aria Starlight", "list.csv")),
+        );
+        // GIF は埋め込めないためサイドカーに書き込まれる
+        settings.tag_location = XmpLocation::Embedded;
+        let config_path = dir.path().join("config.json");
+        let config_path = config_path.to_string_lossy().to_string();
+        save_settings(settings, config_path.clone()).unwrap();
+        let path = image.to_string_lossy().to_string();
+
+        // Act
+        let result = tag_file(path.clone(), "1".to_string(), config_path).unwrap();
+
+        // Assert: ラベル（フォルダ名）と項目名がサイドカーに書き込まれる
+        assert_eq!(result.keywords, vec!["Stellar", "アリア"]);
+        assert!(result.sidecar_created);
+        assert_eq!(result.location, XmpLocation::Sidecar);
+        assert!(image.exists());
+        assert_eq!(xmp::read_subjects(&image), vec!["Stellar", "アリア"]);
+
+        // Act: 元に戻す
+        let removed = untag_file(path, result.keywords, result.location, true).unwrap();
+
+        // Assert
+        assert_eq!(removed.len(), 2);
+        assert!(!xmp::sidecar_path(&image).exists());
+    }
+
+    #[test]
+    fn test_tag_keywords_prefers_custom_label() {
+        let mut settings = Settings::default();
+        settings
+            .tag_labels
+            .insert("2".to_string(), "Keep".to_string());
+
+        let keywords = tag_keywords("/a/b.jpg", "2", &settings, &MatchingConfig::default());
+
+        assert_eq!(keywords.unwrap(), vec!["Keep"]);
+        assert!(tag_keywords("/a/b.jpg", "3", &settings, &MatchingConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_set_rating_and_label() {
+        // Arrange
+        let dir = tempdir().unwrap();
+        let image = dir.path().join("photo.gif");
+        fs::write(&image, b"GIF89a").unwrap();
+        let path = image.to_string_lossy().to_string();
+
+        // Act
+        set_rating(path.clone(), 4, XmpLocation::Embedded).unwrap();
+        let result = set_color_label(
+            path.clone(),
+            Some(ColorLabel::Yellow),
+            XmpLocation::Embedded,
+        )
+        .unwrap();
+
+        // Assert: GIF はサイドカーに書き込まれる
+        assert_eq!(result.rating, 4);
+        assert_eq!(result.label, Some(ColorLabel::Yellow));
+        assert_eq!(result.location, XmpLocation::Sidecar);
+        assert!(set_rating(path, 6, XmpLocation::Sidecar).is_err());
+    }
+}
@@ -1,10 +1,26 @@
+use crate::config::options::{AlphaFormat, AnimationLimits, ThumbnailOptions};
+use crate::metadata::exif::read_orientation;
+use crate::thumbnail::animation::{is_animation_candidate, write_preview, AnimationInfo};
+use crate::thumbnail::cache::{CacheStats, ThumbnailCache};
+use crate::thumbnail::content::{partial_hash, SourceStamp};
+use crate::thumbnail::decode::open_for_thumbnail;
+use crate::thumbnail::queue::{JobQueue, Priority, ReprioritizeSummary};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::metadata::Orientation;
+use image::DynamicImage;
 use once_cell::sync::Lazy;
 use serde::Serialize;
-use sha2::{Digest, Sha256};
-use std::fs;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Read};
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use tauri::Manager;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
@@ -14,6 +30,62 @@ use tracing::{debug, info, warn};
 pub struct ThumbnailResult {
     pub original_path: String,
     pub thumbnail_path: String,
+    /// サムネイルの形式（image/jpeg, image/png, image/webp）
+    pub mime_type: String,
+}
+
+/// 動くプレビューの生成結果
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimatedPreview {
+    pub original_path: String,
+    pub preview_path: String,
+    pub mime_type: String,
+    #[serde(flatten)]
+    pub info: AnimationInfo,
+}
+
+/// サムネイルファイルの形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Jpeg,
+    Png,
+    Webp,
+}
+
+impl OutputFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+            Self::Webp => "webp",
+        }
+    }
+
+    fn mime_type(self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+            Self::Webp => "image/webp",
+        }
+    }
+
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") => Self::Png,
+            Some("webp") => Self::Webp,
+            _ => Self::Jpeg,
+        }
+    }
+}
+
+impl From<AlphaFormat> for OutputFormat {
+    fn from(format: AlphaFormat) -> Self {
+        match format {
+            AlphaFormat::Webp => Self::Webp,
+            AlphaFormat::Png => Self::Png,
+        }
+    }
 }
 
 /// バッチ生成結果
@@ -22,25 +94,135 @@ pub struct ThumbnailResult {
 pub struct ThumbnailBatchResult {
     pub results: Vec<ThumbnailResult>,
     pub errors: Vec<ThumbnailError>,
+    /// 途中でキャンセルされたか
+    pub cancelled: bool,
 }
 
 /// サムネイル生成エラー
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ThumbnailError {
     pub path: String,
     pub error: String,
 }
 
+/// バッチ生成の進捗（1件完了するごとに "thumbnail-batch-progress" イベントで送信）
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailBatchProgress {
+    pub batch_id: Option<String>,
+    pub completed: usize,
+    pub total: usize,
+    pub result: Option<ThumbnailResult>,
+    pub error: Option<ThumbnailError>,
+}
+
 const VIDEO_EXTENSIONS: [&str; 6] = ["mp4", "webm", "mov", "mkv", "avi", "ogv"];
 
+/// 同時に生成するサムネイルの上限（画像: CPUコア数、動画: ffmpeg が重いためより少なく）
+fn worker_limits(cores: usize) -> (usize, usize) {
+    (cores.max(1), (cores / 4).clamp(1, 2))
+}
+
+fn available_cores() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// サムネイル生成の待ち行列（画像・動画それぞれの同時生成数まで実行する）
+static QUEUE: Lazy<JobQueue> = Lazy::new(|| {
+    let (image_limit, video_limit) = worker_limits(available_cores());
+    JobQueue::new(image_limit, video_limit)
+});
+
 /// アプリ全体で共有するキャンセルトークン
 static CANCEL_TOKEN: Lazy<CancellationToken> = Lazy::new(CancellationToken::new);
 
+/// バッチ・リクエストごとのキャンセルトークン（ID → 登録番号, CANCEL_TOKEN の子トークン）
+static TASKS: Lazy<Mutex<HashMap<String, (u64, CancellationToken)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_TASK: AtomicU64 = AtomicU64::new(0);
+
+/// キャンセル時のエラーメッセージ
+const CANCELLED: &str = "Thumbnail generation cancelled";
+
+/// ffmpeg の終了を確認する間隔
+const PROCESS_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// サムネイルキャッシュのアクセス記録（キャッシュディレクトリごとに1つ）
+static CACHE: Lazy<Mutex<Option<ThumbnailCache>>> = Lazy::new(|| Mutex::new(None));
+
 /// アプリ終了時に呼び出す（lib.rsから）
 pub fn cancel_all_tasks() {
     info!("Cancelling all thumbnail generation tasks");
     CANCEL_TOKEN.cancel();
+
+    // アクセス記録を保存
+    if let Ok(mut cache) = CACHE.lock() {
+        if let Some(cache) = cache.as_mut() {
+            if let Err(e) = cache.save() {
+                warn!("Failed to save thumbnail cache index: {}", e);
+            }
+        }
+    }
+}
+
+/// 登録中のタスク（破棄すると登録を外す）
+struct TaskGuard {
+    id: Option<String>,
+    serial: u64,
+    token: CancellationToken,
+}
+
+impl TaskGuard {
+    /// キャンセルトークンを作成し、ID があれば登録する
+    /// アプリ終了時には CANCEL_TOKEN とともにキャンセルされる
+    fn register(id: Option<String>) -> Self {
+        let serial = NEXT_TASK.fetch_add(1, Ordering::Relaxed);
+        let token = CANCEL_TOKEN.child_token();
+        if let (Some(id), Ok(mut tasks)) = (&id, TASKS.lock()) {
+            if let Some((_, previous)) = tasks.insert(id.clone(), (serial, token.clone())) {
+                // 同じ ID の古いタスクは置き換えるためキャンセルする
+                previous.cancel();
+            }
+        }
+        Self { id, serial, token }
+    }
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        let (Some(id), Ok(mut tasks)) = (&self.id, TASKS.lock()) else {
+            return;
+        };
+        if matches!(tasks.get(id), Some((serial, _)) if *serial == self.serial) {
+            tasks.remove(id);
+        }
+    }
+}
+
+/// 登録済みのタスクをキャンセルする（見つかった場合は true）
+fn cancel_task(id: &str) -> Result<bool, String> {
+    let task = TASKS.lock().map_err(|e| e.to_string())?.remove(id);
+    match task {
+        Some((_, token)) => {
+            info!("Cancelling thumbnail task: {}", id);
+            token.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// サムネイルキャッシュを操作する（初回はディレクトリから読み込む）
+fn with_cache<T>(cache_dir: &Path, f: impl FnOnce(&mut ThumbnailCache) -> T) -> Result<T, String> {
+    let mut cache = CACHE.lock().map_err(|e| e.to_string())?;
+    if !matches!(cache.as_ref(), Some(c) if c.dir() == cache_dir) {
+        *cache = Some(ThumbnailCache::open(cache_dir));
+    }
+    Ok(f(cache.as_mut().expect("thumbnail cache is loaded")))
 }
 
 /// 動画ファイルかどうかを判定
@@ -65,31 +247,170 @@ fn get_thumbnail_cache_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
     Ok(cache_dir)
 }
 
-/// ハッシュベースのサムネイルファイル名を生成
-fn get_thumbnail_filename(original_path: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(original_path.as_bytes());
-    let hash = hasher.finalize();
-    format!("{}.jpg", hex::encode(&hash[..16]))
+/// サムネイルの生成方法のバージョン
+/// 生成結果が変わる変更（向きの補正など）をしたら上げる。古いサムネイルは使われなくなり、容量上限で削除される
+const THUMBNAIL_VERSION: u32 = 3;
+
+/// 内容のハッシュからサムネイルファイル名を生成
+/// 内容のハッシュ（ファイルサイズ + 部分ハッシュ）と要求サイズ・品質をキーにするため、
+/// 同じファイルの複数サイズを並べて保持でき、移動しても同じサムネイルを使える
+fn get_thumbnail_filename(
+    content_hash: &str,
+    size: u32,
+    quality: u8,
+    format: OutputFormat,
+) -> String {
+    format!(
+        "{}_{}_q{}_v{}.{}",
+        content_hash,
+        size,
+        quality,
+        THUMBNAIL_VERSION,
+        format.extension()
+    )
+}
+
+/// 動くプレビューのファイル名（GIF と、元画像の情報を保存する JSON）
+fn get_animation_filenames(content_hash: &str, size: u32, limits: &AnimationLimits) -> [String; 2] {
+    let stem = format!(
+        "{}_{}_anim_f{}_d{}_v{}",
+        content_hash, size, limits.max_frames, limits.max_duration_ms, THUMBNAIL_VERSION
+    );
+    [format!("{}.gif", stem), format!("{}.json", stem)]
+}
+
+/// 元ファイルの内容のハッシュを取得（索引にあり、サイズと更新日時が変わっていなければ読み込まない）
+fn content_hash(cache_dir: &Path, path: &str) -> Result<String, String> {
+    let src_path = Path::new(path);
+    let stamp =
+        SourceStamp::of(src_path).map_err(|e| format!("Failed to read file metadata: {}", e))?;
+    if let Some(hash) = with_cache(cache_dir, |cache| cache.known_hash(path, stamp))? {
+        return Ok(hash);
+    }
+
+    let hash = partial_hash(src_path).map_err(|e| format!("Failed to hash file: {}", e))?;
+    with_cache(cache_dir, |cache| {
+        cache.record_hash(path, stamp, hash.clone())
+    })?;
+    Ok(hash)
+}
+
+/// ファイル移動をサムネイルキャッシュの索引に反映する（移動先でもすぐにサムネイルを再利用できる）
+/// キャッシュを読み込む前の移動は、次回の参照時に内容のハッシュを求め直す
+pub(crate) fn record_moves(moves: &[(&str, &str)]) {
+    let Ok(mut cache) = CACHE.lock() else {
+        return;
+    };
+    if let Some(cache) = cache.as_mut() {
+        for (from, to) in moves {
+            cache.record_move(from, to);
+        }
+    }
 }
 
 /// 画像からサムネイルを生成
-fn generate_image_thumbnail(src_path: &Path, thumb_path: &Path, size: u32) -> Result<(), String> {
-    let img =
-        image::open(src_path).map_err(|e| format!("Failed to open image: {}", e))?;
+/// 埋め込みプレビューや JPEG の縮小デコードを使い、全体のデコードはできるだけ避ける
+/// EXIF の向きに合わせて回転・反転する（縮小後に行うため軽い）
+fn generate_image_thumbnail(src_path: &Path, size: u32) -> Result<DynamicImage, String> {
+    let img = open_for_thumbnail(src_path, size)?;
 
-    let thumb = img.thumbnail(size, size);
+    let mut thumb = img.thumbnail(size, size);
+    if let Some(orientation) =
+        read_orientation(src_path).and_then(|o| Orientation::from_exif(o as u8))
+    {
+        thumb.apply_orientation(orientation);
+    }
 
-    thumb
-        .save_with_format(thumb_path, image::ImageFormat::Jpeg)
-        .map_err(|e| format!("Failed to save thumbnail: {}", e))?;
+    Ok(thumb)
+}
 
-    Ok(())
+/// 出力形式を選ぶ（透過する画素がある場合は透過を保てる形式、それ以外は JPEG）
+fn output_format(img: &DynamicImage, options: &ThumbnailOptions) -> OutputFormat {
+    let transparent = img.color().has_alpha() && img.to_rgba8().pixels().any(|p| p[3] < u8::MAX);
+    if transparent {
+        options.alpha_format.into()
+    } else {
+        OutputFormat::Jpeg
+    }
+}
+
+/// サムネイルを保存する
+fn save_thumbnail(
+    img: &DynamicImage,
+    thumb_path: &Path,
+    format: OutputFormat,
+    quality: u8,
+) -> Result<(), String> {
+    let file = File::create(thumb_path).map_err(|e| format!("Failed to save thumbnail: {}", e))?;
+    let mut writer = BufWriter::new(file);
+    match format {
+        OutputFormat::Jpeg => img
+            .to_rgb8()
+            .write_with_encoder(JpegEncoder::new_with_quality(
+                &mut writer,
+                quality.clamp(1, 100),
+            )),
+        OutputFormat::Png => img.write_with_encoder(PngEncoder::new(&mut writer)),
+        OutputFormat::Webp => img
+            .to_rgba8()
+            .write_with_encoder(WebPEncoder::new_lossless(&mut writer)),
+    }
+    .map_err(|e| format!("Failed to save thumbnail: {}", e))
+}
+
+/// JPEG の品質（1〜100）を ffmpeg の -q:v（2〜31、小さいほど高品質）に変換
+fn ffmpeg_qscale(quality: u8) -> u8 {
+    let quality = quality.clamp(1, 100) as u32;
+    (2 + (100 - quality) * 29 / 99) as u8
+}
+
+/// 子プロセスの終了を待つ（キャンセルされた場合はプロセスを終了させる）
+fn wait_or_kill(mut child: Child, token: &CancellationToken) -> Result<Output, String> {
+    // パイプが詰まらないよう、標準エラー出力は別スレッドで読み続ける
+    let stderr = child.stderr.take().map(|mut stderr| {
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = stderr.read_to_end(&mut buffer);
+            buffer
+        })
+    });
+
+    let status = loop {
+        if token.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(CANCELLED.to_string());
+        }
+        match child
+            .try_wait()
+            .map_err(|e| format!("Failed to wait for process: {}", e))?
+        {
+            Some(status) => break status,
+            None => std::thread::sleep(PROCESS_POLL_INTERVAL),
+        }
+    };
+
+    let stderr = stderr
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+    Ok(Output {
+        status,
+        stdout: Vec::new(),
+        stderr,
+    })
 }
 
 /// 動画から5秒付近のフレームを抽出してサムネイルを生成
 /// 先頭フレームは黒画面になりやすいため、5秒付近を使用
-fn generate_video_thumbnail(src_path: &Path, thumb_path: &Path, size: u32) -> Result<(), String> {
+/// キャンセルされた場合は ffmpeg を終了させる
+fn generate_video_thumbnail(
+    src_path: &Path,
+    thumb_path: &Path,
+    size: u32,
+    quality: u8,
+    token: &CancellationToken,
+) -> Result<(), String> {
+    let qscale = ffmpeg_qscale(quality).to_string();
     // ffmpegを使用して5秒付近のフレームを抽出
     let output = Command::new("ffmpeg")
         .args([
@@ -106,11 +427,15 @@ fn generate_video_thumbnail(src_path: &Path, thumb_path: &Path, size: u32) -> Re
             "-frames:v",
             "1",
             "-q:v",
-            "2",
+            &qscale,
             thumb_path.to_str().unwrap(),
         ])
-        .output()
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
         .map_err(|e| format!("Failed to execute ffmpeg: {}. Is ffmpeg installed?", e))?;
+    let output = wait_or_kill(output, token)?;
 
     if !output.status.success() {
         // 5秒位置でエラーの場合（動画が短い等）、先頭から取得を試みる
@@ -127,11 +452,15 @@ fn generate_video_thumbnail(src_path: &Path, thumb_path: &Path, size: u32) -> Re
                 "-frames:v",
                 "1",
                 "-q:v",
-                "2",
+                &qscale,
                 thumb_path.to_str().unwrap(),
             ])
-            .output()
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
             .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+        let fallback_output = wait_or_kill(fallback_output, token)?;
 
         if !fallback_output.status.success() {
             let stderr = String::from_utf8_lossy(&fallback_output.stderr);
@@ -147,6 +476,8 @@ fn generate_thumbnail_sync(
     app: &tauri::AppHandle,
     path: String,
     size: u32,
+    options: &ThumbnailOptions,
+    token: &CancellationToken,
 ) -> Result<ThumbnailResult, String> {
     let src_path = Path::new(&path);
 
@@ -155,36 +486,58 @@ fn generate_thumbnail_sync(
     }
 
     let cache_dir = get_thumbnail_cache_dir(app)?;
-    let thumb_filename = get_thumbnail_filename(&path);
-    let thumb_path = cache_dir.join(&thumb_filename);
+    let hash = content_hash(&cache_dir, &path)?;
+    let filename = |format| get_thumbnail_filename(&hash, size, options.quality, format);
 
-    // キャッシュが存在し、元ファイルより新しければ再利用
-    if thumb_path.exists() {
-        let orig_modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
-        let thumb_modified = fs::metadata(&thumb_path).and_then(|m| m.modified()).ok();
-
-        if let (Some(orig), Some(thumb)) = (orig_modified, thumb_modified) {
-            if thumb > orig {
-                let thumb_path_str = thumb_path.to_string_lossy().to_string();
-                debug!(
-                    "Using cached thumbnail for: {} -> {}",
-                    path, thumb_path_str
-                );
-                return Ok(ThumbnailResult {
-                    original_path: path,
-                    thumbnail_path: thumb_path_str,
-                });
-            }
-        }
+    // キャッシュが存在すれば再利用（アクセスを記録）
+    // 出力形式は元画像の透過の有無で決まるため、どちらの形式も探す
+    // 元ファイルの内容が変わった場合はキーが変わるため、古いサムネイルは容量上限で削除される
+    let candidates = [
+        filename(OutputFormat::Jpeg),
+        filename(options.alpha_format.into()),
+    ];
+    let cached = with_cache(&cache_dir, |cache| cache.lookup_any(&candidates))?;
+    if let Some(cached) = cached {
+        let thumb_path_str = cached.to_string_lossy().to_string();
+        debug!(
+            "Using cached thumbnail for: {} -> {}",
+            path, thumb_path_str
+        );
+        return Ok(ThumbnailResult {
+            original_path: path,
+            thumbnail_path: thumb_path_str,
+            mime_type: OutputFormat::from_path(&cached).mime_type().to_string(),
+        });
     }
 
     // サムネイル生成
     debug!("Generating thumbnail for: {}", path);
 
-    if is_video_file(src_path) {
-        generate_video_thumbnail(src_path, &thumb_path, size)?;
+    // 画像は縮小してから出力形式を決める（動画は常に JPEG）
+    let image = if is_video_file(src_path) {
+        None
     } else {
-        generate_image_thumbnail(src_path, &thumb_path, size)?;
+        Some(generate_image_thumbnail(src_path, size)?)
+    };
+    let format = image
+        .as_ref()
+        .map_or(OutputFormat::Jpeg, |thumb| output_format(thumb, options));
+    let thumb_filename = filename(format);
+    let thumb_path = cache_dir.join(&thumb_filename);
+
+    let generated = match &image {
+        Some(thumb) => save_thumbnail(thumb, &thumb_path, format, options.quality),
+        None => generate_video_thumbnail(src_path, &thumb_path, size, options.quality, token),
+    };
+    if let Err(e) = generated {
+        // 途中まで書き込まれたファイルを残さない
+        let _ = fs::remove_file(&thumb_path);
+        return Err(e);
+    }
+
+    // 容量上限を超えた分は最近使われていない順に削除
+    if let Err(e) = with_cache(&cache_dir, |cache| cache.insert(&thumb_filename))? {
+        warn!("Failed to record thumbnail {}: {}", thumb_filename, e);
     }
 
     let thumb_path_str = thumb_path.to_string_lossy().to_string();
@@ -192,59 +545,302 @@ fn generate_thumbnail_sync(
     Ok(ThumbnailResult {
         original_path: path,
         thumbnail_path: thumb_path_str,
+        mime_type: format.mime_type().to_string(),
     })
 }
 
-/// サムネイルを生成（キャッシュあり・非同期版）
-/// バックグラウンドスレッドで実行し、UIをブロックしない
-#[tauri::command]
-pub async fn generate_thumbnail(
-    app: tauri::AppHandle,
+/// 動くプレビューを生成（同期版・内部用）
+/// 動かない画像の場合は None を返す
+fn generate_animated_preview_sync(
+    app: &tauri::AppHandle,
     path: String,
     size: u32,
-) -> Result<ThumbnailResult, String> {
-    let token = CANCEL_TOKEN.clone();
+    limits: &AnimationLimits,
+    token: &CancellationToken,
+) -> Result<Option<AnimatedPreview>, String> {
+    let src_path = Path::new(&path);
+
+    if !src_path.exists() {
+        return Err(format!("File not found: {}", path));
+    }
+    if !is_animation_candidate(src_path) {
+        return Ok(None);
+    }
 
+    let cache_dir = get_thumbnail_cache_dir(app)?;
+    animated_preview_in(&cache_dir, path, size, limits, token)
+}
+
+/// キャッシュディレクトリを指定して動くプレビューを取得・生成する
+fn animated_preview_in(
+    cache_dir: &Path,
+    path: String,
+    size: u32,
+    limits: &AnimationLimits,
+    token: &CancellationToken,
+) -> Result<Option<AnimatedPreview>, String> {
+    let src_path = Path::new(&path);
+    let names = get_animation_filenames(&content_hash(cache_dir, &path)?, size, limits);
+    let [preview_name, info_name] = &names;
+    let preview_path = cache_dir.join(preview_name);
+    let preview = |info| AnimatedPreview {
+        original_path: path.clone(),
+        preview_path: preview_path.to_string_lossy().to_string(),
+        mime_type: "image/gif".to_string(),
+        info,
+    };
+
+    // キャッシュが存在すれば再利用（情報の JSON が null なら動かない画像として扱う）
+    let cached = with_cache(cache_dir, |cache| cache.lookup(info_name))?
+        .and_then(|info_path| fs::read_to_string(info_path).ok())
+        .and_then(|json| serde_json::from_str::<Option<AnimationInfo>>(&json).ok());
+    match cached {
+        Some(None) => {
+            debug!("Using cached still image result for: {}", path);
+            return Ok(None);
+        }
+        Some(Some(info))
+            if with_cache(cache_dir, |cache| cache.lookup(preview_name))?.is_some() =>
+        {
+            debug!("Using cached animated preview for: {}", path);
+            return Ok(Some(preview(info)));
+        }
+        _ => {}
+    }
+
+    debug!("Generating animated preview for: {}", path);
+    let info = match write_preview(src_path, &preview_path, size, limits, token) {
+        Ok(info) => info,
+        Err(e) => {
+            // 途中まで書き込まれたファイルを残さない
+            let _ = fs::remove_file(&preview_path);
+            return Err(e);
+        }
+    };
+    let json = serde_json::to_string(&info).map_err(|e| e.to_string())?;
+    fs::write(cache_dir.join(info_name), json)
+        .map_err(|e| format!("Failed to save preview info: {}", e))?;
+
+    // 動かない画像は GIF を書き出さないため、情報の JSON のみを記録する
+    let written = if info.is_some() {
+        &names[..]
+    } else {
+        &names[1..]
+    };
+    for name in written {
+        if let Err(e) = with_cache(cache_dir, |cache| cache.insert(name))? {
+            warn!("Failed to record animated preview {}: {}", name, e);
+        }
+    }
+    Ok(info.map(preview))
+}
+
+/// 待ち行列で実行枠を待ち、バックグラウンドスレッドで job を実行する（token がキャンセルされた時点で中断する）
+async fn run_job<T: Send + 'static>(
+    path: &str,
+    priority: Priority,
+    token: CancellationToken,
+    job: impl FnOnce(&CancellationToken) -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
     // キャンセル済みならすぐにエラーを返す
-    if token.is_cancelled() {
+    if CANCEL_TOKEN.is_cancelled() {
         return Err("Application is shutting down".to_string());
     }
+    if token.is_cancelled() {
+        return Err(CANCELLED.to_string());
+    }
 
-    // バックグラウンドスレッドで実行
-    let task = tokio::task::spawn_blocking(move || generate_thumbnail_sync(&app, path, size));
+    // 実行枠は生成が終わるまで保持する（待機側がキャンセルされても二重に実行しない）
+    let video = is_video_file(Path::new(path));
+    let slot = QUEUE.enqueue(path, video, priority);
+    let job_token = token.clone();
+    let run = async move {
+        let slot = slot
+            .await
+            .map_err(|_| "Thumbnail request dropped".to_string())?;
+        tokio::task::spawn_blocking(move || {
+            let _slot = slot;
+            job(&job_token)
+        })
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+    };
 
-    // キャンセルされた場合は中断
+    // キャンセルされた場合は中断（待機中のジョブは待ち行列から外れる）
     tokio::select! {
-        res = task => {
-            res.map_err(|e| format!("Task join error: {}", e))?
-        }
-        _ = token.cancelled() => {
-            Err("Thumbnail generation cancelled".to_string())
-        }
+        res = run => res,
+        _ = token.cancelled() => Err(CANCELLED.to_string()),
     }
 }
 
+/// サムネイル生成ジョブを実行する
+async fn run_thumbnail_job(
+    app: tauri::AppHandle,
+    path: String,
+    size: u32,
+    options: ThumbnailOptions,
+    priority: Priority,
+    token: CancellationToken,
+) -> Result<ThumbnailResult, String> {
+    let job_path = path.clone();
+    run_job(&path, priority, token, move |token| {
+        generate_thumbnail_sync(&app, job_path, size, &options, token)
+    })
+    .await
+}
+
+/// サムネイルを生成（キャッシュあり・非同期版）
+/// 待ち行列で優先度順に実行枠を待ち、バックグラウンドスレッドで実行する（UIをブロックしない）
+/// request_id を指定すると cancel_thumbnail_request でキャンセルできる
+#[tauri::command]
+pub async fn generate_thumbnail(
+    app: tauri::AppHandle,
+    path: String,
+    size: u32,
+    options: Option<ThumbnailOptions>,
+    priority: Option<Priority>,
+    request_id: Option<String>,
+) -> Result<ThumbnailResult, String> {
+    let task = TaskGuard::register(request_id);
+    run_thumbnail_job(
+        app,
+        path,
+        size,
+        options.unwrap_or_default(),
+        priority.unwrap_or_default(),
+        task.token.clone(),
+    )
+    .await
+}
+
 /// 複数のサムネイルをバッチ生成
+/// 同時生成数の上限まで並列に生成し、1件完了するごとに進捗イベントを送信する
+/// batch_id を指定すると cancel_thumbnail_batch でキャンセルできる
+/// 戻り値の順序は paths の順序と同じ
 #[tauri::command]
 pub async fn generate_thumbnails_batch(
     app: tauri::AppHandle,
     paths: Vec<String>,
     size: u32,
+    options: Option<ThumbnailOptions>,
+    batch_id: Option<String>,
 ) -> ThumbnailBatchResult {
-    let mut results = Vec::new();
-    let mut errors = Vec::new();
+    let task = TaskGuard::register(batch_id.clone());
+    let options = options.unwrap_or_default();
+    let total = paths.len();
+    let mut tasks = tokio::task::JoinSet::new();
+    for (index, path) in paths.into_iter().enumerate() {
+        let app = app.clone();
+        let token = task.token.clone();
+        tasks.spawn(async move {
+            let outcome =
+                run_thumbnail_job(app, path.clone(), size, options, Priority::default(), token)
+                    .await;
+            (index, path, outcome)
+        });
+    }
 
-    for path in paths {
-        match generate_thumbnail(app.clone(), path.clone(), size).await {
-            Ok(result) => results.push(result),
+    let mut outcomes = Vec::with_capacity(total);
+    while let Some(joined) = tasks.join_next().await {
+        let (index, path, outcome) = match joined {
+            Ok(finished) => finished,
+            Err(e) => {
+                warn!("Thumbnail task join error: {}", e);
+                continue;
+            }
+        };
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
             Err(e) => {
                 warn!("Failed to generate thumbnail for {}: {}", path, e);
-                errors.push(ThumbnailError { path, error: e });
+                (None, Some(ThumbnailError { path, error: e }))
             }
+        };
+
+        let progress = ThumbnailBatchProgress {
+            batch_id: batch_id.clone(),
+            completed: outcomes.len() + 1,
+            total,
+            result: result.clone(),
+            error: error.clone(),
+        };
+        if let Err(e) = app.emit("thumbnail-batch-progress", progress) {
+            warn!("Failed to emit thumbnail progress: {}", e);
         }
+        outcomes.push((index, result, error));
+    }
+
+    outcomes.sort_by_key(|(index, _, _)| *index);
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    for (_, result, error) in outcomes {
+        results.extend(result);
+        errors.extend(error);
+    }
+
+    let cancelled = task.token.is_cancelled();
+    if cancelled {
+        info!(
+            "Thumbnail batch cancelled: {} of {} generated",
+            results.len(),
+            total
+        );
     }
 
-    ThumbnailBatchResult { results, errors }
+    ThumbnailBatchResult {
+        results,
+        errors,
+        cancelled,
+    }
+}
+
+/// 動くプレビュー（GIF / APNG / WebP アニメーションを縮小した GIF）を生成
+/// 要求されたときに生成し、サムネイルと同じくキャッシュする。動かない画像の場合は None を返す
+/// フレーム数と長さは limits で制限し、元画像のフレーム数と長さもあわせて返す
+#[tauri::command]
+pub async fn generate_animated_preview(
+    app: tauri::AppHandle,
+    path: String,
+    size: u32,
+    limits: Option<AnimationLimits>,
+    request_id: Option<String>,
+) -> Result<Option<AnimatedPreview>, String> {
+    let task = TaskGuard::register(request_id);
+    let limits = limits.unwrap_or_default();
+    let job_path = path.clone();
+    // 表示中の画像に対して要求されるため優先して生成する
+    run_job(&path, Priority::Visible, task.token.clone(), move |token| {
+        generate_animated_preview_sync(&app, job_path, size, &limits, token)
+    })
+    .await
+}
+
+/// バッチ生成をキャンセルする（待機中のジョブは取り消し、実行中の ffmpeg は終了させる）
+/// 見つからない（完了済み）場合は false を返す
+#[tauri::command]
+pub fn cancel_thumbnail_batch(batch_id: String) -> Result<bool, String> {
+    cancel_task(&batch_id)
+}
+
+/// 個別のサムネイル生成をキャンセルする
+#[tauri::command]
+pub fn cancel_thumbnail_request(request_id: String) -> Result<bool, String> {
+    cancel_task(&request_id)
+}
+
+/// 表示中のサムネイルを待ち行列の先頭に移す
+/// drop_hidden が true の場合は、表示外になった待機中の要求を取り消す（呼び出し側にはエラーが返る）
+#[tauri::command]
+pub fn reprioritize_thumbnails(
+    visible_paths: Vec<String>,
+    drop_hidden: Option<bool>,
+) -> ReprioritizeSummary {
+    let summary = QUEUE.reprioritize(&visible_paths, drop_hidden.unwrap_or(false));
+    debug!(
+        "Reprioritized thumbnails: {} promoted, {} dropped",
+        summary.promoted, summary.dropped
+    );
+    summary
 }
 
 /// 複数のファイルを一括移動
@@ -300,7 +896,9 @@ pub fn move_files_batch(sources: Vec<String>, dest_folder: String) -> Result<Vec
         fs::rename(src_path, &final_dest)
             .map_err(|e| format!("Failed to move file {}: {}", src, e))?;
 
-        dest_paths.push(final_dest.to_string_lossy().to_string());
+        let final_dest = final_dest.to_string_lossy().to_string();
+        record_moves(&[(src, &final_dest)]);
+        dest_paths.push(final_dest);
     }
 
     info!("Moved {} files to {}", sources.len(), dest_folder);
@@ -308,41 +906,40 @@ pub fn move_files_batch(sources: Vec<String>, dest_folder: String) -> Result<Vec
 }
 
 /// サムネイルキャッシュをクリーンアップ
+/// 一定期間参照されていないサムネイルを削除し、容量上限（MB）を超えた分を最近使われていない順に削除する
+/// max_size_mb が 0 の場合は現在の容量上限を使う
 #[tauri::command]
 pub fn cleanup_thumbnail_cache(
     app: tauri::AppHandle,
     max_age_days: u64,
-    _max_size_mb: u64,
+    max_size_mb: u64,
 ) -> Result<u64, String> {
     let cache_dir = get_thumbnail_cache_dir(&app)?;
 
-    if !cache_dir.exists() {
-        return Ok(0);
-    }
-
-    let mut removed_count = 0u64;
-    let now = std::time::SystemTime::now();
-    let max_age = std::time::Duration::from_secs(max_age_days * 24 * 60 * 60);
-
-    // 古いサムネイルを削除
-    if let Ok(entries) = fs::read_dir(&cache_dir) {
-        for entry in entries.flatten() {
-            if let Ok(metadata) = entry.metadata() {
-                if let Ok(modified) = metadata.modified() {
-                    if let Ok(age) = now.duration_since(modified) {
-                        if age > max_age {
-                            if fs::remove_file(entry.path()).is_ok() {
-                                removed_count += 1;
-                            }
-                        }
-                    }
-                }
-            }
+    with_cache(&cache_dir, |cache| {
+        if max_size_mb > 0 {
+            cache.set_max_size_mb(max_size_mb);
         }
-    }
+        let expired = cache.remove_older_than(Duration::from_secs(max_age_days * 24 * 60 * 60));
+        let evicted = cache.evict(None).len();
+        cache.prune_paths();
+        if let Err(e) = cache.save() {
+            warn!("Failed to save thumbnail cache index: {}", e);
+        }
+
+        info!(
+            "Cleaned up {} old thumbnails and {} over the size budget",
+            expired, evicted
+        );
+        (expired + evicted) as u64
+    })
+}
 
-    info!("Cleaned up {} old thumbnails", removed_count);
-    Ok(removed_count)
+/// サムネイルキャッシュの統計（件数・合計サイズ・ヒット率）
+#[tauri::command]
+pub fn thumbnail_cache_stats(app: tauri::AppHandle) -> Result<CacheStats, String> {
+    let cache_dir = get_thumbnail_cache_dir(&app)?;
+    with_cache(&cache_dir, |cache| cache.stats())
 }
 
 #[cfg(test)]
@@ -351,13 +948,208 @@ mod tests {
 
     #[test]
     fn test_get_thumbnail_filename() {
-        let filename1 = get_thumbnail_filename("/path/to/image.jpg");
-        let filename2 = get_thumbnail_filename("/path/to/image.jpg");
-        let filename3 = get_thumbnail_filename("/path/to/other.png");
+        let hash = "0123456789abcdef0123456789abcdef";
+        let jpeg = OutputFormat::Jpeg;
+        let filename1 = get_thumbnail_filename(hash, 200, 85, jpeg);
+        let filename2 = get_thumbnail_filename(hash, 200, 85, jpeg);
+        let filename3 = get_thumbnail_filename("fedcba9876543210fedcba9876543210", 200, 85, jpeg);
 
         assert_eq!(filename1, filename2);
         assert_ne!(filename1, filename3);
-        assert!(filename1.ends_with(".jpg"));
+        assert!(filename1.ends_with(&format!("_200_q85_v{}.jpg", THUMBNAIL_VERSION)));
+        // 同じファイルでもサイズ・品質・形式ごとに別のキャッシュになる
+        assert_ne!(filename1, get_thumbnail_filename(hash, 512, 85, jpeg));
+        assert_ne!(filename1, get_thumbnail_filename(hash, 200, 60, jpeg));
+        assert!(get_thumbnail_filename(hash, 200, 85, OutputFormat::Webp).ends_with(".webp"));
+
+        // 動くプレビューは上限ごとに別のキャッシュになる
+        let limits = AnimationLimits::default();
+        let [preview, info] = get_animation_filenames(hash, 200, &limits);
+        assert!(preview.ends_with(".gif") && info.ends_with(".json"));
+        assert_ne!(
+            preview,
+            get_animation_filenames(
+                hash,
+                200,
+                &AnimationLimits {
+                    max_frames: 10,
+                    ..limits
+                }
+            )[0]
+        );
+    }
+
+    #[test]
+    fn test_content_hash_survives_move() {
+        // Arrange: 内容のハッシュを記録してから移動
+        let cache_dir = tempfile::tempdir().unwrap();
+        let files = tempfile::tempdir().unwrap();
+        let src = files.path().join("a.jpg");
+        fs::write(&src, b"image").unwrap();
+        let src = src.to_string_lossy().to_string();
+        let hash = content_hash(cache_dir.path(), &src).unwrap();
+
+        let dest = files.path().join("b.jpg").to_string_lossy().to_string();
+        fs::rename(&src, &dest).unwrap();
+        record_moves(&[(src.as_str(), dest.as_str())]);
+
+        // Act
+        let known = with_cache(cache_dir.path(), |cache| {
+            cache.known_hash(&dest, SourceStamp::of(Path::new(&dest)).unwrap())
+        })
+        .unwrap();
+
+        // Assert: 移動先でもハッシュを求め直さずに同じサムネイルを使う
+        assert_eq!(known, Some(hash.clone()));
+        assert_eq!(content_hash(cache_dir.path(), &dest).unwrap(), hash);
+    }
+
+    #[test]
+    fn test_still_animation_candidate_is_cached() {
+        // Arrange: 1フレームの GIF
+        let cache_dir = tempfile::tempdir().unwrap();
+        let files = tempfile::tempdir().unwrap();
+        let src = files.path().join("still.gif");
+        image::RgbaImage::new(4, 4).save(&src).unwrap();
+        let src = src.to_string_lossy().to_string();
+        let limits = AnimationLimits::default();
+        let token = CancellationToken::new();
+
+        // Act
+        let first = animated_preview_in(cache_dir.path(), src.clone(), 64, &limits, &token);
+        let second = animated_preview_in(cache_dir.path(), src.clone(), 64, &limits, &token);
+
+        // Assert: 動かない結果を情報の JSON に記録し、GIF は書き出さない
+        let [preview_name, info_name] =
+            get_animation_filenames(&content_hash(cache_dir.path(), &src).unwrap(), 64, &limits);
+        assert!(first.unwrap().is_none());
+        assert!(second.unwrap().is_none());
+        assert_eq!(
+            fs::read_to_string(cache_dir.path().join(info_name)).unwrap(),
+            "null"
+        );
+        assert!(!cache_dir.path().join(preview_name).exists());
+    }
+
+    #[test]
+    fn test_generate_image_thumbnail_applies_orientation() {
+        // Arrange: 横長の JPEG に「右に90度回転」(6) の EXIF を付ける
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("photo.jpg");
+        let mut jpeg = std::io::Cursor::new(Vec::new());
+        image::RgbImage::new(40, 20)
+            .write_to(&mut jpeg, image::ImageFormat::Jpeg)
+            .unwrap();
+        let jpeg = jpeg.into_inner();
+        let mut app1 = b"Exif\0\0".to_vec();
+        app1.extend(crate::metadata::exif::tests::build_tiff(
+            &[(0x0112, crate::metadata::exif::tests::TestValue::Short(6))],
+            &[],
+            &[],
+        ));
+        let mut bytes = jpeg[..2].to_vec();
+        bytes.extend_from_slice(&[0xFF, 0xE1]);
+        bytes.extend_from_slice(&(app1.len() as u16 + 2).to_be_bytes());
+        bytes.extend(app1);
+        bytes.extend_from_slice(&jpeg[2..]);
+        fs::write(&src, bytes).unwrap();
+
+        // Act
+        let thumb = generate_image_thumbnail(&src, 10).unwrap();
+
+        // Assert: 縦長になる
+        assert_eq!((thumb.width(), thumb.height()), (5, 10));
+    }
+
+    #[test]
+    fn test_transparent_images_keep_alpha() {
+        // Arrange: 一部が透過した画像と、アルファチャンネルはあるが不透明な画像
+        let dir = tempfile::tempdir().unwrap();
+        let mut sticker = image::RgbaImage::from_pixel(4, 4, image::Rgba([255, 0, 0, 255]));
+        sticker.put_pixel(0, 0, image::Rgba([0, 0, 0, 0]));
+        let sticker = DynamicImage::from(sticker);
+        let opaque = DynamicImage::from(image::RgbaImage::from_pixel(
+            4,
+            4,
+            image::Rgba([255, 0, 0, 255]),
+        ));
+        let options = ThumbnailOptions::default();
+
+        // Act
+        let format = output_format(&sticker, &options);
+        let png = output_format(
+            &sticker,
+            &ThumbnailOptions {
+                alpha_format: AlphaFormat::Png,
+                ..options
+            },
+        );
+        let path = dir.path().join("sticker.webp");
+        save_thumbnail(&sticker, &path, format, options.quality).unwrap();
+
+        // Assert: 透過は保たれ、不透明な画像は JPEG になる
+        assert_eq!(format, OutputFormat::Webp);
+        assert_eq!(png, OutputFormat::Png);
+        assert_eq!(output_format(&opaque, &options), OutputFormat::Jpeg);
+        let saved = image::open(&path).unwrap().to_rgba8();
+        assert_eq!(saved.get_pixel(0, 0)[3], 0);
+        assert_eq!(saved.get_pixel(1, 1)[3], 255);
+    }
+
+    #[test]
+    fn test_ffmpeg_qscale() {
+        assert_eq!(ffmpeg_qscale(100), 2);
+        assert_eq!(ffmpeg_qscale(1), 31);
+        assert_eq!(ffmpeg_qscale(0), 31);
+    }
+
+    #[test]
+    fn test_cancel_task() {
+        // Arrange
+        let task = TaskGuard::register(Some("test-batch".to_string()));
+        let other = TaskGuard::register(Some("test-other".to_string()));
+
+        // Act
+        let found = cancel_task("test-batch").unwrap();
+
+        // Assert: 指定したタスクだけがキャンセルされる
+        assert!(found);
+        assert!(task.token.is_cancelled());
+        assert!(!other.token.is_cancelled());
+        assert!(!CANCEL_TOKEN.is_cancelled());
+        assert!(!cancel_task("test-batch").unwrap());
+
+        // 完了したタスクは登録が外れる
+        drop(other);
+        assert!(!cancel_task("test-other").unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wait_or_kill_stops_cancelled_process() {
+        // Arrange
+        let child = Command::new("sleep")
+            .arg("10")
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let token = CancellationToken::new();
+        token.cancel();
+        let started = std::time::Instant::now();
+
+        // Act
+        let result = wait_or_kill(child, &token);
+
+        // Assert: 終了を待たずにプロセスを終了させる
+        assert_eq!(result.unwrap_err(), CANCELLED);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_worker_limits() {
+        assert_eq!(worker_limits(0), (1, 1));
+        assert_eq!(worker_limits(4), (4, 1));
+        assert_eq!(worker_limits(16), (16, 2));
     }
 
     #[test]
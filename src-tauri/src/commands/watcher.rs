@@ -1,5 +1,7 @@
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::marker::PhantomData;
 use std::path::PathBuf;
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, Mutex};
@@ -12,7 +14,7 @@ use tracing::{debug, error, info, warn};
 const DEBOUNCE_MS: u64 = 500;
 
 /// ファイルシステム変更イベントの種類
-#[derive(Clone, serde::Serialize)]
+#[derive(Clone, Serialize)]
 #[serde(tag = "type", content = "path")]
 pub enum FsChangeEvent {
     Created(String),
@@ -34,50 +36,67 @@ enum PendingEventType {
     Removed,
 }
 
-/// ウォッチャーの状態を管理
-pub struct WatcherState {
+/// ウォッチャーの状態を管理（発火するイベントの型ごとに別の状態として管理する）
+pub struct WatcherState<E> {
     sender: Option<Sender<()>>,
-    watching_path: Option<PathBuf>,
+    watching_paths: Vec<PathBuf>,
+    _event: PhantomData<E>,
 }
 
-impl Default for WatcherState {
+impl<E> Default for WatcherState<E> {
     fn default() -> Self {
         Self {
             sender: None,
-            watching_path: None,
+            watching_paths: Vec::new(),
+            _event: PhantomData,
+        }
+    }
+}
+
+impl<E> WatcherState<E> {
+    /// 監視スレッドに停止を要求する（監視していなければ false）
+    pub(crate) fn stop(&mut self) -> bool {
+        self.watching_paths.clear();
+        match self.sender.take() {
+            Some(sender) => {
+                let _ = sender.send(());
+                true
+            }
+            None => false,
         }
     }
 }
 
 /// グローバルなウォッチャー状態
-pub type WatcherStateHandle = Arc<Mutex<WatcherState>>;
+pub type WatcherStateHandle = Arc<Mutex<WatcherState<FsChangeEvent>>>;
 
-/// 監視を開始
-#[tauri::command]
-pub fn start_watching(
-    app: AppHandle,
-    path: String,
-    state: tauri::State<'_, WatcherStateHandle>,
-) -> Result<(), String> {
-    let path = PathBuf::from(&path);
+/// 監視スレッドが受け取った変更をデバウンスしてイベントに変換する
+pub(crate) trait WatchHandler: Send + 'static {
+    type Event: Serialize + Clone;
 
-    if !path.exists() || !path.is_dir() {
-        return Err("指定されたパスが存在しないか、ディレクトリではありません".to_string());
-    }
+    /// 通知された変更を記録する
+    fn collect(&mut self, event: Event);
 
-    // 既存の監視を停止
-    {
-        let mut watcher_state = state.lock().map_err(|e| e.to_string())?;
-        if let Some(sender) = watcher_state.sender.take() {
-            let _ = sender.send(());
-        }
-    }
+    /// デバウンス期間を過ぎた変更を発火するイベントとして取り出す
+    fn flush(&mut self, debounce_duration: Duration) -> Vec<Self::Event>;
+}
+
+/// 指定ディレクトリの監視スレッドを開始し、変更を `event_name` で発火する（既存の監視は置き換える）
+pub(crate) fn start_watch<H: WatchHandler>(
+    app: &AppHandle,
+    state: &Mutex<WatcherState<H::Event>>,
+    dirs: Vec<PathBuf>,
+    event_name: &'static str,
+    mut handler: H,
+) -> Result<(), String> {
+    let mut watcher_state = state.lock().map_err(|e| e.to_string())?;
+    watcher_state.stop();
 
     let (stop_tx, stop_rx): (Sender<()>, Receiver<()>) = channel();
-    let watch_path = path.clone();
+    let watch_dirs = dirs.clone();
     let app_handle = app.clone();
 
-    info!("ファイル監視を開始: {:?}", path);
+    info!("監視を開始: {} {:?}", event_name, dirs);
 
     thread::spawn(move || {
         let (tx, rx) = channel::<Result<Event, notify::Error>>();
@@ -95,31 +114,30 @@ pub fn start_watching(
             }
         };
 
-        if let Err(e) = watcher.watch(&watch_path, RecursiveMode::NonRecursive) {
-            error!("監視の開始に失敗: {}", e);
-            return;
+        for dir in &watch_dirs {
+            if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                error!("監視の開始に失敗: {:?} - {}", dir, e);
+            }
         }
 
-        debug!("ウォッチャースレッド開始");
+        debug!("ウォッチャースレッド開始: {}", event_name);
 
-        // デバウンス用のペンディングイベント
-        let mut pending_events: HashMap<String, PendingEvent> = HashMap::new();
         let debounce_duration = Duration::from_millis(DEBOUNCE_MS);
 
         loop {
             // 停止シグナルをチェック
             if stop_rx.try_recv().is_ok() {
-                info!("ファイル監視を停止");
+                info!("監視を停止: {}", event_name);
                 break;
             }
 
             // イベントを処理
             match rx.recv_timeout(Duration::from_millis(50)) {
                 Ok(Ok(event)) => {
-                    collect_event(&mut pending_events, event);
+                    handler.collect(event);
                 }
                 Ok(Err(e)) => {
-                    warn!("ファイル監視エラー: {}", e);
+                    warn!("監視エラー: {} - {}", event_name, e);
                 }
                 Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
                     // タイムアウトは正常
@@ -131,18 +149,40 @@ pub fn start_watching(
             }
 
             // デバウンス済みイベントを発火
-            emit_debounced_events(&app_handle, &mut pending_events, debounce_duration);
+            for change in handler.flush(debounce_duration) {
+                if let Err(e) = app_handle.emit(event_name, change) {
+                    error!("イベント送信エラー: {}", e);
+                }
+            }
         }
     });
 
     // 状態を更新
-    {
-        let mut watcher_state = state.lock().map_err(|e| e.to_string())?;
-        watcher_state.sender = Some(stop_tx);
-        watcher_state.watching_path = Some(path);
+    watcher_state.sender = Some(stop_tx);
+    watcher_state.watching_paths = dirs;
+    Ok(())
+}
+
+/// 監視を開始
+#[tauri::command]
+pub fn start_watching(
+    app: AppHandle,
+    path: String,
+    state: tauri::State<'_, WatcherStateHandle>,
+) -> Result<(), String> {
+    let path = PathBuf::from(&path);
+
+    if !path.exists() || !path.is_dir() {
+        return Err("指定されたパスが存在しないか、ディレクトリではありません".to_string());
     }
 
-    Ok(())
+    start_watch(
+        &app,
+        &state,
+        vec![path],
+        "fs-change",
+        MediaChanges::default(),
+    )
 }
 
 /// 監視を停止
@@ -150,15 +190,30 @@ pub fn start_watching(
 pub fn stop_watching(state: tauri::State<'_, WatcherStateHandle>) -> Result<(), String> {
     let mut watcher_state = state.lock().map_err(|e| e.to_string())?;
 
-    if let Some(sender) = watcher_state.sender.take() {
-        let _ = sender.send(());
+    if watcher_state.stop() {
         info!("ファイル監視停止リクエスト送信");
     }
-
-    watcher_state.watching_path = None;
     Ok(())
 }
 
+/// 分別元フォルダのメディアファイルの変更（パスごとに最新の変更のみ保持）
+#[derive(Default)]
+struct MediaChanges {
+    pending: HashMap<String, PendingEvent>,
+}
+
+impl WatchHandler for MediaChanges {
+    type Event = FsChangeEvent;
+
+    fn collect(&mut self, event: Event) {
+        collect_event(&mut self.pending, event);
+    }
+
+    fn flush(&mut self, debounce_duration: Duration) -> Vec<FsChangeEvent> {
+        take_debounced_events(&mut self.pending, debounce_duration)
+    }
+}
+
 /// イベントをペンディングリストに追加
 fn collect_event(pending: &mut HashMap<String, PendingEvent>, event: Event) {
     use notify::EventKind;
@@ -209,12 +264,11 @@ fn collect_event(pending: &mut HashMap<String, PendingEvent>, event: Event) {
     }
 }
 
-/// デバウンス期間を過ぎたイベントを発火
-fn emit_debounced_events(
-    app: &AppHandle,
+/// デバウンス期間を過ぎたイベントを取り出す
+fn take_debounced_events(
     pending: &mut HashMap<String, PendingEvent>,
     debounce_duration: Duration,
-) {
+) -> Vec<FsChangeEvent> {
     let now = Instant::now();
     let mut to_emit = Vec::new();
 
@@ -225,29 +279,28 @@ fn emit_debounced_events(
         }
     }
 
-    // イベントを発火して削除
-    for (path, event) in to_emit {
-        pending.remove(&path);
+    // イベントに変換して削除
+    to_emit
+        .into_iter()
+        .map(|(path, event)| {
+            pending.remove(&path);
 
-        let change_event = match event.event_type {
-            PendingEventType::Created => {
-                info!("ファイル作成検知（デバウンス後）: {}", path);
-                FsChangeEvent::Created(path)
-            }
-            PendingEventType::Modified => {
-                debug!("ファイル変更検知（デバウンス後）: {}", path);
-                FsChangeEvent::Modified(path)
-            }
-            PendingEventType::Removed => {
-                info!("ファイル削除検知（デバウンス後）: {}", path);
-                FsChangeEvent::Removed(path)
+            match event.event_type {
+                PendingEventType::Created => {
+                    info!("ファイル作成検知（デバウンス後）: {}", path);
+                    FsChangeEvent::Created(path)
+                }
+                PendingEventType::Modified => {
+                    debug!("ファイル変更検知（デバウンス後）: {}", path);
+                    FsChangeEvent::Modified(path)
+                }
+                PendingEventType::Removed => {
+                    info!("ファイル削除検知（デバウンス後）: {}", path);
+                    FsChangeEvent::Removed(path)
+                }
             }
-        };
-
-        if let Err(e) = app.emit("fs-change", change_event) {
-            error!("イベント送信エラー: {}", e);
-        }
-    }
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -256,13 +309,35 @@ mod tests {
 
     #[test]
     fn test_watcher_state_default() {
-        let state = WatcherState::default();
+        let state = WatcherState::<FsChangeEvent>::default();
         assert!(state.sender.is_none());
-        assert!(state.watching_path.is_none());
+        assert!(state.watching_paths.is_empty());
     }
 
     #[test]
     fn test_debounce_constant() {
         assert_eq!(DEBOUNCE_MS, 500);
     }
+
+    #[test]
+    fn test_media_changes_debounce() {
+        use notify::event::{CreateKind, EventKind};
+
+        // Arrange
+        let mut changes = MediaChanges::default();
+        let event = |path: &str| {
+            Event::new(EventKind::Create(CreateKind::File)).add_path(PathBuf::from(path))
+        };
+
+        // Act
+        changes.collect(event("/src/a.jpg"));
+        changes.collect(event("/src/notes.txt"));
+
+        // Assert: デバウンス期間中は発火しない、メディア以外は無視する
+        assert!(changes.flush(Duration::from_secs(60)).is_empty());
+        let emitted = changes.flush(Duration::ZERO);
+        assert_eq!(emitted.len(), 1);
+        assert!(matches!(&emitted[0], FsChangeEvent::Created(p) if p == "/src/a.jpg"));
+        assert!(changes.flush(Duration::ZERO).is_empty());
+    }
 }
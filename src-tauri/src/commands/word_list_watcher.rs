@@ -0,0 +1,291 @@
+use super::watcher::{start_watch, WatchHandler, WatcherState};
+use crate::config::settings::{WordList, WordListEntry};
+use crate::matching::word_list::load_word_list_file;
+use notify::{Event, EventKind};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tracing::{debug, info, warn};
+
+/// 単語リスト更新イベント
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WordListChangeEvent {
+    /// 単語リストのスロット（"0"〜"9"）
+    pub key: String,
+    pub file_path: String,
+    pub word_list: WordList,
+    /// 追加されたエントリ（"type:canonical"）
+    pub added: Vec<String>,
+    /// 削除されたエントリ
+    pub removed: Vec<String>,
+    /// 別名等が変更されたエントリ
+    pub changed: Vec<String>,
+}
+
+/// グローバルな単語リスト監視状態
+pub type WordListWatcherStateHandle = Arc<Mutex<WatcherState<WordListChangeEvent>>>;
+
+/// 監視中のスロット
+struct WatchedSlot {
+    key: String,
+    /// 単語リストに記録された読み込み元パス
+    source_path: String,
+    /// イベント照合用の正規化済みパス
+    path: PathBuf,
+    word_list: WordList,
+    pending_since: Option<Instant>,
+}
+
+fn entity_id(entry: &WordListEntry) -> String {
+    format!("{}:{}", entry.entry_type, entry.canonical)
+}
+
+/// 2つの単語リストの差分（追加・削除・変更）を返す
+pub fn diff_word_lists(old: &WordList, new: &WordList) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let old_entries: HashMap<String, &WordListEntry> =
+        old.entries.iter().map(|e| (entity_id(e), e)).collect();
+    let new_entries: HashMap<String, &WordListEntry> =
+        new.entries.iter().map(|e| (entity_id(e), e)).collect();
+
+    let mut added: Vec<String> = new_entries
+        .keys()
+        .filter(|id| !old_entries.contains_key(*id))
+        .cloned()
+        .collect();
+    let mut removed: Vec<String> = old_entries
+        .keys()
+        .filter(|id| !new_entries.contains_key(*id))
+        .cloned()
+        .collect();
+    let mut changed: Vec<String> = new_entries
+        .iter()
+        .filter(|(id, entry)| old_entries.get(*id).is_some_and(|old| old != *entry))
+        .map(|(id, _)| id.clone())
+        .collect();
+
+    added.sort();
+    removed.sort();
+    changed.sort();
+    (added, removed, changed)
+}
+
+/// CSVを再読み込みし、内容が変わっていれば更新イベントを返す
+fn reload_slot(key: &str, path: &str, previous: &WordList) -> Option<WordListChangeEvent> {
+    let word_list = match load_word_list_file(path) {
+        Ok(list) => list,
+        Err(e) => {
+            warn!("単語リストの再読み込みに失敗: {} - {}", path, e);
+            return None;
+        }
+    };
+    if word_list.entries == previous.entries {
+        return None;
+    }
+
+    let (added, removed, changed) = diff_word_lists(previous, &word_list);
+    Some(WordListChangeEvent {
+        key: key.to_string(),
+        file_path: path.to_string(),
+        word_list,
+        added,
+        removed,
+        changed,
+    })
+}
+
+/// イベントのパスと一致するか（シンボリックリンク等を考慮して正規化して比較）
+fn is_same_path(event_path: &Path, slot_path: &Path) -> bool {
+    let event_path = fs::canonicalize(event_path).unwrap_or_else(|_| event_path.to_path_buf());
+    event_path == slot_path
+}
+
+/// 監視中の単語リスト（変更されたCSVをデバウンス後に再読み込みする）
+struct WatchedSlots(Vec<WatchedSlot>);
+
+impl WatchHandler for WatchedSlots {
+    type Event = WordListChangeEvent;
+
+    fn collect(&mut self, event: Event) {
+        if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+            return;
+        }
+        for slot in self.0.iter_mut() {
+            if event.paths.iter().any(|p| is_same_path(p, &slot.path)) {
+                slot.pending_since = Some(Instant::now());
+            }
+        }
+    }
+
+    fn flush(&mut self, debounce_duration: Duration) -> Vec<WordListChangeEvent> {
+        let mut changes = Vec::new();
+        for slot in self.0.iter_mut() {
+            let Some(since) = slot.pending_since else {
+                continue;
+            };
+            if since.elapsed() < debounce_duration {
+                continue;
+            }
+            slot.pending_since = None;
+
+            if let Some(change) = reload_slot(&slot.key, &slot.source_path, &slot.word_list) {
+                info!(
+                    "単語リスト更新: {} (+{} / -{} / ~{})",
+                    slot.source_path,
+                    change.added.len(),
+                    change.removed.len(),
+                    change.changed.len()
+                );
+                slot.word_list = change.word_list.clone();
+                changes.push(change);
+            }
+        }
+        changes
+    }
+}
+
+/// 単語リストCSVの監視を開始（既存の監視は置き換える）
+#[tauri::command]
+pub fn watch_word_lists(
+    app: AppHandle,
+    word_lists: HashMap<String, Option<WordList>>,
+    state: tauri::State<'_, WordListWatcherStateHandle>,
+) -> Result<(), String> {
+    let slots: Vec<WatchedSlot> = word_lists
+        .into_iter()
+        .filter_map(|(key, list)| {
+            let list = list?;
+            let source_path = list.source_path.clone()?;
+            let path = fs::canonicalize(&source_path).ok()?;
+            Some(WatchedSlot {
+                key,
+                source_path,
+                path,
+                word_list: list,
+                pending_since: None,
+            })
+        })
+        .collect();
+
+    if slots.is_empty() {
+        // 既存の監視を停止
+        state.lock().map_err(|e| e.to_string())?.stop();
+        debug!("監視対象の単語リストなし");
+        return Ok(());
+    }
+
+    // エディタの置き換え保存に対応するため親ディレクトリを監視
+    let dirs: BTreeSet<PathBuf> = slots
+        .iter()
+        .filter_map(|s| s.path.parent().map(Path::to_path_buf))
+        .collect();
+
+    info!("単語リスト監視を開始: {}件", slots.len());
+    start_watch(
+        &app,
+        &state,
+        dirs.into_iter().collect(),
+        "word-list-changed",
+        WatchedSlots(slots),
+    )
+}
+
+/// 単語リストCSVの監視を停止
+#[tauri::command]
+pub fn unwatch_word_lists(
+    state: tauri::State<'_, WordListWatcherStateHandle>,
+) -> Result<(), String> {
+    let mut watcher_state = state.lock().map_err(|e| e.to_string())?;
+
+    if watcher_state.stop() {
+        info!("単語リスト監視停止リクエスト送信");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_diff_word_lists() {
+        // Arrange
+        let old = crate::matching::word_list::parse_word_list_csv(
+            "アリア,Aria\nレオン,Leon\n月影,Moon",
+            "list.csv",
+        );
+        let new = crate::matching::word_list::parse_word_list_csv(
+            "アリア,Aria\n月影,Moon Shadow\nセレナ,Serena",
+            "list.csv",
+        );
+
+        // Act
+        let (added, removed, changed) = diff_word_lists(&old, &new);
+
+        // Assert
+        assert_eq!(added, vec!["character:セレナ"]);
+        assert_eq!(removed, vec!["character:レオン"]);
+        assert_eq!(changed, vec!["character:月影"]);
+    }
+
+    #[test]
+    fn test_reload_slot() {
+        // Arrange
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("list.csv");
+        fs::write(&path, "アリア,Aria\n").unwrap();
+        let path_str = path.to_string_lossy().to_string();
+        let previous = load_word_list_file(&path_str).unwrap();
+
+        // Act & Assert: 変更なし
+        assert!(reload_slot("1", &path_str, &previous).is_none());
+
+        // Act: エントリ追加
+        fs::write(&path, "アリア,Aria\nレオン,Leon\n").unwrap();
+        let change = reload_slot("1", &path_str, &previous).unwrap();
+
+        // Assert
+        assert_eq!(change.key, "1");
+        assert_eq!(change.added, vec!["character:レオン"]);
+        assert!(change.removed.is_empty());
+        assert_eq!(
+            change.word_list.source_path.as_deref(),
+            Some(path_str.as_str())
+        );
+    }
+    #[test]
+    fn test_watched_slots_reload_changed_csv() {
+        use notify::event::{DataChange, EventKind, ModifyKind};
+
+        // Arrange
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("list.csv");
+        fs::write(&path, "アリア,Aria\n").unwrap();
+        let source_path = path.to_string_lossy().to_string();
+        let mut slots = WatchedSlots(vec![WatchedSlot {
+            key: "1".to_string(),
+            word_list: load_word_list_file(&source_path).unwrap(),
+            source_path,
+            path: fs::canonicalize(&path).unwrap(),
+            pending_since: None,
+        }]);
+
+        // Act
+        fs::write(&path, "アリア,Aria\nレオン,Leon\n").unwrap();
+        slots.collect(
+            Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Content)))
+                .add_path(path.clone()),
+        );
+        let changes = slots.flush(Duration::ZERO);
+
+        // Assert
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].added, vec!["character:レオン"]);
+        assert!(slots.flush(Duration::ZERO).is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod options;
 pub mod settings;
 
 pub use settings::{load_settings, save_settings, Settings};
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+
+/// マッチング対象テキストの取得元
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchSource {
+    /// 親フォルダ名 + ファイル名
+    FileName,
+    /// EXIF ImageDescription
+    ExifDescription,
+    /// XMP dc:subject キーワード
+    XmpSubject,
+    /// IPTC キーワード
+    IptcKeywords,
+    /// PNG tEXt / zTXt / iTXt チャンク
+    PngText,
+    /// 画像生成プロンプト（A1111 / ComfyUI）
+    AiPrompt,
+}
+
+/// XMP の書き込み先
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum XmpLocation {
+    /// サイドカーファイル（photo.jpg.xmp）
+    #[default]
+    Sidecar,
+    /// 画像に埋め込む（JPEG / PNG / WebP のみ）
+    Embedded,
+}
+
+/// 色ラベル（xmp:Label には Lightroom / Bridge と同じ英語名で書き込む）
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum ColorLabel {
+    Red,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl ColorLabel {
+    /// xmp:Label に書き込む値
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ColorLabel::Red => "Red",
+            ColorLabel::Yellow => "Yellow",
+            ColorLabel::Green => "Green",
+            ColorLabel::Blue => "Blue",
+            ColorLabel::Purple => "Purple",
+        }
+    }
+
+    /// xmp:Label の値を解釈する（大文字小文字は区別しない）
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            ColorLabel::Red,
+            ColorLabel::Yellow,
+            ColorLabel::Green,
+            ColorLabel::Blue,
+            ColorLabel::Purple,
+        ]
+        .into_iter()
+        .find(|label| label.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+}
+
+/// 追加の分別先への配置方法
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FanOutMode {
+    /// ハードリンク（別ボリューム等で作成できない場合はコピー）
+    HardLink,
+    /// コピー
+    Copy,
+}
+
+/// スキャン結果の並び順
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ScanSort {
+    /// ファイル名の自然順
+    #[default]
+    Name,
+    /// 評価の高い順（同じ評価はファイル名順）
+    Rating,
+    /// 色ラベル順（ラベルなしは最後、同じラベルはファイル名順）
+    Label,
+}
+
+/// スキャン時の絞り込み条件（拡張属性のタグで処理済みのファイルを隠す、評価・色ラベルで絞り込む）
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanOptions {
+    /// picSort で仕分け済み（user.picsort.key あり）のファイルを除外する
+    #[serde(default)]
+    pub hide_sorted: bool,
+    /// いずれかのタグ（user.xdg.tags）を持つファイルを除外する
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
+    /// この評価未満のファイルを除外する
+    #[serde(default)]
+    pub min_rating: u8,
+    /// いずれかの色ラベルを持つファイルのみ表示する（空なら絞り込まない）
+    #[serde(default)]
+    pub labels: Vec<ColorLabel>,
+    #[serde(default)]
+    pub sort_by: ScanSort,
+}
+
+impl ScanOptions {
+    /// 拡張属性のタグを読み込む必要があるか
+    pub fn needs_tags(&self) -> bool {
+        self.hide_sorted || !self.exclude_tags.is_empty()
+    }
+
+    /// 拡張属性のタグによって除外するファイルか（sorted は picSort で仕分け済みか）
+    pub fn excludes(&self, sorted: bool, tags: &[String]) -> bool {
+        (self.hide_sorted && sorted) || tags.iter().any(|tag| self.exclude_tags.contains(tag))
+    }
+
+    /// 評価・色ラベルの読み込みが必要か（XMP の解析はファイルごとに重いため）
+    pub fn needs_rating(&self) -> bool {
+        self.min_rating > 0 || !self.labels.is_empty() || self.sort_by != ScanSort::Name
+    }
+
+    /// 評価・色ラベルの条件を満たすか
+    pub fn accepts(&self, rating: u8, label: Option<ColorLabel>) -> bool {
+        rating >= self.min_rating
+            && (self.labels.is_empty() || label.is_some_and(|l| self.labels.contains(&l)))
+    }
+}
+
+/// 透過のある画像のサムネイル形式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AlphaFormat {
+    /// ロスレス WebP
+    #[default]
+    Webp,
+    Png,
+}
+
+/// サムネイルの出力設定
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailOptions {
+    /// JPEG の品質（1〜100、動画のサムネイルにも使う）
+    #[serde(default = "default_quality")]
+    pub quality: u8,
+    /// 透過のある画像の出力形式（透過のない画像は JPEG）
+    #[serde(default)]
+    pub alpha_format: AlphaFormat,
+}
+
+fn default_quality() -> u8 {
+    85
+}
+
+impl Default for ThumbnailOptions {
+    fn default() -> Self {
+        Self {
+            quality: default_quality(),
+            alpha_format: AlphaFormat::default(),
+        }
+    }
+}
+
+/// 動くプレビューの上限
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationLimits {
+    #[serde(default = "default_max_frames")]
+    pub max_frames: u32,
+    /// 先頭からこの長さ（ミリ秒）までのフレームを使う
+    #[serde(default = "default_max_duration_ms")]
+    pub max_duration_ms: u64,
+}
+
+fn default_max_frames() -> u32 {
+    60
+}
+
+fn default_max_duration_ms() -> u64 {
+    3000
+}
+
+impl Default for AnimationLimits {
+    fn default() -> Self {
+        Self {
+            max_frames: default_max_frames(),
+            max_duration_ms: default_max_duration_ms(),
+        }
+    }
+}
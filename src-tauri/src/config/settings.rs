@@ -1,3 +1,6 @@
+use crate::config::options::{
+    AnimationLimits, FanOutMode, MatchSource, ScanOptions, ThumbnailOptions, XmpLocation,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -14,12 +17,21 @@ pub struct WordListEntry {
     pub canonical_normalized: String,
     pub aliases_normalized: Vec<String>,
     pub search_keys_normalized: Vec<String>,
+    /// ローマ字・読み等から生成した派生キー（search_keys_normalized にも含まれる）
+    #[serde(default)]
+    pub derived_keys_normalized: Vec<String>,
+    /// キャラクターの所属作品（"work:作品名"）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct WordList {
     pub file_name: String,
+    /// 読み込み元CSVのパス（設定時は変更を監視して再読み込みする）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_path: Option<String>,
     pub entries: Vec<WordListEntry>,
 }
 
@@ -34,6 +46,17 @@ pub enum Theme {
     Dark,
 }
 
+/// 分別キーを押したときの動作
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationMode {
+    /// ファイルを分別先に移動する
+    #[default]
+    Move,
+    /// 移動せず XMP キーワードを書き込む
+    Tag,
+}
+
 impl Default for Theme {
     fn default() -> Self {
         Theme::System
@@ -71,12 +94,46 @@ pub struct Settings {
     pub window: WindowSettings,
     #[serde(default, rename = "wordLists")]
     pub word_lists: HashMap<String, Option<WordList>>,
+    /// マッチング対象テキストの取得元（評価順）
+    #[serde(default = "default_matching_sources", rename = "matchingSources")]
+    pub matching_sources: Vec<MatchSource>,
+    /// 複数の分別先に確定したファイルの配置方法（未設定なら振り分けない）
+    #[serde(default, rename = "fanOut", skip_serializing_if = "Option::is_none")]
+    pub fan_out: Option<FanOutMode>,
+    #[serde(default, rename = "operationMode")]
+    pub operation_mode: OperationMode,
+    /// タグモードでの XMP の書き込み先
+    #[serde(default, rename = "tagLocation")]
+    pub tag_location: XmpLocation,
+    /// タグモードで書き込む分別先キーごとのラベル（未設定なら分別先フォルダ名）
+    #[serde(default, rename = "tagLabels", skip_serializing_if = "HashMap::is_empty")]
+    pub tag_labels: HashMap<String, String>,
+    /// 移動したファイルに仕分け結果を拡張属性として記録する
+    #[serde(default, rename = "xattrTagging")]
+    pub xattr_tagging: bool,
+    /// スキャン時の絞り込み条件
+    #[serde(default, rename = "scanOptions")]
+    pub scan_options: ScanOptions,
+    /// サムネイルの品質と透過のある画像の出力形式
+    #[serde(default, rename = "thumbnailOptions")]
+    pub thumbnail_options: ThumbnailOptions,
+    /// 動く画像の縮小プレビューの上限（未設定なら生成しない）
+    #[serde(
+        default,
+        rename = "animatedPreviews",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub animated_previews: Option<AnimationLimits>,
 }
 
 fn default_show_welcome() -> bool {
     true
 }
 
+fn default_matching_sources() -> Vec<MatchSource> {
+    vec![MatchSource::FileName]
+}
+
 impl Default for Settings {
     fn default() -> Self {
         let mut destinations = HashMap::new();
@@ -99,6 +156,15 @@ impl Default for Settings {
             source_folder: None,
             window: WindowSettings::default(),
             word_lists,
+            matching_sources: default_matching_sources(),
+            fan_out: None,
+            operation_mode: OperationMode::default(),
+            tag_location: XmpLocation::default(),
+            tag_labels: HashMap::new(),
+            xattr_tagging: false,
+            scan_options: ScanOptions::default(),
+            thumbnail_options: ThumbnailOptions::default(),
+            animated_previews: None,
         }
     }
 }
@@ -159,6 +225,30 @@ mod tests {
         assert!(settings.word_lists.values().all(|v| v.is_none()));
         assert_eq!(settings.language, "ja");
         assert!(matches!(settings.theme, Theme::System));
+        assert_eq!(settings.matching_sources, vec![MatchSource::FileName]);
+    }
+
+    #[test]
+    fn test_load_settings_matching_sources() {
+        // Arrange: matchingSources を含む設定ファイル
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("settings.json");
+        let settings = Settings {
+            matching_sources: vec![MatchSource::FileName, MatchSource::XmpSubject],
+            ..Default::default()
+        };
+        save_settings(settings, config_path.to_string_lossy().to_string()).unwrap();
+
+        // Act: 設定読み込み
+        let content = fs::read_to_string(&config_path).unwrap();
+        let loaded = load_settings(config_path.to_string_lossy().to_string()).unwrap();
+
+        // Assert: camelCase で保存され、読み込める
+        assert!(content.contains("\"xmpSubject\""));
+        assert_eq!(
+            loaded.matching_sources,
+            vec![MatchSource::FileName, MatchSource::XmpSubject]
+        );
     }
 
     #[test]
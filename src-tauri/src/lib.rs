@@ -1,12 +1,33 @@
 mod commands;
 mod config;
 mod logging;
+mod matching;
+mod metadata;
+mod thumbnail;
 
-use commands::file_ops::{move_file, scan_images, undo_move};
+use commands::auto_sort::{plan_auto_sort, plan_fan_out, run_auto_sort};
+use commands::classifier::{
+    export_classifier, reset_classifier, suggest_learned_destination, train_classifier,
+    untrain_classifier,
+};
+use commands::file_ops::{fan_out_file, move_file, scan_images, undo_fan_out, undo_move};
+use commands::matching::{
+    accept_aliases, dedupe_word_list, explain_match, import_word_list, lint_word_list,
+    merge_word_lists, mine_aliases, suggest_destination,
+};
+use commands::metadata::{read_ai_metadata, read_metadata};
+use commands::tagging::{
+    read_file_tags, read_rating_label, record_sort_tags, remove_file_tags, set_color_label,
+    set_rating, tag_file, untag_file, write_file_tags,
+};
 use commands::watcher::{start_watching, stop_watching, WatcherStateHandle};
+use commands::word_list_watcher::{
+    unwatch_word_lists, watch_word_lists, WordListWatcherStateHandle,
+};
 use commands::thumbnail::{
-    cancel_all_tasks, cleanup_thumbnail_cache, generate_thumbnail, generate_thumbnails_batch,
-    move_files_batch,
+    cancel_all_tasks, cancel_thumbnail_batch, cancel_thumbnail_request, cleanup_thumbnail_cache,
+    generate_animated_preview, generate_thumbnail, generate_thumbnails_batch, move_files_batch,
+    reprioritize_thumbnails, thumbnail_cache_stats,
 };
 use config::settings::{load_settings, save_settings};
 use logging::{get_log_path, init_logging};
@@ -20,11 +41,14 @@ pub fn run() {
     tracing::info!("picSort アプリケーション起動");
 
     let watcher_state: WatcherStateHandle = Arc::new(Mutex::new(Default::default()));
+    let word_list_watcher_state: WordListWatcherStateHandle =
+        Arc::new(Mutex::new(Default::default()));
 
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .manage(watcher_state)
+        .manage(word_list_watcher_state)
         .on_window_event(|_window, event| {
             if let tauri::WindowEvent::CloseRequested { .. } = event {
                 // サムネイル生成タスクをキャンセル
@@ -42,8 +66,44 @@ pub fn run() {
             get_log_path,
             generate_thumbnail,
             generate_thumbnails_batch,
+            generate_animated_preview,
             move_files_batch,
-            cleanup_thumbnail_cache
+            cleanup_thumbnail_cache,
+            thumbnail_cache_stats,
+            reprioritize_thumbnails,
+            cancel_thumbnail_batch,
+            cancel_thumbnail_request,
+            suggest_destination,
+            plan_auto_sort,
+            run_auto_sort,
+            plan_fan_out,
+            read_ai_metadata,
+            read_metadata,
+            train_classifier,
+            untrain_classifier,
+            suggest_learned_destination,
+            reset_classifier,
+            export_classifier,
+            mine_aliases,
+            accept_aliases,
+            import_word_list,
+            watch_word_lists,
+            unwatch_word_lists,
+            lint_word_list,
+            merge_word_lists,
+            dedupe_word_list,
+            explain_match,
+            fan_out_file,
+            undo_fan_out,
+            tag_file,
+            untag_file,
+            read_file_tags,
+            write_file_tags,
+            remove_file_tags,
+            record_sort_tags,
+            set_rating,
+            set_color_label,
+            read_rating_label
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,290 @@
+use super::matcher::get_ngrams;
+use super::normalize::{normalize, strip_mask_tokens};
+use super::sources::collect_targets;
+use crate::config::options::MatchSource;
+use crate::metadata::ai;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// モデルファイルの形式バージョン
+const MODEL_VERSION: u32 = 1;
+
+/// 特徴量に使う n-gram の長さ
+const NGRAM_SIZE: usize = 2;
+
+/// 分別先キーごとの学習統計
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassStats {
+    /// 学習したファイル数
+    pub documents: u64,
+    /// 特徴量の出現総数
+    pub feature_total: u64,
+    pub features: BTreeMap<String, u64>,
+}
+
+/// 分別操作から学習するナイーブベイズ分類器
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassifierModel {
+    pub version: u32,
+    pub classes: BTreeMap<String, ClassStats>,
+}
+
+impl Default for ClassifierModel {
+    fn default() -> Self {
+        Self {
+            version: MODEL_VERSION,
+            classes: BTreeMap::new(),
+        }
+    }
+}
+
+impl ClassifierModel {
+    /// 学習済みファイルの総数
+    pub fn total_documents(&self) -> u64 {
+        self.classes.values().map(|c| c.documents).sum()
+    }
+
+    /// 1件の分別結果を学習する
+    pub fn train(&mut self, features: &BTreeSet<String>, key: &str) {
+        let stats = self.classes.entry(key.to_string()).or_default();
+        stats.documents += 1;
+        for feature in features {
+            *stats.features.entry(feature.clone()).or_insert(0) += 1;
+            stats.feature_total += 1;
+        }
+    }
+
+    /// 学習した分別結果を取り消す（Undo 時）
+    pub fn forget(&mut self, features: &BTreeSet<String>, key: &str) {
+        let Some(stats) = self.classes.get_mut(key) else {
+            return;
+        };
+        stats.documents = stats.documents.saturating_sub(1);
+        for feature in features {
+            if let Some(count) = stats.features.get_mut(feature) {
+                *count -= 1;
+                stats.feature_total = stats.feature_total.saturating_sub(1);
+                if *count == 0 {
+                    stats.features.remove(feature);
+                }
+            }
+        }
+        if stats.documents == 0 {
+            self.classes.remove(key);
+        }
+    }
+
+    /// 分別先キーごとの確信度（合計 1.0）を降順で返す
+    pub fn predict(&self, features: &BTreeSet<String>) -> Vec<(String, f64)> {
+        let total_documents = self.total_documents();
+        if total_documents == 0 {
+            return Vec::new();
+        }
+
+        let vocabulary: BTreeSet<&String> = self
+            .classes
+            .values()
+            .flat_map(|c| c.features.keys())
+            .collect();
+        let vocabulary_size = vocabulary.len() as f64;
+
+        // 対数尤度（ラプラス平滑化、未知の特徴量は無視）
+        let log_scores: Vec<(String, f64)> = self
+            .classes
+            .iter()
+            .map(|(key, stats)| {
+                let mut score = (stats.documents as f64 / total_documents as f64).ln();
+                let denominator = stats.feature_total as f64 + vocabulary_size;
+                for feature in features.iter().filter(|f| vocabulary.contains(f)) {
+                    let count = stats.features.get(feature).copied().unwrap_or(0) as f64;
+                    score += ((count + 1.0) / denominator).ln();
+                }
+                (key.clone(), score)
+            })
+            .collect();
+
+        // softmax で確信度に変換
+        let max = log_scores
+            .iter()
+            .map(|(_, s)| *s)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let sum: f64 = log_scores.iter().map(|(_, s)| (s - max).exp()).sum();
+
+        let mut predictions: Vec<(String, f64)> = log_scores
+            .into_iter()
+            .map(|(key, s)| (key, (s - max).exp() / sum))
+            .collect();
+        predictions.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        predictions
+    }
+}
+
+/// ファイルから特徴量を抽出する
+/// 移動後のパスで学習するため、フォルダ名は使わずファイル名のみを対象にする
+pub fn extract_features(file_path: &str, sources: &[MatchSource]) -> BTreeSet<String> {
+    let path = Path::new(file_path);
+    let mut features = BTreeSet::new();
+
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let normalized = strip_mask_tokens(&normalize(&stem));
+    features.extend(
+        get_ngrams(&normalized, NGRAM_SIZE)
+            .into_iter()
+            .map(|g| format!("name:{}", g)),
+    );
+
+    if let Some(ext) = path.extension() {
+        features.insert(format!("ext:{}", ext.to_string_lossy().to_lowercase()));
+    }
+
+    // ファイル名以外の取得元（EXIF / XMP 等）
+    let metadata_sources: Vec<MatchSource> = sources
+        .iter()
+        .copied()
+        .filter(|s| *s != MatchSource::FileName)
+        .collect();
+    for target in collect_targets(file_path, &metadata_sources) {
+        let normalized = strip_mask_tokens(&normalize(&target.text));
+        features.extend(
+            get_ngrams(&normalized, NGRAM_SIZE)
+                .into_iter()
+                .map(|g| format!("meta:{}", g)),
+        );
+    }
+
+    // 画像生成メタデータのモデル名・LoRA
+    if let Some(meta) = ai::read_ai_metadata(path) {
+        if let Some(model) = meta.model {
+            features.insert(format!("model:{}", model));
+        }
+        for lora in meta.loras {
+            features.insert(format!("lora:{}", lora));
+        }
+    }
+
+    features
+}
+
+/// 設定ファイルに対応するモデルファイルのパス（config.json → config.classifier.json）
+pub fn model_path(config_path: &str) -> PathBuf {
+    let path = Path::new(config_path);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "config".to_string());
+    path.with_file_name(format!("{}.classifier.json", stem))
+}
+
+/// モデルを読み込む（存在しない場合は空のモデル）
+pub fn load_model(path: &Path) -> Result<ClassifierModel, String> {
+    if !path.exists() {
+        return Ok(ClassifierModel::default());
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+/// モデルを保存する
+pub fn save_model(path: &Path, model: &ClassifierModel) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+    }
+    let content = serde_json::to_string_pretty(model).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn features(file_path: &str) -> BTreeSet<String> {
+        extract_features(file_path, &[MatchSource::FileName])
+    }
+
+    #[test]
+    fn test_extract_features_ignores_folder() {
+        // Arrange & Act: 同じファイル名でフォルダだけ異なる
+        let before = features("/source/aria_001.png");
+        let after = features("/dest/aria/aria_001.png");
+
+        // Assert
+        assert_eq!(before, after);
+        assert!(before.contains("name:ar"));
+        assert!(before.contains("ext:png"));
+    }
+
+    #[test]
+    fn test_predict_after_training() {
+        // Arrange
+        let mut model = ClassifierModel::default();
+        model.train(&features("aria_001.png"), "1");
+        model.train(&features("aria_002.png"), "1");
+        model.train(&features("leon_001.jpg"), "2");
+
+        // Act
+        let predictions = model.predict(&features("aria_010.png"));
+
+        // Assert
+        assert_eq!(predictions[0].0, "1");
+        assert!(predictions[0].1 > 0.5);
+        let sum: f64 = predictions.iter().map(|(_, c)| c).sum();
+        assert!((sum - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_forget_reverses_train() {
+        // Arrange
+        let mut model = ClassifierModel::default();
+        model.train(&features("aria_001.png"), "1");
+        let trained = model.clone();
+
+        // Act: 誤って学習した分別結果を取り消す
+        model.train(&features("aria_002.png"), "2");
+        model.forget(&features("aria_002.png"), "2");
+
+        // Assert
+        assert_eq!(model, trained);
+    }
+
+    #[test]
+    fn test_predict_empty_model() {
+        let model = ClassifierModel::default();
+        assert!(model.predict(&features("aria.png")).is_empty());
+    }
+
+    #[test]
+    fn test_model_path() {
+        let path = model_path("/data/profiles/work.json");
+        assert_eq!(path, PathBuf::from("/data/profiles/work.classifier.json"));
+    }
+
+    #[test]
+    fn test_save_and_load_model() {
+        // Arrange
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.classifier.json");
+        let mut model = ClassifierModel::default();
+        model.train(&features("aria_001.png"), "1");
+
+        // Act
+        save_model(&path, &model).unwrap();
+        let loaded = load_model(&path).unwrap();
+
+        // Assert
+        assert_eq!(loaded, model);
+        assert_eq!(
+            load_model(&dir.path().join("missing.json")).unwrap(),
+            ClassifierModel::default()
+        );
+    }
+}
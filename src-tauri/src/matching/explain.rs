@@ -0,0 +1,305 @@
+use super::matcher::{
+    match_targets_traced, rule_a, rule_b, rule_c, rule_d, score_breakdown, MatchDebug, MatchItem,
+    MatchResult, MatchRule, MatchTarget, MatchingConfig, ResolutionStep, ScoreBreakdown,
+};
+use super::normalize::{normalization_stages, normalize, strip_mask_tokens};
+use crate::config::options::MatchSource;
+use crate::config::settings::{WordList, WordListEntry};
+use serde::Serialize;
+
+/// 正規化の途中結果
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizationStage {
+    pub name: String,
+    pub output: String,
+}
+
+/// ルールごとの評価結果
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleEvaluation {
+    pub rule: MatchRule,
+    pub matched: bool,
+    /// 評価したキー（ルールDは不一致でも最良のキーを返す）
+    pub key_normalized: Option<String>,
+    pub derived: bool,
+    pub score: Option<ScoreBreakdown>,
+}
+
+/// 項目ごとの評価結果
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityEvaluation {
+    pub entity_id: String,
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    pub canonical: String,
+    pub rules: Vec<RuleEvaluation>,
+    /// 実際に採用されたルール（A → D の順で最初に一致したもの）
+    pub applied_rule: Option<MatchRule>,
+}
+
+/// ターゲット文字列ごとの評価過程
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetExplanation {
+    pub source: MatchSource,
+    pub raw_text: String,
+    pub stages: Vec<NormalizationStage>,
+    pub normalized: String,
+    pub entities: Vec<EntityEvaluation>,
+}
+
+/// マッチングの評価過程
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchExplanation {
+    pub file_path: String,
+    pub targets: Vec<TargetExplanation>,
+    /// 最終結果（各項目に debug を付与）
+    pub result: MatchResult,
+    /// 衝突処理で除外された項目（debug.resolution に段階を記録）
+    pub dropped: Vec<MatchItem>,
+}
+
+/// 一致した項目からルールの評価結果を作る
+fn evaluation_from_item(rule: MatchRule, target: &str, item: MatchItem) -> RuleEvaluation {
+    // ルールCは伏せ字を除いた文字列でスコアを計算している
+    let scored_target = if rule == MatchRule::C {
+        strip_mask_tokens(target)
+    } else {
+        target.to_string()
+    };
+    let breakdown = score_breakdown(&scored_target, &item.matched_key_normalized);
+    RuleEvaluation {
+        rule,
+        matched: true,
+        derived: item.derived,
+        score: Some(ScoreBreakdown {
+            score: item.score,
+            ..breakdown
+        }),
+        key_normalized: Some(item.matched_key_normalized),
+    }
+}
+
+fn unmatched(rule: MatchRule) -> RuleEvaluation {
+    RuleEvaluation {
+        rule,
+        matched: false,
+        key_normalized: None,
+        derived: false,
+        score: None,
+    }
+}
+
+/// 1項目に対して全ルールを評価する
+fn evaluate_entity(
+    target: &str,
+    entry: &WordListEntry,
+    config: &MatchingConfig,
+) -> EntityEvaluation {
+    let mut rules = vec![
+        rule_a(target, entry).map_or(unmatched(MatchRule::A), |item| {
+            evaluation_from_item(MatchRule::A, target, item)
+        }),
+        rule_b(target, entry, config).map_or(unmatched(MatchRule::B), |item| {
+            evaluation_from_item(MatchRule::B, target, item)
+        }),
+        rule_c(target, entry, config).map_or(unmatched(MatchRule::C), |item| {
+            evaluation_from_item(MatchRule::C, target, item)
+        }),
+    ];
+
+    // ルールDは閾値未満でも最良のキーのスコアを示す
+    rules.push(match rule_d(target, entry, config) {
+        Some(item) => evaluation_from_item(MatchRule::D, target, item),
+        None => {
+            let best = entry
+                .search_keys_normalized
+                .iter()
+                .map(|key| (key, score_breakdown(target, key)))
+                .max_by(|a, b| a.1.score.total_cmp(&b.1.score));
+            RuleEvaluation {
+                key_normalized: best.map(|(key, _)| key.clone()),
+                derived: best.is_some_and(|(key, _)| entry.derived_keys_normalized.contains(key)),
+                score: best.map(|(_, breakdown)| breakdown),
+                ..unmatched(MatchRule::D)
+            }
+        }
+    });
+
+    EntityEvaluation {
+        entity_id: format!("{}:{}", entry.entry_type, entry.canonical),
+        entry_type: entry.entry_type.clone(),
+        canonical: entry.canonical.clone(),
+        applied_rule: rules.iter().find(|r| r.matched).map(|r| r.rule),
+        rules,
+    }
+}
+
+fn with_debug(mut item: MatchItem, resolution: Option<ResolutionStep>) -> MatchItem {
+    item.debug = Some(MatchDebug {
+        match_length: item.matched_key_normalized.chars().count(),
+        resolution,
+    });
+    item
+}
+
+/// マッチングの評価過程を説明する
+pub fn explain_match(
+    file_path: &str,
+    targets: &[MatchTarget],
+    word_list: &WordList,
+    config: &MatchingConfig,
+) -> MatchExplanation {
+    let explained_targets = targets
+        .iter()
+        .map(|target| {
+            let normalized = normalize(&target.text);
+            TargetExplanation {
+                source: target.source,
+                raw_text: target.text.clone(),
+                stages: normalization_stages(&target.text)
+                    .into_iter()
+                    .map(|(name, output)| NormalizationStage {
+                        name: name.to_string(),
+                        output,
+                    })
+                    .collect(),
+                entities: word_list
+                    .entries
+                    .iter()
+                    .map(|entry| evaluate_entity(&normalized, entry, config))
+                    .collect(),
+                normalized,
+            }
+        })
+        .collect();
+
+    let (mut result, trace) = match_targets_traced(file_path, targets, word_list, config);
+
+    // 作品の衝突で格下げされた候補には段階を記録する
+    let demoted = |item: &MatchItem| {
+        trace
+            .iter()
+            .any(|(t, step)| *step == ResolutionStep::WorkConflict && t.entity_id == item.entity_id)
+            .then_some(ResolutionStep::WorkConflict)
+    };
+    result.confirmed = std::mem::take(&mut result.confirmed)
+        .into_iter()
+        .map(|item| with_debug(item, None))
+        .collect();
+    result.candidates = std::mem::take(&mut result.candidates)
+        .into_iter()
+        .map(|item| {
+            let resolution = demoted(&item);
+            with_debug(item, resolution)
+        })
+        .collect();
+
+    let dropped = trace
+        .into_iter()
+        .filter(|(_, step)| *step != ResolutionStep::WorkConflict)
+        .map(|(item, step)| with_debug(item, Some(step)))
+        .collect();
+
+    MatchExplanation {
+        file_path: file_path.to_string(),
+        targets: explained_targets,
+        result,
+        dropped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::word_list::parse_word_list_csv;
+
+    fn target(text: &str) -> MatchTarget {
+        MatchTarget {
+            source: MatchSource::FileName,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_explain_rules_and_stages() {
+        // Arrange
+        let list = parse_word_list_csv(
+            "アリア・スターライト,Aria Starlight\nレオン,Leon Blaze",
+            "list.csv",
+        );
+
+        // Act
+        let explanation = explain_match(
+            "/src/Aria_Starlight_01.png",
+            &[target("src Aria_Starlight_01")],
+            &list,
+            &MatchingConfig::default(),
+        );
+
+        // Assert
+        let explained = &explanation.targets[0];
+        assert_eq!(explained.raw_text, "src Aria_Starlight_01");
+        assert_eq!(
+            explained.stages.last().unwrap().output,
+            explained.normalized
+        );
+
+        let aria = &explained.entities[0];
+        assert_eq!(aria.applied_rule, Some(MatchRule::B));
+        let rule_b = &aria.rules[1];
+        assert!(rule_b.matched);
+        assert_eq!(rule_b.key_normalized.as_deref(), Some("ariastarlight"));
+        let breakdown = rule_b.score.unwrap();
+        assert!((breakdown.length_bonus - 1.0).abs() < 1e-9);
+        assert!(breakdown.ngram_similarity > 0.0);
+
+        // ルールDは不一致でも最良のキーを示す
+        let leon = &explained.entities[1];
+        assert_eq!(leon.applied_rule, None);
+        assert!(!leon.rules[3].matched);
+        assert!(leon.rules[3].score.is_some());
+
+        assert_eq!(
+            explanation.result.confirmed[0]
+                .debug
+                .as_ref()
+                .unwrap()
+                .match_length,
+            13
+        );
+    }
+
+    #[test]
+    fn test_explain_work_conflict() {
+        // Arrange: 2つの作品が確定する
+        let list = parse_word_list_csv("@work,ステラナイツ\n@work,ナイツ", "list.csv");
+
+        // Act
+        let explanation = explain_match(
+            "/src/ステラナイツ.png",
+            &[target("ステラナイツ"), target("ステラナイツ")],
+            &list,
+            &MatchingConfig::default(),
+        );
+
+        // Assert: 短い方は作品の衝突で候補に格下げ
+        let demoted = &explanation.result.candidates[0];
+        assert_eq!(demoted.canonical, "ナイツ");
+        assert_eq!(
+            demoted.debug.as_ref().unwrap().resolution,
+            Some(ResolutionStep::WorkConflict)
+        );
+        // 2つ目のターゲットの重複は除外
+        assert!(explanation
+            .dropped
+            .iter()
+            .all(|item| item.debug.as_ref().unwrap().resolution
+                == Some(ResolutionStep::DuplicateEntity)));
+        assert_eq!(explanation.dropped.len(), 2);
+    }
+}
@@ -0,0 +1,339 @@
+use super::matcher::MatchingConfig;
+use super::normalize::normalize;
+use super::word_list::create_entry;
+use crate::config::settings::{WordList, WordListEntry};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 検査項目の種類
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LintKind {
+    /// 同じ canonical のエントリが複数ある
+    DuplicateCanonical,
+    /// 異なるエントリが同じ検索キーを持つ
+    KeyCollision,
+    /// 検索キーが部分一致の最小長未満
+    ShortKey,
+    /// 空の別名（正規化すると空になるものを含む）
+    EmptyAlias,
+    /// キャラクターの所属作品が未設定
+    MissingWorkId,
+}
+
+/// 検査結果
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LintIssue {
+    pub kind: LintKind,
+    /// 対象エントリ（"type:canonical"）
+    pub entity_ids: Vec<String>,
+    /// 対象の検索キー（正規化済み）
+    pub key: Option<String>,
+}
+
+/// 同一エントリが衝突した場合の扱い
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeStrategy {
+    /// 統合先のエントリを残す
+    KeepBase,
+    /// 取り込むエントリで置き換える
+    PreferIncoming,
+    /// 別名を合算する
+    UnionAliases,
+}
+
+/// 衝突の解決方法
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeResolution {
+    KeptBase,
+    UsedIncoming,
+    UnitedAliases,
+    /// 自動解決せず報告のみ（検索キーの衝突）
+    Unresolved,
+}
+
+/// 統合時の衝突
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeConflict {
+    pub kind: LintKind,
+    pub entity_ids: Vec<String>,
+    pub key: Option<String>,
+    pub resolution: MergeResolution,
+}
+
+/// 統合結果
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeResult {
+    pub word_list: WordList,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+fn entity_id(entry: &WordListEntry) -> String {
+    format!("{}:{}", entry.entry_type, entry.canonical)
+}
+
+/// 検索キーごとに、そのキーを持つエントリを集める
+fn key_owners(entries: &[WordListEntry]) -> BTreeMap<&str, BTreeSet<String>> {
+    let mut owners: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for entry in entries {
+        for key in &entry.search_keys_normalized {
+            owners.entry(key).or_default().insert(entity_id(entry));
+        }
+    }
+    owners
+}
+
+/// 単語リストを検査する
+pub fn lint_word_list(word_list: &WordList, config: &MatchingConfig) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    // canonical の重複
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for entry in &word_list.entries {
+        *counts.entry(entity_id(entry)).or_insert(0) += 1;
+    }
+    for (id, count) in counts {
+        if count > 1 {
+            issues.push(LintIssue {
+                kind: LintKind::DuplicateCanonical,
+                entity_ids: vec![id],
+                key: None,
+            });
+        }
+    }
+
+    // 検索キーの衝突・短すぎるキー
+    for (key, owners) in key_owners(&word_list.entries) {
+        if owners.len() > 1 {
+            issues.push(LintIssue {
+                kind: LintKind::KeyCollision,
+                entity_ids: owners.iter().cloned().collect(),
+                key: Some(key.to_string()),
+            });
+        }
+        if key.chars().count() < config.partial_match_min_len {
+            issues.push(LintIssue {
+                kind: LintKind::ShortKey,
+                entity_ids: owners.into_iter().collect(),
+                key: Some(key.to_string()),
+            });
+        }
+    }
+
+    for entry in &word_list.entries {
+        // 空の別名
+        if entry.aliases.iter().any(|a| normalize(a).is_empty()) {
+            issues.push(LintIssue {
+                kind: LintKind::EmptyAlias,
+                entity_ids: vec![entity_id(entry)],
+                key: None,
+            });
+        }
+        // 所属作品の未設定
+        if entry.entry_type == "character" && entry.work_id.is_none() {
+            issues.push(LintIssue {
+                kind: LintKind::MissingWorkId,
+                entity_ids: vec![entity_id(entry)],
+                key: None,
+            });
+        }
+    }
+
+    issues
+}
+
+/// 別名を合算したエントリを作り直す（派生キーと所属作品は両方から引き継ぐ）
+fn unite_entries(base: &WordListEntry, incoming: &WordListEntry) -> WordListEntry {
+    let mut aliases = base.aliases.clone();
+    for alias in &incoming.aliases {
+        if !aliases.contains(alias) {
+            aliases.push(alias.clone());
+        }
+    }
+
+    let mut entry = create_entry(&base.entry_type, &base.canonical, aliases, &[]);
+    for key in base
+        .derived_keys_normalized
+        .iter()
+        .chain(&incoming.derived_keys_normalized)
+    {
+        if !entry.search_keys_normalized.contains(key) {
+            entry.search_keys_normalized.push(key.clone());
+            entry.derived_keys_normalized.push(key.clone());
+        }
+    }
+    entry.work_id = base.work_id.clone().or_else(|| incoming.work_id.clone());
+    entry
+}
+
+/// 2つの単語リストを統合する（同一リスト内の重複も解消する）
+pub fn merge_word_lists(
+    base: &WordList,
+    incoming: &WordList,
+    strategy: MergeStrategy,
+) -> MergeResult {
+    let mut entries: Vec<WordListEntry> = Vec::new();
+    let mut positions: BTreeMap<String, usize> = BTreeMap::new();
+    let mut conflicts = Vec::new();
+
+    for (entry, from_incoming) in base
+        .entries
+        .iter()
+        .map(|e| (e, false))
+        .chain(incoming.entries.iter().map(|e| (e, true)))
+    {
+        let id = entity_id(entry);
+        let Some(&index) = positions.get(&id) else {
+            positions.insert(id, entries.len());
+            entries.push(entry.clone());
+            continue;
+        };
+
+        let existing = &entries[index];
+        if existing == entry {
+            continue;
+        }
+
+        // 同一リスト内の重複は常に別名を合算する
+        let resolution = if !from_incoming {
+            MergeResolution::UnitedAliases
+        } else {
+            match strategy {
+                MergeStrategy::KeepBase => MergeResolution::KeptBase,
+                MergeStrategy::PreferIncoming => MergeResolution::UsedIncoming,
+                MergeStrategy::UnionAliases => MergeResolution::UnitedAliases,
+            }
+        };
+        match resolution {
+            MergeResolution::UnitedAliases => entries[index] = unite_entries(existing, entry),
+            MergeResolution::UsedIncoming => entries[index] = entry.clone(),
+            _ => {}
+        }
+        conflicts.push(MergeConflict {
+            kind: LintKind::DuplicateCanonical,
+            entity_ids: vec![id],
+            key: None,
+            resolution,
+        });
+    }
+
+    // 異なるエントリ間の検索キー衝突は報告のみ
+    for (key, owners) in key_owners(&entries) {
+        if owners.len() > 1 {
+            conflicts.push(MergeConflict {
+                kind: LintKind::KeyCollision,
+                entity_ids: owners.into_iter().collect(),
+                key: Some(key.to_string()),
+                resolution: MergeResolution::Unresolved,
+            });
+        }
+    }
+
+    MergeResult {
+        word_list: WordList {
+            file_name: base.file_name.clone(),
+            // 統合結果はCSVと内容が異なるため監視対象から外す
+            source_path: None,
+            entries,
+        },
+        conflicts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::word_list::parse_word_list_csv;
+
+    #[test]
+    fn test_lint_word_list() {
+        // Arrange
+        let list = parse_word_list_csv(
+            "月影,Moon,,・・\n@work,ステラナイツ,SK\nアリア,Aria Starlight\nアリア,Aria\nレオン,Starlight",
+            "list.csv",
+        );
+
+        // Act
+        let issues = lint_word_list(&list, &MatchingConfig::default());
+        let find = |kind: LintKind| issues.iter().filter(move |i| i.kind == kind);
+
+        // Assert
+        assert_eq!(
+            find(LintKind::DuplicateCanonical)
+                .next()
+                .unwrap()
+                .entity_ids,
+            vec!["character:アリア"]
+        );
+        let collision = find(LintKind::KeyCollision)
+            .find(|i| i.key.as_deref() == Some("starlight"))
+            .unwrap();
+        assert_eq!(
+            collision.entity_ids,
+            vec!["character:アリア", "character:レオン"]
+        );
+        assert!(find(LintKind::ShortKey).any(|i| i.key.as_deref() == Some("sk")));
+        assert_eq!(
+            find(LintKind::EmptyAlias).next().unwrap().entity_ids,
+            vec!["character:月影"]
+        );
+        // @work より前の月影のみ所属作品なし
+        let missing: Vec<_> = find(LintKind::MissingWorkId).collect();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].entity_ids, vec!["character:月影"]);
+    }
+
+    #[test]
+    fn test_merge_word_lists_strategies() {
+        // Arrange
+        let base = parse_word_list_csv("アリア,Aria\nレオン,Leon", "base.csv");
+        let incoming = parse_word_list_csv("アリア,Aria Starlight\nセレナ,Serena", "new.csv");
+
+        // Act
+        let united = merge_word_lists(&base, &incoming, MergeStrategy::UnionAliases);
+        let kept = merge_word_lists(&base, &incoming, MergeStrategy::KeepBase);
+        let replaced = merge_word_lists(&base, &incoming, MergeStrategy::PreferIncoming);
+
+        // Assert
+        let canonicals: Vec<&str> = united
+            .word_list
+            .entries
+            .iter()
+            .map(|e| e.canonical.as_str())
+            .collect();
+        assert_eq!(canonicals, vec!["アリア", "レオン", "セレナ"]);
+        assert_eq!(
+            united.word_list.entries[0].aliases,
+            vec!["Aria", "Aria Starlight"]
+        );
+        assert_eq!(kept.word_list.entries[0].aliases, vec!["Aria"]);
+        assert_eq!(
+            replaced.word_list.entries[0].aliases,
+            vec!["Aria Starlight"]
+        );
+        assert_eq!(united.conflicts.len(), 1);
+        assert_eq!(
+            united.conflicts[0].resolution,
+            MergeResolution::UnitedAliases
+        );
+        assert_eq!(kept.conflicts[0].resolution, MergeResolution::KeptBase);
+    }
+
+    #[test]
+    fn test_merge_reports_key_collision() {
+        let base = parse_word_list_csv("アリア,Star", "base.csv");
+        let incoming = parse_word_list_csv("レオン,Star", "new.csv");
+
+        let result = merge_word_lists(&base, &incoming, MergeStrategy::UnionAliases);
+
+        assert_eq!(result.word_list.entries.len(), 2);
+        assert_eq!(result.conflicts[0].kind, LintKind::KeyCollision);
+        assert_eq!(result.conflicts[0].key.as_deref(), Some("star"));
+        assert_eq!(result.conflicts[0].resolution, MergeResolution::Unresolved);
+    }
+}
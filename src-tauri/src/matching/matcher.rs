@@ -0,0 +1,708 @@
+use super::normalize::{has_mask, normalize, split_mask_tokens, strip_mask_tokens, Segment};
+use crate::config::options::MatchSource;
+use crate::config::settings::{WordList, WordListEntry};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// マッチングルール
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MatchRule {
+    /// 完全一致
+    A,
+    /// 部分一致
+    B,
+    /// 伏せ字ワイルドカード一致
+    C,
+    /// あいまい一致（候補のみ）
+    D,
+}
+
+/// マッチング対象テキスト
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchTarget {
+    pub source: MatchSource,
+    pub text: String,
+}
+
+/// マッチした項目
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchItem {
+    pub entity_id: String,
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    pub canonical: String,
+    pub matched_key: String,
+    pub matched_key_normalized: String,
+    pub rule: MatchRule,
+    pub score: f64,
+    pub source: MatchSource,
+    /// 派生キー（ローマ字・読み等）による一致か
+    pub derived: bool,
+    /// 内部値（explain_match のみ設定、SPEC.md 9章の debug）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debug: Option<MatchDebug>,
+}
+
+/// 衝突処理の段階（どの段階でマッチが落とされたか）
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ResolutionStep {
+    /// 別のターゲットで同じ項目が先にマッチ済み
+    DuplicateEntity,
+    /// 確定済みの項目のため候補から除外
+    AlreadyConfirmed,
+    /// 作品の最長一致に負けて候補に格下げ（SPEC.md 8.1）
+    WorkConflict,
+    /// 候補最大数を超えたため除外
+    CandidateLimit,
+}
+
+/// マッチ項目の内部値
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchDebug {
+    pub match_length: usize,
+    /// このマッチを格下げ・除外した衝突処理の段階
+    pub resolution: Option<ResolutionStep>,
+}
+
+/// スコアの内訳
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreBreakdown {
+    pub ngram_similarity: f64,
+    pub length_bonus: f64,
+    pub score: f64,
+}
+
+/// ファイルごとのマッチ結果
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchResult {
+    pub file_path: String,
+    pub target_text: String,
+    pub target_normalized: String,
+    pub confirmed: Vec<MatchItem>,
+    pub candidates: Vec<MatchItem>,
+    pub unmatched: bool,
+}
+
+/// マッチング設定（SPEC.md 11章のデフォルト値）
+#[derive(Debug, Clone)]
+pub struct MatchingConfig {
+    /// ルールB確定条件（デフォルト4）
+    pub partial_match_min_len: usize,
+    /// ルールC確定条件（デフォルト6）
+    pub wildcard_match_min_len: usize,
+    /// 伏せ字許容量（デフォルト2）
+    pub wildcard_tolerance_k: usize,
+    /// 候補最大数（デフォルト5）
+    pub candidate_max_count: usize,
+    /// 候補最小スコア（デフォルト0.80）
+    pub candidate_min_score: f64,
+    /// 強候補スコア（デフォルト0.92）
+    pub strong_candidate_score: f64,
+}
+
+impl Default for MatchingConfig {
+    fn default() -> Self {
+        Self {
+            partial_match_min_len: 4,
+            wildcard_match_min_len: 6,
+            wildcard_tolerance_k: 2,
+            candidate_max_count: 5,
+            candidate_min_score: 0.80,
+            strong_candidate_score: 0.92,
+        }
+    }
+}
+
+// =========================
+// n-gram 類似度
+// =========================
+
+pub(crate) fn get_ngrams(s: &str, n: usize) -> HashSet<String> {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() < n {
+        return HashSet::new();
+    }
+    chars.windows(n).map(|w| w.iter().collect()).collect()
+}
+
+fn jaccard_similarity(set1: &HashSet<String>, set2: &HashSet<String>) -> f64 {
+    if set1.is_empty() && set2.is_empty() {
+        return 1.0;
+    }
+    if set1.is_empty() || set2.is_empty() {
+        return 0.0;
+    }
+
+    let intersection = set1.intersection(set2).count();
+    let union = set1.len() + set2.len() - intersection;
+    if union == 0 {
+        0.0
+    } else {
+        intersection as f64 / union as f64
+    }
+}
+
+/// score = 0.7 * ngramSimilarity + 0.3 * lengthBonus の内訳
+pub fn score_breakdown(target_normalized: &str, key_normalized: &str) -> ScoreBreakdown {
+    let ngram_similarity = jaccard_similarity(
+        &get_ngrams(target_normalized, 2),
+        &get_ngrams(key_normalized, 2),
+    );
+    let length_bonus = (key_normalized.chars().count() as f64 / 12.0).min(1.0);
+    ScoreBreakdown {
+        ngram_similarity,
+        length_bonus,
+        score: 0.7 * ngram_similarity + 0.3 * length_bonus,
+    }
+}
+
+/// score = 0.7 * ngramSimilarity + 0.3 * lengthBonus
+pub fn calculate_score(target_normalized: &str, key_normalized: &str) -> f64 {
+    score_breakdown(target_normalized, key_normalized).score
+}
+
+// =========================
+// ヘルパー関数
+// =========================
+
+/// CJK文字（漢字・ひらがな・カタカナ）を含むかどうか判定
+fn is_cjk(s: &str) -> bool {
+    s.chars().any(|c| {
+        ('\u{3040}'..='\u{309F}').contains(&c)
+            || ('\u{30A0}'..='\u{30FF}').contains(&c)
+            || ('\u{4E00}'..='\u{9FFF}').contains(&c)
+    })
+}
+
+/// 正規化済みキーから元の表記（canonical または alias）を逆引き
+/// 派生キーは元の表記を持たないため、正規化済みキーをそのまま返す
+fn raw_key_for(entry: &WordListEntry, key: &str) -> String {
+    if entry.derived_keys_normalized.iter().any(|k| k == key) {
+        return key.to_string();
+    }
+    if entry.canonical_normalized == key {
+        return entry.canonical.clone();
+    }
+    entry
+        .aliases_normalized
+        .iter()
+        .position(|a| a == key)
+        .and_then(|i| entry.aliases.get(i).cloned())
+        .unwrap_or_else(|| entry.canonical.clone())
+}
+
+fn make_item(entry: &WordListEntry, key: &str, rule: MatchRule, score: f64) -> MatchItem {
+    MatchItem {
+        entity_id: format!("{}:{}", entry.entry_type, entry.canonical),
+        entry_type: entry.entry_type.clone(),
+        canonical: entry.canonical.clone(),
+        matched_key: raw_key_for(entry, key),
+        matched_key_normalized: key.to_string(),
+        rule,
+        score,
+        source: MatchSource::FileName,
+        derived: entry.derived_keys_normalized.iter().any(|k| k == key),
+        debug: None,
+    }
+}
+
+/// 伏せ字パターンがキー全体に一致するか（伏せ字1文字 → 0〜k文字）
+fn wildcard_match(segments: &[Segment], key: &[char], k: usize) -> bool {
+    match segments.split_first() {
+        None => key.is_empty(),
+        Some((Segment::Text(text), rest)) => {
+            let text: Vec<char> = text.chars().collect();
+            key.starts_with(&text) && wildcard_match(rest, &key[text.len()..], k)
+        }
+        Some((Segment::Mask(len), rest)) => {
+            let max = (len * k).min(key.len());
+            (0..=max).any(|i| wildcard_match(rest, &key[i..], k))
+        }
+    }
+}
+
+// =========================
+// マッチングルール
+// =========================
+
+pub(crate) fn rule_a(target_normalized: &str, entry: &WordListEntry) -> Option<MatchItem> {
+    // 完全一致
+    entry
+        .search_keys_normalized
+        .iter()
+        .find(|key| target_normalized == key.as_str())
+        .map(|key| make_item(entry, key, MatchRule::A, 1.0))
+}
+
+pub(crate) fn rule_b(
+    target_normalized: &str,
+    entry: &WordListEntry,
+    config: &MatchingConfig,
+) -> Option<MatchItem> {
+    // 部分一致
+    entry.search_keys_normalized.iter().find_map(|key| {
+        // CJK文字を含む場合は最小長1、それ以外は設定値(4)
+        let min_len = if is_cjk(key) {
+            1
+        } else {
+            config.partial_match_min_len
+        };
+        if key.chars().count() >= min_len && target_normalized.contains(key.as_str()) {
+            Some(make_item(
+                entry,
+                key,
+                MatchRule::B,
+                calculate_score(target_normalized, key),
+            ))
+        } else {
+            None
+        }
+    })
+}
+
+pub(crate) fn rule_c(
+    target_normalized: &str,
+    entry: &WordListEntry,
+    config: &MatchingConfig,
+) -> Option<MatchItem> {
+    // 伏せ字ワイルドカード一致
+    if !has_mask(target_normalized) {
+        return None;
+    }
+
+    let segments = split_mask_tokens(target_normalized);
+    let unmasked = strip_mask_tokens(target_normalized);
+
+    entry.search_keys_normalized.iter().find_map(|key| {
+        let key_chars: Vec<char> = key.chars().collect();
+        if key_chars.len() >= config.wildcard_match_min_len
+            && wildcard_match(&segments, &key_chars, config.wildcard_tolerance_k)
+        {
+            Some(make_item(
+                entry,
+                key,
+                MatchRule::C,
+                calculate_score(&unmasked, key),
+            ))
+        } else {
+            None
+        }
+    })
+}
+
+pub(crate) fn rule_d(
+    target_normalized: &str,
+    entry: &WordListEntry,
+    config: &MatchingConfig,
+) -> Option<MatchItem> {
+    // あいまい一致（候補のみ）
+    let mut best: Option<(f64, &String)> = None;
+
+    for key in &entry.search_keys_normalized {
+        let score = calculate_score(target_normalized, key);
+        if score >= config.candidate_min_score && best.map_or(true, |(b, _)| score > b) {
+            best = Some((score, key));
+        }
+    }
+
+    best.map(|(score, key)| make_item(entry, key, MatchRule::D, score))
+}
+
+// =========================
+// 衝突処理
+// =========================
+
+/// 作品が複数確定した場合は最長一致の1件に絞り、残りは候補に落とす（SPEC.md 8.1）
+/// 格下げした項目を返す
+fn resolve_work_conflicts(
+    confirmed: &mut Vec<MatchItem>,
+    candidates: &mut Vec<MatchItem>,
+) -> Vec<MatchItem> {
+    let best_work = confirmed
+        .iter()
+        .filter(|item| item.entry_type == "work")
+        .max_by(|a, b| {
+            a.matched_key_normalized
+                .chars()
+                .count()
+                .cmp(&b.matched_key_normalized.chars().count())
+                // 同長の場合は canonical の辞書順で安定化
+                .then_with(|| b.canonical.cmp(&a.canonical))
+        })
+        .map(|item| item.entity_id.clone());
+
+    let Some(best_work) = best_work else {
+        return Vec::new();
+    };
+
+    let (kept, dropped): (Vec<MatchItem>, Vec<MatchItem>) = std::mem::take(confirmed)
+        .into_iter()
+        .partition(|item| item.entry_type != "work" || item.entity_id == best_work);
+    *confirmed = kept;
+    candidates.extend(dropped.iter().cloned());
+    dropped
+}
+
+// =========================
+// メイン関数
+// =========================
+
+/// 1つのターゲット文字列に対して全項目のルールを評価する
+pub(crate) fn evaluate_target(
+    target_normalized: &str,
+    source: MatchSource,
+    word_list: &WordList,
+    config: &MatchingConfig,
+) -> (Vec<MatchItem>, Vec<MatchItem>) {
+    let mut confirmed = Vec::new();
+    let mut candidates = Vec::new();
+
+    for entry in &word_list.entries {
+        // ルールA: 完全一致
+        if let Some(item) = rule_a(target_normalized, entry) {
+            confirmed.push(MatchItem { source, ..item });
+            continue;
+        }
+
+        // ルールB: 部分一致
+        if let Some(item) = rule_b(target_normalized, entry, config) {
+            confirmed.push(MatchItem { source, ..item });
+            continue;
+        }
+
+        // ルールC: 伏せ字ワイルドカード
+        if let Some(item) = rule_c(target_normalized, entry, config) {
+            if item.score >= config.strong_candidate_score {
+                confirmed.push(MatchItem { source, ..item });
+            } else {
+                candidates.push(MatchItem { source, ..item });
+            }
+            continue;
+        }
+
+        // ルールD: あいまい一致
+        if let Some(item) = rule_d(target_normalized, entry, config) {
+            candidates.push(MatchItem { source, ..item });
+        }
+    }
+
+    (confirmed, candidates)
+}
+
+/// 同じ項目が複数のターゲットでマッチした場合は先に評価したものを残す
+/// (残した項目, 除いた項目) を返す
+fn dedupe_by_entity(items: Vec<MatchItem>) -> (Vec<MatchItem>, Vec<MatchItem>) {
+    let mut seen = HashSet::new();
+    items
+        .into_iter()
+        .partition(|item| seen.insert(item.entity_id.clone()))
+}
+
+/// マッチングを行い、衝突処理で格下げ・除外した項目と段階も返す
+pub(crate) fn match_targets_traced(
+    file_path: &str,
+    targets: &[MatchTarget],
+    word_list: &WordList,
+    config: &MatchingConfig,
+) -> (MatchResult, Vec<(MatchItem, ResolutionStep)>) {
+    let mut confirmed = Vec::new();
+    let mut candidates = Vec::new();
+    let mut normalized_targets = Vec::new();
+    let mut trace = Vec::new();
+
+    for target in targets {
+        let target_normalized = normalize(&target.text);
+        let (c, d) = evaluate_target(&target_normalized, target.source, word_list, config);
+        confirmed.extend(c);
+        candidates.extend(d);
+        normalized_targets.push(target_normalized);
+    }
+
+    let (mut confirmed, duplicates) = dedupe_by_entity(confirmed);
+    trace.extend(
+        duplicates
+            .into_iter()
+            .map(|i| (i, ResolutionStep::DuplicateEntity)),
+    );
+
+    // 確定済みの項目は候補から除く
+    let confirmed_ids: HashSet<String> = confirmed.iter().map(|i| i.entity_id.clone()).collect();
+    let (already, rest): (Vec<MatchItem>, Vec<MatchItem>) = candidates
+        .into_iter()
+        .partition(|item| confirmed_ids.contains(&item.entity_id));
+    trace.extend(
+        already
+            .into_iter()
+            .map(|i| (i, ResolutionStep::AlreadyConfirmed)),
+    );
+
+    let mut candidates = rest;
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let (mut candidates, duplicates) = dedupe_by_entity(candidates);
+    trace.extend(
+        duplicates
+            .into_iter()
+            .map(|i| (i, ResolutionStep::DuplicateEntity)),
+    );
+
+    let demoted = resolve_work_conflicts(&mut confirmed, &mut candidates);
+    trace.extend(
+        demoted
+            .into_iter()
+            .map(|i| (i, ResolutionStep::WorkConflict)),
+    );
+
+    // 候補をスコア降順でソートし、上位N件に絞る
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    if candidates.len() > config.candidate_max_count {
+        let truncated = candidates.split_off(config.candidate_max_count);
+        trace.extend(
+            truncated
+                .into_iter()
+                .map(|i| (i, ResolutionStep::CandidateLimit)),
+        );
+    }
+
+    let result = MatchResult {
+        file_path: file_path.to_string(),
+        target_text: targets.first().map(|t| t.text.clone()).unwrap_or_default(),
+        target_normalized: normalized_targets.into_iter().next().unwrap_or_default(),
+        unmatched: confirmed.is_empty(),
+        confirmed,
+        candidates,
+    };
+    (result, trace)
+}
+
+/// 複数のターゲット文字列（ファイル名・メタデータ）と単語リストをマッチング
+/// 先頭のターゲットを結果の targetText とする
+pub fn match_targets(
+    file_path: &str,
+    targets: &[MatchTarget],
+    word_list: &WordList,
+    config: &MatchingConfig,
+) -> MatchResult {
+    match_targets_traced(file_path, targets, word_list, config).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::normalize::create_target_text;
+
+    /// 生成済みのターゲット文字列と単語リストをマッチング
+    fn match_target(
+        file_path: &str,
+        target_text: &str,
+        word_list: &WordList,
+        config: &MatchingConfig,
+    ) -> MatchResult {
+        let target = MatchTarget {
+            source: MatchSource::FileName,
+            text: target_text.to_string(),
+        };
+        match_targets(file_path, &[target], word_list, config)
+    }
+
+    /// ファイル名から生成したターゲット文字列と単語リストをマッチング
+    fn match_file(file_path: &str, word_list: &WordList, config: &MatchingConfig) -> MatchResult {
+        match_target(file_path, &create_target_text(file_path), word_list, config)
+    }
+
+    fn entry(entry_type: &str, canonical: &str, aliases: &[&str]) -> WordListEntry {
+        let canonical_normalized = normalize(canonical);
+        let aliases_normalized: Vec<String> = aliases.iter().map(|a| normalize(a)).collect();
+        let mut search_keys_normalized = vec![canonical_normalized.clone()];
+        for key in &aliases_normalized {
+            if !search_keys_normalized.contains(key) {
+                search_keys_normalized.push(key.clone());
+            }
+        }
+        WordListEntry {
+            entry_type: entry_type.to_string(),
+            canonical: canonical.to_string(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            canonical_normalized,
+            aliases_normalized,
+            search_keys_normalized,
+            derived_keys_normalized: vec![],
+            work_id: None,
+        }
+    }
+
+    fn sample_list() -> WordList {
+        WordList {
+            file_name: "sample.csv".to_string(),
+            source_path: None,
+            entries: vec![
+                entry("work", "ステラナイツ", &["Stellar Knights", "SK"]),
+                entry("character", "アリア・スターライト", &["Aria Starlight"]),
+                entry("character", "レオン・ブレイズ", &["Leon Blaze"]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_rule_a_exact_match() {
+        let result = match_file(
+            "/Aria Starlight.jpg",
+            &sample_list(),
+            &MatchingConfig::default(),
+        );
+
+        assert!(!result.unmatched);
+        assert_eq!(result.confirmed.len(), 1);
+        assert_eq!(result.confirmed[0].rule, MatchRule::A);
+        assert_eq!(result.confirmed[0].matched_key, "Aria Starlight");
+        assert_eq!(result.confirmed[0].score, 1.0);
+    }
+
+    #[test]
+    fn test_rule_b_partial_match() {
+        let result = match_file(
+            "/inbox/stellar_knights/leon_blaze_001.png",
+            &sample_list(),
+            &MatchingConfig::default(),
+        );
+
+        let ids: Vec<&str> = result
+            .confirmed
+            .iter()
+            .map(|i| i.entity_id.as_str())
+            .collect();
+        assert!(ids.contains(&"work:ステラナイツ"));
+        assert!(ids.contains(&"character:レオン・ブレイズ"));
+        assert!(result.confirmed.iter().all(|i| i.rule == MatchRule::B));
+    }
+
+    #[test]
+    fn test_rule_b_short_key_not_confirmed() {
+        // "sk" は4文字未満のため部分一致しない
+        let result = match_file(
+            "/inbox/sk_01.jpg",
+            &sample_list(),
+            &MatchingConfig::default(),
+        );
+        assert!(result.unmatched);
+    }
+
+    #[test]
+    fn test_rule_c_wildcard_match() {
+        let list = WordList {
+            file_name: "w.csv".to_string(),
+            source_path: None,
+            entries: vec![entry("character", "アレクサンダー", &[])],
+        };
+        let result = match_target("x", "アレク〇ンダー", &list, &MatchingConfig::default());
+
+        assert_eq!(result.confirmed.len() + result.candidates.len(), 1);
+        let item = result
+            .confirmed
+            .first()
+            .or(result.candidates.first())
+            .unwrap();
+        assert_eq!(item.rule, MatchRule::C);
+    }
+
+    #[test]
+    fn test_wildcard_match_tolerance() {
+        let segments = split_mask_tokens("ab<MASK:1>cd");
+        let key = |s: &str| s.chars().collect::<Vec<char>>();
+        assert!(wildcard_match(&segments, &key("abcd"), 2));
+        assert!(wildcard_match(&segments, &key("abxxcd"), 2));
+        assert!(!wildcard_match(&segments, &key("abxxxcd"), 2));
+    }
+
+    #[test]
+    fn test_work_conflict_longest_wins() {
+        let list = WordList {
+            file_name: "w.csv".to_string(),
+            source_path: None,
+            entries: vec![
+                entry("work", "ステラ", &[]),
+                entry("work", "ステラナイツ", &[]),
+            ],
+        };
+        let result = match_target("x", "ステラナイツ 01", &list, &MatchingConfig::default());
+
+        assert_eq!(result.confirmed.len(), 1);
+        assert_eq!(result.confirmed[0].canonical, "ステラナイツ");
+        assert!(result.candidates.iter().any(|i| i.canonical == "ステラ"));
+    }
+
+    #[test]
+    fn test_match_targets_reports_source() {
+        let targets = vec![
+            MatchTarget {
+                source: MatchSource::FileName,
+                text: "inbox IMG_0001".to_string(),
+            },
+            MatchTarget {
+                source: MatchSource::XmpSubject,
+                text: "Leon Blaze".to_string(),
+            },
+            MatchTarget {
+                source: MatchSource::ExifDescription,
+                text: "leon blaze at the beach".to_string(),
+            },
+        ];
+
+        let result = match_targets(
+            "/inbox/IMG_0001.jpg",
+            &targets,
+            &sample_list(),
+            &MatchingConfig::default(),
+        );
+
+        assert_eq!(result.target_text, "inbox IMG_0001");
+        assert_eq!(result.confirmed.len(), 1);
+        assert_eq!(result.confirmed[0].source, MatchSource::XmpSubject);
+        assert_eq!(result.confirmed[0].rule, MatchRule::A);
+    }
+
+    #[test]
+    fn test_unmatched() {
+        let result = match_file(
+            "/misc/IMG_0001.jpg",
+            &sample_list(),
+            &MatchingConfig::default(),
+        );
+        assert!(result.unmatched);
+        assert!(result.confirmed.is_empty());
+    }
+
+    #[test]
+    fn test_derived_key_match() {
+        // Arrange: 読み列から生成したローマ字キー
+        let list = WordList {
+            file_name: "sample.csv".to_string(),
+            source_path: None,
+            entries: vec![crate::matching::word_list::create_entry(
+                "character",
+                "月影",
+                vec![],
+                &["つきかげ".to_string()],
+            )],
+        };
+
+        // Act
+        let result = match_file("/src/tsukikage_01.png", &list, &MatchingConfig::default());
+
+        // Assert
+        assert_eq!(result.confirmed.len(), 1);
+        assert!(result.confirmed[0].derived);
+        assert_eq!(result.confirmed[0].matched_key, "tsukikage");
+        assert_eq!(result.confirmed[0].canonical, "月影");
+    }
+
+    #[test]
+    fn test_calculate_score() {
+        assert!((calculate_score("abcdefghijkl", "abcdefghijkl") - 1.0).abs() < 1e-9);
+        assert!(calculate_score("abcd", "wxyz") < 0.2);
+    }
+}
@@ -0,0 +1,319 @@
+use super::matcher::{calculate_score, MatchingConfig};
+use super::normalize::{normalize, strip_mask_tokens};
+use crate::config::settings::WordList;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// 別名候補抽出の設定
+#[derive(Debug, Clone)]
+pub struct MiningConfig {
+    /// 近似一致とみなす最小スコア（デフォルト0.65）
+    pub near_miss_min_score: f64,
+    /// 近似一致の上限スコア（候補最小スコア未満）
+    pub near_miss_max_score: f64,
+    /// 頻出語とみなす最小ファイル数（デフォルト2）
+    pub min_occurrences: usize,
+    /// 例示ファイルの最大数（デフォルト3）
+    pub max_examples: usize,
+    /// 頻出語の最大件数（デフォルト20）
+    pub max_frequent: usize,
+    /// 連結するトークン数の上限（デフォルト3）
+    pub max_window_tokens: usize,
+}
+
+impl Default for MiningConfig {
+    fn default() -> Self {
+        Self {
+            near_miss_min_score: 0.65,
+            near_miss_max_score: MatchingConfig::default().candidate_min_score,
+            min_occurrences: 2,
+            max_examples: 3,
+            max_frequent: 20,
+            max_window_tokens: 3,
+        }
+    }
+}
+
+/// 別名追加の提案
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasProposal {
+    /// 単語リストのスロット（"0"〜"9"）
+    pub key: String,
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    pub canonical: String,
+    /// 追加する別名（ファイル名中の表記）
+    pub alias: String,
+    pub alias_normalized: String,
+    /// 最も近かった既存キー（正規化済み）
+    pub nearest_key_normalized: String,
+    pub score: f64,
+    pub occurrences: usize,
+    pub example_files: Vec<String>,
+}
+
+/// 未登録の頻出語
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FrequentTerm {
+    pub text: String,
+    pub normalized: String,
+    pub occurrences: usize,
+    pub example_files: Vec<String>,
+}
+
+/// 別名候補抽出の結果
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasMiningReport {
+    pub proposals: Vec<AliasProposal>,
+    pub frequent_terms: Vec<FrequentTerm>,
+}
+
+/// ファイル名（拡張子なし）を区切り文字でトークンに分割する
+fn tokenize(file_path: &str) -> Vec<String> {
+    let normalized_path = file_path.replace('\\', "/");
+    let stem = Path::new(&normalized_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    stem.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// 連続トークンの組み合わせ（表記, 正規化済み）を列挙する
+fn windows(tokens: &[String], max_tokens: usize) -> Vec<(String, String)> {
+    let mut result = Vec::new();
+    for size in 1..=max_tokens.min(tokens.len()) {
+        for window in tokens.windows(size) {
+            let text = window.join(" ");
+            let normalized = strip_mask_tokens(&normalize(&text));
+            // 数字のみ・1文字以下は対象外
+            if normalized.chars().count() < 2 || normalized.chars().all(|c| c.is_ascii_digit()) {
+                continue;
+            }
+            result.push((text, normalized));
+        }
+    }
+    result
+}
+
+/// 集計中の提案
+struct Accumulator {
+    text: String,
+    score: f64,
+    nearest: String,
+    files: BTreeSet<String>,
+}
+
+/// 未マッチのファイル名から別名候補と頻出語を抽出する
+pub fn mine_aliases(
+    file_paths: &[String],
+    word_lists: &BTreeMap<String, &WordList>,
+    config: &MiningConfig,
+) -> AliasMiningReport {
+    // 既存キー（正規化済み）
+    let known_keys: BTreeSet<&str> = word_lists
+        .values()
+        .flat_map(|list| list.entries.iter())
+        .flat_map(|entry| entry.search_keys_normalized.iter().map(String::as_str))
+        .collect();
+
+    // (スロット, type, canonical, 別名) → 集計
+    let mut proposals: BTreeMap<(String, String, String, String), Accumulator> = BTreeMap::new();
+    // 正規化済み語 → 集計
+    let mut terms: BTreeMap<String, Accumulator> = BTreeMap::new();
+
+    for file_path in file_paths {
+        for (text, normalized) in windows(&tokenize(file_path), config.max_window_tokens) {
+            if known_keys.contains(normalized.as_str()) {
+                continue;
+            }
+
+            terms
+                .entry(normalized.clone())
+                .or_insert_with(|| Accumulator {
+                    text: text.clone(),
+                    score: 0.0,
+                    nearest: String::new(),
+                    files: BTreeSet::new(),
+                })
+                .files
+                .insert(file_path.clone());
+
+            let target = normalized.as_str();
+            for (slot, list) in word_lists {
+                // スロット内で最も近いエントリ
+                let best = list
+                    .entries
+                    .iter()
+                    .flat_map(|entry| {
+                        entry
+                            .search_keys_normalized
+                            .iter()
+                            .map(move |key| (entry, key, calculate_score(target, key)))
+                    })
+                    .max_by(|a, b| a.2.total_cmp(&b.2));
+                let Some((entry, key, score)) = best else {
+                    continue;
+                };
+                if score < config.near_miss_min_score || score >= config.near_miss_max_score {
+                    continue;
+                }
+
+                let acc = proposals
+                    .entry((
+                        slot.clone(),
+                        entry.entry_type.clone(),
+                        entry.canonical.clone(),
+                        normalized.clone(),
+                    ))
+                    .or_insert_with(|| Accumulator {
+                        text: text.clone(),
+                        score,
+                        nearest: key.clone(),
+                        files: BTreeSet::new(),
+                    });
+                acc.files.insert(file_path.clone());
+            }
+        }
+    }
+
+    let mut report = AliasMiningReport::default();
+
+    for ((key, entry_type, canonical, alias_normalized), acc) in proposals {
+        report.proposals.push(AliasProposal {
+            key,
+            entry_type,
+            canonical,
+            alias: acc.text,
+            alias_normalized,
+            nearest_key_normalized: acc.nearest,
+            score: acc.score,
+            occurrences: acc.files.len(),
+            example_files: acc.files.into_iter().take(config.max_examples).collect(),
+        });
+    }
+    // 出現数 → スコア降順
+    report.proposals.sort_by(|a, b| {
+        b.occurrences
+            .cmp(&a.occurrences)
+            .then_with(|| b.score.total_cmp(&a.score))
+            .then_with(|| a.alias_normalized.cmp(&b.alias_normalized))
+    });
+
+    for (normalized, acc) in terms {
+        if acc.files.len() < config.min_occurrences {
+            continue;
+        }
+        report.frequent_terms.push(FrequentTerm {
+            text: acc.text,
+            normalized,
+            occurrences: acc.files.len(),
+            example_files: acc.files.into_iter().take(config.max_examples).collect(),
+        });
+    }
+    // 出現数降順、同数なら長い語を優先
+    report.frequent_terms.sort_by(|a, b| {
+        b.occurrences
+            .cmp(&a.occurrences)
+            .then_with(|| {
+                b.normalized
+                    .chars()
+                    .count()
+                    .cmp(&a.normalized.chars().count())
+            })
+            .then_with(|| a.normalized.cmp(&b.normalized))
+    });
+    report.frequent_terms.truncate(config.max_frequent);
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::settings::WordListEntry;
+
+    fn word_list(canonical: &str) -> WordList {
+        WordList {
+            file_name: "list.csv".to_string(),
+            source_path: None,
+            entries: vec![WordListEntry {
+                entry_type: "character".to_string(),
+                canonical: canonical.to_string(),
+                aliases: vec![],
+                canonical_normalized: normalize(canonical),
+                aliases_normalized: vec![],
+                search_keys_normalized: vec![normalize(canonical)],
+                derived_keys_normalized: vec![],
+                work_id: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("C:\\images\\aria_starlite-001.png"),
+            vec!["aria", "starlite", "001"]
+        );
+    }
+
+    #[test]
+    fn test_mine_near_miss_alias() {
+        // Arrange
+        let list = word_list("Aria Starlight");
+        let mut lists = BTreeMap::new();
+        lists.insert("1".to_string(), &list);
+        let files = vec![
+            "/src/aria_starlite_001.png".to_string(),
+            "/src/aria_starlite_002.png".to_string(),
+            "/src/unrelated_003.png".to_string(),
+        ];
+
+        // Act
+        let report = mine_aliases(&files, &lists, &MiningConfig::default());
+
+        // Assert
+        let proposal = report
+            .proposals
+            .iter()
+            .find(|p| p.alias_normalized == "ariastarlite")
+            .unwrap();
+        assert_eq!(proposal.key, "1");
+        assert_eq!(proposal.canonical, "Aria Starlight");
+        assert_eq!(proposal.alias, "aria starlite");
+        assert_eq!(proposal.occurrences, 2);
+        assert!(proposal.score >= 0.65 && proposal.score < 0.80);
+        assert_eq!(report.proposals[0].alias_normalized, "ariastarlite");
+    }
+
+    #[test]
+    fn test_mine_frequent_terms() {
+        // Arrange
+        let list = word_list("Aria Starlight");
+        let mut lists = BTreeMap::new();
+        lists.insert("1".to_string(), &list);
+        let files = vec![
+            "/src/night_sky_01.png".to_string(),
+            "/src/night_sky_02.png".to_string(),
+            "/src/aria_starlight_03.png".to_string(),
+        ];
+
+        // Act
+        let report = mine_aliases(&files, &lists, &MiningConfig::default());
+
+        // Assert: 頻出語は長いものが先、数字のみ・既存キーは除外
+        assert_eq!(report.frequent_terms[0].normalized, "nightsky");
+        assert_eq!(report.frequent_terms[0].occurrences, 2);
+        assert!(report
+            .frequent_terms
+            .iter()
+            .all(|t| t.normalized != "ariastarlight" && t.normalized != "01"));
+    }
+}
@@ -0,0 +1,12 @@
+pub mod classifier;
+pub mod explain;
+pub mod lint;
+pub mod matcher;
+pub mod mining;
+pub mod normalize;
+pub mod reading;
+pub mod sources;
+pub mod word_list;
+
+pub use matcher::{match_targets, MatchItem, MatchRule, MatchingConfig};
+pub use sources::collect_targets;
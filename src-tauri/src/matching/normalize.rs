@@ -0,0 +1,350 @@
+use unicode_normalization::UnicodeNormalization;
+
+// =========================
+// 1) MASK (伏せ字) - 保持対象
+// =========================
+
+const MASK_SINGLE_CHARS: &[char] = &['〇', '◯', '○', '●', '*', '?'];
+
+const MASK_TOKEN_PREFIX: &str = "<MASK:";
+const MASK_TOKEN_SUFFIX: &str = ">";
+
+// =========================
+// 2) SEPARATORS (区切り文字 → スペース化)
+// =========================
+
+const SEPARATOR_CHARS: &[char] = &[
+    ' ', '\u{3000}', '-', '‐', '‒', '–', '—', '―', '−', '・', '･', '·', '•', '/', '／', '\\', '＼',
+    '|', '｜', '¦', ',', '，', '、', '.', '．', '。', ':', '：', ';', '；', '~', '〜', '～', '+',
+    '＋', '=', '＝', '#', '＃', '@', '＠', '&', '＆', '!', '！', '‼', '？', '?', '_',
+];
+
+// =========================
+// 3) DECORATIONS (装飾記号 → 削除)
+// =========================
+
+const DECORATION_CHARS: &[char] = &[
+    '(', ')', '[', ']', '{', '}', '<', '>', '（', '）', '［', '］', '｛', '｝', '＜', '＞', '「',
+    '」', '『', '』', '【', '】', '〔', '〕', '〈', '〉', '《', '》', '〖', '〗', '"', '“', '”',
+    '\'', '‘', '’', '＂', '＇', '※', '★', '☆', '♪', '♯', '♭', '◆', '◇', '■', '□',
+];
+
+/// 伏せ字保護後のテキスト片
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Text(String),
+    Mask(usize),
+}
+
+fn make_mask_token(len: usize) -> String {
+    format!("{}{}{}", MASK_TOKEN_PREFIX, len, MASK_TOKEN_SUFFIX)
+}
+
+/// 伏せ字（連続アンダースコア・連続x/X・単一マスク文字）を分離する
+fn protect_masks(text: &str) -> Vec<Segment> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        // 連続する "_" または "x/X" (2文字以上) は伏せ字
+        let is_run_char = |ch: char| {
+            if c == '_' {
+                ch == '_'
+            } else {
+                ch == 'x' || ch == 'X'
+            }
+        };
+        if c == '_' || c == 'x' || c == 'X' {
+            let mut end = i;
+            while end < chars.len() && is_run_char(chars[end]) {
+                end += 1;
+            }
+            if end - i >= 2 {
+                if !current.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut current)));
+                }
+                segments.push(Segment::Mask(end - i));
+                i = end;
+                continue;
+            }
+        }
+
+        if MASK_SINGLE_CHARS.contains(&c) {
+            if !current.is_empty() {
+                segments.push(Segment::Text(std::mem::take(&mut current)));
+            }
+            segments.push(Segment::Mask(1));
+        } else {
+            current.push(c);
+        }
+        i += 1;
+    }
+
+    if !current.is_empty() {
+        segments.push(Segment::Text(current));
+    }
+    segments
+}
+
+/// ひらがな → カタカナ変換
+fn hiragana_to_katakana(c: char) -> char {
+    if ('\u{3041}'..='\u{3096}').contains(&c) {
+        char::from_u32(c as u32 + 0x60).unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+fn stage_nfkc(text: &str) -> String {
+    text.nfkc().collect()
+}
+
+fn stage_lowercase(text: &str) -> String {
+    text.to_lowercase()
+}
+
+fn stage_katakana(text: &str) -> String {
+    text.chars().map(hiragana_to_katakana).collect()
+}
+
+fn stage_separators(text: &str) -> String {
+    text.chars()
+        .map(|c| if SEPARATOR_CHARS.contains(&c) { ' ' } else { c })
+        .collect()
+}
+
+fn stage_decorations(text: &str) -> String {
+    text.chars()
+        .filter(|c| !DECORATION_CHARS.contains(c))
+        .collect()
+}
+
+fn stage_whitespace(text: &str) -> String {
+    text.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+type TextStage = (&'static str, fn(&str) -> String);
+
+/// テキスト片に適用する正規化の段階（適用順）
+const TEXT_STAGES: &[TextStage] = &[
+    ("nfkc", stage_nfkc),
+    ("lowercase", stage_lowercase),
+    ("katakana", stage_katakana),
+    ("separators", stage_separators),
+    ("decorations", stage_decorations),
+    ("whitespace", stage_whitespace),
+];
+
+/// 伏せ字を含まないテキスト片を正規化する
+fn normalize_text(text: &str) -> String {
+    TEXT_STAGES
+        .iter()
+        .fold(text.to_string(), |acc, (_, stage)| stage(&acc))
+}
+
+fn render_segments(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text(t) => t.clone(),
+            Segment::Mask(len) => make_mask_token(*len),
+        })
+        .collect()
+}
+
+/// 正規化の各段階の途中結果を返す（explain_match 用）
+/// 先頭は伏せ字の保護、最後の段階の出力は normalize() と一致する
+pub fn normalization_stages(text: &str) -> Vec<(&'static str, String)> {
+    let mut segments = protect_masks(text);
+    let mut stages = vec![("masks", render_segments(&segments))];
+
+    for (name, stage) in TEXT_STAGES {
+        for segment in segments.iter_mut() {
+            if let Segment::Text(t) = segment {
+                *t = stage(t);
+            }
+        }
+        stages.push((*name, render_segments(&segments)));
+    }
+    stages
+}
+
+/// 文字列を正規化し、伏せ字とテキスト片に分けて返す
+pub fn normalize_segments(text: &str) -> Vec<Segment> {
+    let mut result: Vec<Segment> = Vec::new();
+    for segment in protect_masks(text) {
+        match segment {
+            Segment::Text(t) => {
+                let normalized = normalize_text(&t);
+                if normalized.is_empty() {
+                    continue;
+                }
+                // 隣接するテキスト片は結合
+                if let Some(Segment::Text(prev)) = result.last_mut() {
+                    prev.push_str(&normalized);
+                } else {
+                    result.push(Segment::Text(normalized));
+                }
+            }
+            mask => result.push(mask),
+        }
+    }
+    result
+}
+
+/// 文字列を正規化する
+/// 処理順序:
+/// 1. 伏せ字の保護（マスクトークン化）
+/// 2. NFKC正規化
+/// 3. 小文字化
+/// 4. ひらがな→カタカナ
+/// 5. 区切り文字→スペース
+/// 6. 装飾記号削除
+/// 7. 空白全削除
+///
+/// 伏せ字は `<MASK:n>` トークンとして保持する
+pub fn normalize(text: &str) -> String {
+    render_segments(&normalize_segments(text))
+}
+
+/// 正規化済み文字列を伏せ字とテキスト片に分解する
+pub fn split_mask_tokens(normalized: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut rest = normalized;
+
+    while let Some(start) = rest.find(MASK_TOKEN_PREFIX) {
+        let after = &rest[start + MASK_TOKEN_PREFIX.len()..];
+        let Some(end) = after.find(MASK_TOKEN_SUFFIX) else {
+            break;
+        };
+        let Ok(len) = after[..end].parse::<usize>() else {
+            break;
+        };
+        if start > 0 {
+            segments.push(Segment::Text(rest[..start].to_string()));
+        }
+        segments.push(Segment::Mask(len));
+        rest = &after[end + MASK_TOKEN_SUFFIX.len()..];
+    }
+
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest.to_string()));
+    }
+    segments
+}
+
+/// 正規化済み文字列に伏せ字が含まれるか
+pub fn has_mask(normalized: &str) -> bool {
+    split_mask_tokens(normalized)
+        .iter()
+        .any(|s| matches!(s, Segment::Mask(_)))
+}
+
+/// 正規化済み文字列から伏せ字トークンを除去する
+pub fn strip_mask_tokens(normalized: &str) -> String {
+    split_mask_tokens(normalized)
+        .into_iter()
+        .filter_map(|s| match s {
+            Segment::Text(t) => Some(t),
+            Segment::Mask(_) => None,
+        })
+        .collect()
+}
+
+/// ターゲット文字列を生成する
+/// targetText = parentFolderName + " " + fileBaseName
+pub fn create_target_text(file_path: &str) -> String {
+    let unified = file_path.replace('\\', "/");
+    let mut parts: Vec<&str> = unified.split('/').collect();
+    let file_name = parts.pop().unwrap_or("");
+    let parent_folder = parts.pop().unwrap_or("");
+
+    // 拡張子を除去
+    let file_base_name = match file_name.rfind('.') {
+        Some(pos) if pos > 0 => &file_name[..pos],
+        _ => file_name,
+    };
+
+    if parent_folder.is_empty() {
+        file_base_name.to_string()
+    } else {
+        format!("{} {}", parent_folder, file_base_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_basic() {
+        assert_eq!(normalize("Aria Starlight"), "ariastarlight");
+        assert_eq!(normalize("アリア・スターライト"), "アリアスターライト");
+        assert_eq!(normalize("ありあ"), "アリア");
+        assert_eq!(normalize("【ＺＺＺ】"), "zzz");
+    }
+
+    #[test]
+    fn test_normalize_separators() {
+        assert_eq!(normalize("leon_blaze-01"), "leonblaze01");
+        assert_eq!(normalize("a.b/c|d"), "abcd");
+    }
+
+    #[test]
+    fn test_normalize_keeps_masks() {
+        assert_eq!(normalize("ア〇ア"), "ア<MASK:1>ア");
+        assert_eq!(normalize("aria__light"), "aria<MASK:2>light");
+        assert_eq!(normalize("zeXXro"), "ze<MASK:2>ro");
+        assert!(has_mask(&normalize("ステラ*ナイツ")));
+        assert!(!has_mask(&normalize("ステラナイツ")));
+    }
+
+    #[test]
+    fn test_strip_mask_tokens() {
+        assert_eq!(strip_mask_tokens("ア<MASK:1>ア"), "アア");
+        assert_eq!(
+            split_mask_tokens("ab<MASK:2>"),
+            vec![Segment::Text("ab".to_string()), Segment::Mask(2)]
+        );
+    }
+
+    #[test]
+    fn test_create_target_text() {
+        assert_eq!(
+            create_target_text("/data/ゼンレスゾーンゼロ/001_戦闘.mp4"),
+            "ゼンレスゾーンゼロ 001_戦闘"
+        );
+        assert_eq!(
+            create_target_text("C:\\pics\\folder\\image.jpg"),
+            "folder image"
+        );
+        assert_eq!(create_target_text("image.jpg"), "image");
+    }
+
+    #[test]
+    fn test_normalization_stages() {
+        let text = "【ＡＲＩＡ】ありあ__01";
+        let stages = normalization_stages(text);
+
+        let names: Vec<&str> = stages.iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "masks",
+                "nfkc",
+                "lowercase",
+                "katakana",
+                "separators",
+                "decorations",
+                "whitespace"
+            ]
+        );
+        assert_eq!(stages[0].1, "【ＡＲＩＡ】ありあ<MASK:2>01");
+        assert_eq!(stages[1].1, "【ARIA】ありあ<MASK:2>01");
+        assert_eq!(stages.last().unwrap().1, normalize(text));
+    }
+}
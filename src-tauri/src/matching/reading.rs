@@ -0,0 +1,163 @@
+use unicode_normalization::UnicodeNormalization;
+
+// =========================
+// カナ → ヘボン式ローマ字
+// =========================
+
+/// 単独のカナ（正規化済みのカタカナ）に対応するローマ字
+#[rustfmt::skip]
+fn kana_romaji(c: char) -> Option<&'static str> {
+    let romaji = match c {
+        'ア' => "a", 'イ' => "i", 'ウ' => "u", 'エ' => "e", 'オ' => "o",
+        'カ' => "ka", 'キ' => "ki", 'ク' => "ku", 'ケ' => "ke", 'コ' => "ko",
+        'ガ' => "ga", 'ギ' => "gi", 'グ' => "gu", 'ゲ' => "ge", 'ゴ' => "go",
+        'サ' => "sa", 'シ' => "shi", 'ス' => "su", 'セ' => "se", 'ソ' => "so",
+        'ザ' => "za", 'ジ' => "ji", 'ズ' => "zu", 'ゼ' => "ze", 'ゾ' => "zo",
+        'タ' => "ta", 'チ' => "chi", 'ツ' => "tsu", 'テ' => "te", 'ト' => "to",
+        'ダ' => "da", 'ヂ' => "ji", 'ヅ' => "zu", 'デ' => "de", 'ド' => "do",
+        'ナ' => "na", 'ニ' => "ni", 'ヌ' => "nu", 'ネ' => "ne", 'ノ' => "no",
+        'ハ' => "ha", 'ヒ' => "hi", 'フ' => "fu", 'ヘ' => "he", 'ホ' => "ho",
+        'バ' => "ba", 'ビ' => "bi", 'ブ' => "bu", 'ベ' => "be", 'ボ' => "bo",
+        'パ' => "pa", 'ピ' => "pi", 'プ' => "pu", 'ペ' => "pe", 'ポ' => "po",
+        'マ' => "ma", 'ミ' => "mi", 'ム' => "mu", 'メ' => "me", 'モ' => "mo",
+        'ヤ' => "ya", 'ユ' => "yu", 'ヨ' => "yo",
+        'ラ' => "ra", 'リ' => "ri", 'ル' => "ru", 'レ' => "re", 'ロ' => "ro",
+        'ワ' => "wa", 'ヰ' => "i", 'ヱ' => "e", 'ヲ' => "o", 'ン' => "n", 'ヴ' => "vu",
+        'ァ' => "a", 'ィ' => "i", 'ゥ' => "u", 'ェ' => "e", 'ォ' => "o",
+        'ャ' => "ya", 'ュ' => "yu", 'ョ' => "yo", 'ヮ' => "wa",
+        _ => return None,
+    };
+    Some(romaji)
+}
+
+/// 拗音・外来音（2文字の組み合わせ）
+#[rustfmt::skip]
+fn digraph_romaji(first: char, second: char) -> Option<String> {
+    // イ段 + ャュョェ（キャ → kya、シャ → sha、ジェ → je）
+    if matches!(first, 'キ' | 'ギ' | 'シ' | 'ジ' | 'チ' | 'ヂ' | 'ニ' | 'ヒ' | 'ビ' | 'ピ' | 'ミ' | 'リ') {
+        let vowel = match second {
+            'ャ' => "a",
+            'ュ' => "u",
+            'ョ' => "o",
+            'ェ' => "e",
+            _ => return None,
+        };
+        let base = kana_romaji(first)?.trim_end_matches('i');
+        return Some(if base.ends_with("sh") || base.ends_with("ch") || base == "j" {
+            format!("{}{}", base, vowel)
+        } else {
+            format!("{}y{}", base, vowel)
+        });
+    }
+
+    let romaji = match (first, second) {
+        ('テ', 'ィ') => "ti", ('デ', 'ィ') => "di", ('ト', 'ゥ') => "tu", ('ド', 'ゥ') => "du",
+        ('テ', 'ュ') => "tyu", ('デ', 'ュ') => "dyu",
+        ('フ', 'ァ') => "fa", ('フ', 'ィ') => "fi", ('フ', 'ェ') => "fe", ('フ', 'ォ') => "fo",
+        ('フ', 'ュ') => "fyu",
+        ('ヴ', 'ァ') => "va", ('ヴ', 'ィ') => "vi", ('ヴ', 'ェ') => "ve", ('ヴ', 'ォ') => "vo",
+        ('ウ', 'ィ') => "wi", ('ウ', 'ェ') => "we", ('ウ', 'ォ') => "wo",
+        ('ツ', 'ァ') => "tsa", ('ツ', 'ィ') => "tsi", ('ツ', 'ェ') => "tse", ('ツ', 'ォ') => "tso",
+        ('イ', 'ェ') => "ye", ('ク', 'ァ') => "kwa", ('グ', 'ァ') => "gwa",
+        _ => return None,
+    };
+    Some(romaji.to_string())
+}
+
+/// 正規化済みのカナ文字列をヘボン式ローマ字に変換する
+/// カナ以外（漢字・英字等）を含む場合は None
+///
+/// - 促音「ッ」は次の子音を重ねる（「ッチ」→ tchi）
+/// - 長音「ー」は省略する
+pub fn kana_to_romaji(normalized: &str) -> Option<String> {
+    let chars: Vec<char> = normalized.chars().collect();
+    let mut result = String::new();
+    let mut geminate = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            'ッ' => {
+                geminate = true;
+                i += 1;
+                continue;
+            }
+            'ー' => {
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let (syllable, consumed) = match chars.get(i + 1).and_then(|&next| digraph_romaji(c, next))
+        {
+            Some(s) => (s, 2),
+            None => (kana_romaji(c)?.to_string(), 1),
+        };
+
+        if geminate {
+            if syllable.starts_with("ch") {
+                result.push('t');
+            } else if let Some(first) = syllable.chars().next().filter(|f| !"aiueon".contains(*f)) {
+                result.push(first);
+            }
+            geminate = false;
+        }
+
+        result.push_str(&syllable);
+        i += consumed;
+    }
+
+    (!result.is_empty()).then_some(result)
+}
+
+// =========================
+// ピンイン声調記号の除去
+// =========================
+
+/// ラテン文字のダイアクリティカルマークを除去する（"Yuè Yǐng" → "Yue Ying"）
+/// カナの濁点等を壊さないよう、分解結果の先頭が ASCII 英字の文字のみ対象にする
+pub fn strip_diacritics(text: &str) -> String {
+    text.chars()
+        .map(|c| match std::iter::once(c).nfd().next() {
+            Some(base) if !c.is_ascii() && base.is_ascii_alphabetic() => base,
+            _ => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::normalize::normalize;
+
+    #[test]
+    fn test_kana_to_romaji() {
+        assert_eq!(
+            kana_to_romaji(&normalize("つきかげ")).as_deref(),
+            Some("tsukikage")
+        );
+        assert_eq!(kana_to_romaji("アリア").as_deref(), Some("aria"));
+        assert_eq!(kana_to_romaji("シャーロット").as_deref(), Some("sharotto"));
+        assert_eq!(kana_to_romaji("マッチ").as_deref(), Some("matchi"));
+        assert_eq!(kana_to_romaji("ジェシカ").as_deref(), Some("jeshika"));
+        assert_eq!(kana_to_romaji("ティファニー").as_deref(), Some("tifani"));
+        assert_eq!(kana_to_romaji("キョウ").as_deref(), Some("kyou"));
+    }
+
+    #[test]
+    fn test_kana_to_romaji_rejects_non_kana() {
+        assert_eq!(kana_to_romaji("月影"), None);
+        assert_eq!(kana_to_romaji("aria"), None);
+        assert_eq!(kana_to_romaji(""), None);
+    }
+
+    #[test]
+    fn test_strip_diacritics() {
+        assert_eq!(strip_diacritics("Yuè Yǐng"), "Yue Ying");
+        assert_eq!(strip_diacritics("Lǜ"), "Lu");
+        // カナの濁点は保持
+        assert_eq!(strip_diacritics("ガ"), "ガ");
+    }
+}
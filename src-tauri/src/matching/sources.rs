@@ -0,0 +1,127 @@
+use super::matcher::MatchTarget;
+use super::normalize::create_target_text;
+use crate::config::options::MatchSource;
+use crate::metadata::{ai, exif, iptc, jpeg, open_container, png, xmp, ContainerFormat};
+use std::path::Path;
+use tracing::debug;
+
+/// IPTC キーワードを読み込む（JPEG APP13）
+fn read_iptc_keywords(path: &Path) -> Vec<String> {
+    let Ok((ContainerFormat::Jpeg, mut reader)) = open_container(path) else {
+        return Vec::new();
+    };
+    let Ok(segments) = jpeg::read_segments(&mut reader) else {
+        return Vec::new();
+    };
+    jpeg::find_app_payload(&segments, 0xED, iptc::PHOTOSHOP_IDENTIFIER)
+        .map(iptc::parse_keywords)
+        .unwrap_or_default()
+}
+
+/// PNG のテキストチャンクを読み込む（XMP パケットは除く）
+fn read_png_texts(path: &Path) -> Vec<String> {
+    let Ok((ContainerFormat::Png, mut reader)) = open_container(path) else {
+        return Vec::new();
+    };
+    png::read_text_chunks(&mut reader)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|t| t.keyword != png::XMP_KEYWORD && !t.text.trim().is_empty())
+        .map(|t| t.text)
+        .collect()
+}
+
+/// 指定した取得元からマッチング対象テキストを集める（設定順）
+pub fn collect_targets(file_path: &str, sources: &[MatchSource]) -> Vec<MatchTarget> {
+    let path = Path::new(file_path);
+    let mut targets = Vec::new();
+
+    for &source in sources {
+        let texts = match source {
+            MatchSource::FileName => vec![create_target_text(file_path)],
+            MatchSource::ExifDescription => exif::image_description(path).into_iter().collect(),
+            MatchSource::XmpSubject => xmp::read_subjects(path),
+            MatchSource::IptcKeywords => read_iptc_keywords(path),
+            MatchSource::PngText => read_png_texts(path),
+            MatchSource::AiPrompt => ai::read_ai_metadata(path)
+                .and_then(|meta| meta.prompt)
+                .map(|prompt| ai::prompt_tags(&prompt))
+                .unwrap_or_default(),
+        };
+        targets.extend(texts.into_iter().map(|text| MatchTarget { source, text }));
+    }
+
+    debug!("マッチング対象: {} - {}件", file_path, targets.len());
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::iptc::tests::build_payload;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_collect_targets_from_jpeg() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("IMG_0001.jpg");
+
+        let app1 = crate::metadata::exif::tests::build_exif_app1(&[(0x010E, "Aria Starlight")]);
+        let mut app13 = iptc::PHOTOSHOP_IDENTIFIER.to_vec();
+        app13.extend(build_payload(&["Leon Blaze"]));
+        let bytes = jpeg::tests::build_jpeg(&[(0xE1, &app1), (0xED, &app13)]);
+        fs::write(&path, bytes).unwrap();
+
+        let file_path = path.to_string_lossy().to_string();
+        let targets = collect_targets(
+            &file_path,
+            &[
+                MatchSource::FileName,
+                MatchSource::ExifDescription,
+                MatchSource::IptcKeywords,
+                MatchSource::PngText,
+            ],
+        );
+
+        assert_eq!(targets.len(), 3);
+        assert_eq!(targets[0].source, MatchSource::FileName);
+        assert!(targets[0].text.ends_with("IMG_0001"));
+        assert_eq!(targets[1].text, "Aria Starlight");
+        assert_eq!(targets[2].source, MatchSource::IptcKeywords);
+        assert_eq!(targets[2].text, "Leon Blaze");
+    }
+
+    #[test]
+    fn test_collect_targets_from_png_text() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sticker.png");
+        let bytes = png::tests::build_png(&[
+            (b"tEXt", b"Title\0Tsukikage".to_vec()),
+            (b"iTXt", png::tests::itxt(png::XMP_KEYWORD, "<x:xmpmeta/>")),
+        ]);
+        fs::write(&path, bytes).unwrap();
+
+        let targets = collect_targets(&path.to_string_lossy(), &[MatchSource::PngText]);
+
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].text, "Tsukikage");
+    }
+
+    #[test]
+    fn test_collect_targets_from_ai_prompt() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("00001-1234.png");
+        let bytes = png::tests::build_png(&[(
+            b"tEXt",
+            b"parameters\0masterpiece, aria starlight\nSteps: 20, Seed: 1".to_vec(),
+        )]);
+        fs::write(&path, bytes).unwrap();
+
+        let targets = collect_targets(&path.to_string_lossy(), &[MatchSource::AiPrompt]);
+
+        let texts: Vec<&str> = targets.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["masterpiece", "aria starlight"]);
+        assert!(targets.iter().all(|t| t.source == MatchSource::AiPrompt));
+    }
+}
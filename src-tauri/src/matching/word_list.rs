@@ -0,0 +1,466 @@
+use super::normalize::normalize;
+use super::reading::{kana_to_romaji, strip_diacritics};
+use crate::config::settings::{WordList, WordListEntry};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// 複数単語の別名から検索キーに追加する単語の最小長
+const MIN_WORD_LENGTH: usize = 4;
+
+/// 単語の区切り文字
+const WORD_SEPARATORS: &[char] = &[' ', '\u{3000}', '・', '•', '-', '－', '‐'];
+
+/// 単語リストに追加する別名（別名候補の提案から確定したもの）
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasAddition {
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    pub canonical: String,
+    pub alias: String,
+}
+
+/// CSV行をパース（カンマ区切り、クォート対応）
+fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                // エスケープされたクォート
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' | '，' if !in_quotes => {
+                result.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+
+    result.push(current.trim().to_string());
+    result
+}
+
+/// CSV行を書き出す（区切り文字・クォートを含む列はクォートする）
+fn format_csv_line(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '，', '"']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// ヘッダー行かどうか判定
+fn is_header_line(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    ["日本語", "英語", "中国語", "aliases", "name"]
+        .iter()
+        .any(|word| lower.contains(word))
+}
+
+/// パイプ区切りの列を分割する
+fn split_pipe(column: Option<&String>) -> Vec<String> {
+    column
+        .map(|c| {
+            c.split('|')
+                .map(|a| a.trim().to_string())
+                .filter(|a| !a.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn push_unique(keys: &mut Vec<String>, key: String) {
+    if !key.is_empty() && !keys.contains(&key) {
+        keys.push(key);
+    }
+}
+
+/// 表記・読みから派生キー（ローマ字・声調記号なしピンイン）を生成する
+fn derive_keys(texts: &[String], readings: &[String]) -> Vec<String> {
+    let mut keys = Vec::new();
+
+    for text in texts {
+        let normalized = normalize(text);
+
+        // カナ → ローマ字（複数単語なら各単語も）
+        if let Some(romaji) = kana_to_romaji(&normalized) {
+            push_unique(&mut keys, romaji);
+        }
+        let words: Vec<&str> = text.split(WORD_SEPARATORS).collect();
+        if words.len() > 1 {
+            for word in words {
+                if let Some(romaji) = kana_to_romaji(&normalize(word)) {
+                    if romaji.chars().count() >= MIN_WORD_LENGTH {
+                        push_unique(&mut keys, romaji);
+                    }
+                }
+            }
+        }
+
+        // ピンインの声調記号を除去
+        let stripped = normalize(&strip_diacritics(text));
+        if stripped != normalized {
+            push_unique(&mut keys, stripped);
+        }
+    }
+
+    // 読み列（カナ表記とローマ字の両方）
+    for reading in readings {
+        let normalized = normalize(reading);
+        if let Some(romaji) = kana_to_romaji(&normalized) {
+            push_unique(&mut keys, romaji);
+        }
+        push_unique(&mut keys, normalized);
+    }
+
+    keys
+}
+
+/// WordListEntry を作成する
+pub fn create_entry(
+    entry_type: &str,
+    canonical: &str,
+    aliases: Vec<String>,
+    readings: &[String],
+) -> WordListEntry {
+    let canonical_normalized = normalize(canonical);
+    let aliases_normalized: Vec<String> = aliases.iter().map(|a| normalize(a)).collect();
+
+    // 検索キー（重複除去）
+    let mut search_keys_normalized = Vec::new();
+    push_unique(&mut search_keys_normalized, canonical_normalized.clone());
+    for key in &aliases_normalized {
+        push_unique(&mut search_keys_normalized, key.clone());
+    }
+
+    // 複数単語の別名から各単語も検索キーに追加
+    // 例: "Grace Howard" → ["gracehoward", "grace", "howard"]
+    for alias in &aliases {
+        let words: Vec<&str> = alias.split(WORD_SEPARATORS).collect();
+        if words.len() > 1 {
+            for word in words {
+                let normalized_word = normalize(word);
+                if normalized_word.chars().count() >= MIN_WORD_LENGTH {
+                    push_unique(&mut search_keys_normalized, normalized_word);
+                }
+            }
+        }
+    }
+
+    // 派生キーは既存の検索キーと重複しないものだけ追加
+    let mut texts = vec![canonical.to_string()];
+    texts.extend(aliases.iter().cloned());
+    let derived_keys_normalized: Vec<String> = derive_keys(&texts, readings)
+        .into_iter()
+        .filter(|k| !search_keys_normalized.contains(k))
+        .collect();
+    search_keys_normalized.extend(derived_keys_normalized.iter().cloned());
+
+    WordListEntry {
+        entry_type: entry_type.to_string(),
+        canonical: canonical.to_string(),
+        aliases,
+        canonical_normalized,
+        aliases_normalized,
+        search_keys_normalized,
+        derived_keys_normalized,
+        work_id: None,
+    }
+}
+
+/// 行の列に別名を追加する（既にある表記は追加しない）
+/// 作品は行末の列に、キャラクターは aliases 列にパイプ区切りで追加する
+fn append_alias(parts: &mut Vec<String>, is_work: bool, alias: &str) -> bool {
+    let alias = alias.trim();
+    if alias.is_empty() || alias.contains('|') {
+        return false;
+    }
+
+    if is_work {
+        if parts[1..].iter().any(|p| p == alias) {
+            return false;
+        }
+        parts.push(alias.to_string());
+    } else {
+        let mut existing: Vec<String> = parts.iter().take(3).cloned().collect();
+        existing.extend(split_pipe(parts.get(3)));
+        if existing.iter().any(|a| a == alias) {
+            return false;
+        }
+        if parts.len() < 4 {
+            parts.resize(4, String::new());
+        }
+        parts[3] = if parts[3].is_empty() {
+            alias.to_string()
+        } else {
+            format!("{}|{}", parts[3], alias)
+        };
+    }
+    true
+}
+
+/// 単語リストCSVのテキストに別名を追加する（追加後のテキストと追加した件数を返す）
+/// 別名を追加した行以外は元のまま残す
+pub fn append_aliases_to_csv(csv_text: &str, additions: &[AliasAddition]) -> (String, usize) {
+    let newline = if csv_text.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut header_found = false;
+    let mut added = 0;
+    let mut lines = Vec::new();
+
+    for raw in csv_text.lines() {
+        let line = raw.trim();
+        let is_work = line.starts_with("@work,") || line.starts_with("@work，");
+        if line.is_empty() || (!is_work && !header_found && is_header_line(line)) {
+            header_found |= !line.is_empty();
+            lines.push(raw.to_string());
+            continue;
+        }
+
+        let mut parts = parse_csv_line(line);
+        let (entry_type, canonical) = if is_work {
+            ("work", parts.get(1).cloned().unwrap_or_default())
+        } else {
+            ("character", parts[0].clone())
+        };
+        let mut changed = false;
+        for addition in additions
+            .iter()
+            .filter(|a| a.entry_type == entry_type && a.canonical == canonical)
+        {
+            if append_alias(&mut parts, is_work, &addition.alias) {
+                changed = true;
+                added += 1;
+            }
+        }
+        lines.push(if changed {
+            format_csv_line(&parts)
+        } else {
+            raw.to_string()
+        });
+    }
+
+    let mut text = lines.join(newline);
+    if csv_text.ends_with('\n') {
+        text.push_str(newline);
+    }
+    (text, added)
+}
+
+/// CSVテキストをパースして WordList を生成する
+///
+/// フォーマット:
+/// ```text
+/// @work,作品名,英語名,別名1,別名2
+/// 日本語名,英語名,中国語名,aliases,読み
+/// キャラ1,Char1,角色1,alias1|alias2,きゃらいち
+/// ```
+///
+/// 読み列（パイプ区切り）は省略可能
+pub fn parse_word_list_csv(csv_text: &str, file_name: &str) -> WordList {
+    let mut entries = Vec::new();
+    let mut header_found = false;
+    // 直前の @work 行（以降のキャラクターの所属作品）
+    let mut current_work: Option<String> = None;
+
+    for line in csv_text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        // @work で始まる行は作品名
+        if line.starts_with("@work,") || line.starts_with("@work，") {
+            let parts = parse_csv_line(line);
+            if parts.len() >= 2 {
+                let aliases: Vec<String> = parts[2..]
+                    .iter()
+                    .filter(|a| !a.is_empty())
+                    .cloned()
+                    .collect();
+                let entry = create_entry("work", &parts[1], aliases, &[]);
+                current_work = Some(format!("{}:{}", entry.entry_type, entry.canonical));
+                entries.push(entry);
+            }
+            continue;
+        }
+
+        // ヘッダー行をスキップ
+        if !header_found && is_header_line(line) {
+            header_found = true;
+            continue;
+        }
+
+        // キャラクター行
+        let parts = parse_csv_line(line);
+        if parts[0].is_empty() {
+            continue;
+        }
+        let mut aliases = Vec::new();
+        // 英語名・中国語名
+        for column in parts.iter().skip(1).take(2) {
+            if !column.is_empty() {
+                aliases.push(column.clone());
+            }
+        }
+        // aliases列（パイプ区切り）
+        aliases.extend(split_pipe(parts.get(3)));
+        let readings = split_pipe(parts.get(4));
+
+        let mut entry = create_entry("character", &parts[0], aliases, &readings);
+        entry.work_id = current_work.clone();
+        entries.push(entry);
+    }
+
+    WordList {
+        file_name: file_name.to_string(),
+        source_path: None,
+        entries,
+    }
+}
+
+/// CSVファイルを読み込み、読み込み元パスを記録した WordList を返す
+pub fn load_word_list_file(path: &str) -> Result<WordList, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let file_name = Path::new(&path.replace('\\', "/"))
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string());
+
+    let mut word_list = parse_word_list_csv(&content, &file_name);
+    word_list.source_path = Some(path.to_string());
+    Ok(word_list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_CSV: &str = "\
+@work,ステラナイツ,Stellar Knights,SK
+日本語名,英語名,中国語名,aliases,読み
+アリア・スターライト,Aria Starlight,艾莉亚,アリア,
+月影,Moon Shadow,Yuè Yǐng,\"tsuki|\"\"moon\"\"\",つきかげ
+";
+
+    #[test]
+    fn test_parse_word_list_csv() {
+        let list = parse_word_list_csv(SAMPLE_CSV, "sample.csv");
+
+        assert_eq!(list.file_name, "sample.csv");
+        assert_eq!(list.entries.len(), 3);
+        assert_eq!(list.entries[0].entry_type, "work");
+        assert_eq!(list.entries[0].aliases, vec!["Stellar Knights", "SK"]);
+        assert_eq!(
+            list.entries[1].work_id.as_deref(),
+            Some("work:ステラナイツ")
+        );
+        assert_eq!(
+            list.entries[2].aliases,
+            vec!["Moon Shadow", "Yuè Yǐng", "tsuki", "\"moon\""]
+        );
+    }
+
+    #[test]
+    fn test_derived_romaji_keys() {
+        let list = parse_word_list_csv(SAMPLE_CSV, "sample.csv");
+        let aria = &list.entries[1];
+
+        assert!(aria
+            .derived_keys_normalized
+            .contains(&"ariasutaraito".to_string()));
+        assert!(aria
+            .derived_keys_normalized
+            .contains(&"sutaraito".to_string()));
+        assert!(aria
+            .search_keys_normalized
+            .contains(&"ariasutaraito".to_string()));
+        // 英語名由来の "aria" は派生キーではない
+        assert!(!aria.derived_keys_normalized.contains(&"aria".to_string()));
+    }
+
+    #[test]
+    fn test_derived_reading_and_pinyin_keys() {
+        let list = parse_word_list_csv(SAMPLE_CSV, "sample.csv");
+        let tsukikage = &list.entries[2];
+
+        assert!(tsukikage
+            .derived_keys_normalized
+            .contains(&"yueying".to_string()));
+        assert!(tsukikage
+            .derived_keys_normalized
+            .contains(&"ツキカゲ".to_string()));
+        assert!(tsukikage
+            .derived_keys_normalized
+            .contains(&"tsukikage".to_string()));
+    }
+
+    fn addition(entry_type: &str, canonical: &str, alias: &str) -> AliasAddition {
+        AliasAddition {
+            entry_type: entry_type.to_string(),
+            canonical: canonical.to_string(),
+            alias: alias.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_append_aliases_to_csv() {
+        // Act
+        let (text, added) = append_aliases_to_csv(
+            SAMPLE_CSV,
+            &[
+                addition("work", "ステラナイツ", "Stellar, Knights"),
+                addition("character", "アリア・スターライト", "aria star"),
+                addition("character", "月影", "tsukikage"),
+                // 既にある表記・存在しない項目は追加しない
+                addition("character", "月影", "tsuki"),
+                addition("character", "レオン", "leon"),
+            ],
+        );
+
+        // Assert: 追加した行だけが書き換わり、読み込み直すと別名になっている
+        assert_eq!(added, 3);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"@work,ステラナイツ,Stellar Knights,SK,"Stellar, Knights""#
+        );
+        assert_eq!(lines[1], "日本語名,英語名,中国語名,aliases,読み");
+        assert_eq!(
+            lines[2],
+            "アリア・スターライト,Aria Starlight,艾莉亚,アリア|aria star,"
+        );
+        assert_eq!(
+            lines[3],
+            r#"月影,Moon Shadow,Yuè Yǐng,"tsuki|""moon""|tsukikage",つきかげ"#
+        );
+        assert!(text.ends_with('\n'));
+
+        let list = parse_word_list_csv(&text, "sample.csv");
+        assert_eq!(
+            list.entries[0].aliases,
+            vec!["Stellar Knights", "SK", "Stellar, Knights"]
+        );
+        assert!(list.entries[1].aliases.contains(&"aria star".to_string()));
+        assert_eq!(
+            list.entries[2].aliases,
+            vec!["Moon Shadow", "Yuè Yǐng", "tsuki", "\"moon\"", "tsukikage"]
+        );
+        // 読み列は残る
+        assert!(list.entries[2]
+            .derived_keys_normalized
+            .contains(&"ツキカゲ".to_string()));
+    }
+}
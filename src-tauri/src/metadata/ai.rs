@@ -0,0 +1,485 @@
+use super::{exif, open_container, png, ContainerFormat};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// 生成ツールの種類
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AiGenerator {
+    /// AUTOMATIC1111 系（PNG `parameters` / EXIF UserComment）
+    A1111,
+    /// ComfyUI（API 形式のプロンプトグラフ）
+    ComfyUi,
+}
+
+/// 画像生成メタデータ
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AiMetadata {
+    pub generator: AiGenerator,
+    pub prompt: Option<String>,
+    pub negative_prompt: Option<String>,
+    pub model: Option<String>,
+    pub seed: Option<u64>,
+    pub sampler: Option<String>,
+    pub steps: Option<u32>,
+    pub cfg_scale: Option<f64>,
+    pub loras: Vec<String>,
+}
+
+impl AiMetadata {
+    fn new(generator: AiGenerator) -> Self {
+        Self {
+            generator,
+            prompt: None,
+            negative_prompt: None,
+            model: None,
+            seed: None,
+            sampler: None,
+            steps: None,
+            cfg_scale: None,
+            loras: Vec::new(),
+        }
+    }
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn push_unique(list: &mut Vec<String>, value: String) {
+    if !value.is_empty() && !list.contains(&value) {
+        list.push(value);
+    }
+}
+
+// =========================
+// A1111
+// =========================
+
+/// "Steps: 20, Sampler: Euler a, Lora hashes: \"a: 1, b: 2\"" 形式のパラメータ行を解析
+fn parse_parameter_line(line: &str) -> BTreeMap<String, String> {
+    let mut params = BTreeMap::new();
+    let mut rest = line.trim();
+
+    while !rest.is_empty() {
+        let Some(colon) = rest.find(':') else {
+            break;
+        };
+        let key = rest[..colon].trim().to_string();
+        let after = rest[colon + 1..].trim_start();
+
+        let (value, remaining) = if let Some(quoted) = after.strip_prefix('"') {
+            match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            }
+        } else {
+            match after.find(", ") {
+                Some(end) => (&after[..end], &after[end..]),
+                None => (after, ""),
+            }
+        };
+
+        params.insert(key, value.trim().to_string());
+        rest = remaining.trim_start_matches(',').trim_start();
+    }
+
+    params
+}
+
+/// プロンプト中の `<lora:name:weight>` から LoRA 名を取り出す
+fn extract_prompt_loras(prompt: &str) -> Vec<String> {
+    let mut loras = Vec::new();
+    let mut rest = prompt;
+    while let Some(start) = rest.find("<lora:") {
+        let after = &rest[start + "<lora:".len()..];
+        let Some(end) = after.find('>') else {
+            break;
+        };
+        let name = after[..end].split(':').next().unwrap_or("");
+        push_unique(&mut loras, name.trim().to_string());
+        rest = &after[end + 1..];
+    }
+    loras
+}
+
+/// A1111 形式の `parameters` テキストを解析する
+pub fn parse_a1111(text: &str) -> Option<AiMetadata> {
+    let text = text.trim();
+
+    // 最後の "Steps: " 行以降がパラメータ
+    let params_start = if text.starts_with("Steps: ") {
+        0
+    } else {
+        text.rfind("\nSteps: ")? + 1
+    };
+    let (prompts, params_line) = text.split_at(params_start);
+    let params = parse_parameter_line(params_line);
+
+    let (prompt, negative) = match prompts.find("Negative prompt:") {
+        Some(pos) => (
+            &prompts[..pos],
+            Some(&prompts[pos + "Negative prompt:".len()..]),
+        ),
+        None => (prompts, None),
+    };
+
+    let mut meta = AiMetadata::new(AiGenerator::A1111);
+    meta.prompt = non_empty(prompt);
+    meta.negative_prompt = negative.and_then(non_empty);
+    meta.model = params.get("Model").cloned();
+    meta.seed = params.get("Seed").and_then(|s| s.parse().ok());
+    meta.sampler = params.get("Sampler").cloned();
+    meta.steps = params.get("Steps").and_then(|s| s.parse().ok());
+    meta.cfg_scale = params.get("CFG scale").and_then(|s| s.parse().ok());
+
+    meta.loras = extract_prompt_loras(prompt);
+    if let Some(hashes) = params.get("Lora hashes") {
+        for pair in hashes.split(',') {
+            let name = pair.split(':').next().unwrap_or("").trim();
+            push_unique(&mut meta.loras, name.to_string());
+        }
+    }
+
+    Some(meta)
+}
+
+// =========================
+// ComfyUI
+// =========================
+
+/// 最大探索深度（リンクの循環対策）
+const MAX_LINK_DEPTH: usize = 8;
+
+/// 入力値がリンク（[ノードID, 出力番号]）ならリンク先ノードを返す
+fn linked_node<'a>(graph: &'a serde_json::Map<String, Value>, input: &Value) -> Option<&'a Value> {
+    let id = match input.as_array()?.first()? {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+    graph.get(&id)
+}
+
+/// リンクを辿ってテキストエンコードノードのプロンプトを取得する
+fn resolve_text(
+    graph: &serde_json::Map<String, Value>,
+    input: &Value,
+    depth: usize,
+) -> Option<String> {
+    if depth > MAX_LINK_DEPTH {
+        return None;
+    }
+    if let Some(text) = input.as_str() {
+        return Some(text.to_string());
+    }
+
+    let node = linked_node(graph, input)?;
+    let inputs = node.get("inputs")?.as_object()?;
+
+    // CLIPTextEncode 系: text / text_g / text_l
+    for key in ["text", "text_g", "text_l", "string", "prompt"] {
+        if let Some(value) = inputs.get(key) {
+            if let Some(text) = resolve_text(graph, value, depth + 1) {
+                return Some(text);
+            }
+        }
+    }
+    // ConditioningCombine などは先頭の条件を辿る
+    for key in [
+        "conditioning",
+        "conditioning_1",
+        "conditioning_to",
+        "positive",
+    ] {
+        if let Some(value) = inputs.get(key) {
+            if let Some(text) = resolve_text(graph, value, depth + 1) {
+                return Some(text);
+            }
+        }
+    }
+    None
+}
+
+/// ComfyUI の API 形式プロンプトグラフを解析する
+pub fn parse_comfyui(json: &str) -> Option<AiMetadata> {
+    let root: Value = serde_json::from_str(json).ok()?;
+    let graph = root.as_object()?;
+
+    let class_type = |node: &Value| -> String {
+        node.get("class_type")
+            .and_then(|c| c.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+
+    // ノードIDの数値順で安定化
+    let mut nodes: Vec<(&String, &Value)> = graph.iter().collect();
+    nodes.sort_by_key(|(id, _)| id.parse::<u64>().unwrap_or(u64::MAX));
+
+    let mut meta = AiMetadata::new(AiGenerator::ComfyUi);
+    let mut found = false;
+
+    for (_, node) in &nodes {
+        let Some(inputs) = node.get("inputs").and_then(|i| i.as_object()) else {
+            continue;
+        };
+        let class = class_type(node);
+
+        if class.contains("Sampler") && meta.sampler.is_none() && meta.seed.is_none() {
+            found = true;
+            meta.seed = inputs
+                .get("seed")
+                .or_else(|| inputs.get("noise_seed"))
+                .and_then(|v| v.as_u64());
+            meta.sampler = inputs
+                .get("sampler_name")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+            meta.steps = inputs
+                .get("steps")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32);
+            meta.cfg_scale = inputs.get("cfg").and_then(|v| v.as_f64());
+            meta.prompt = inputs
+                .get("positive")
+                .and_then(|v| resolve_text(graph, v, 0))
+                .and_then(|t| non_empty(&t));
+            meta.negative_prompt = inputs
+                .get("negative")
+                .and_then(|v| resolve_text(graph, v, 0))
+                .and_then(|t| non_empty(&t));
+        }
+
+        if meta.model.is_none() {
+            if let Some(name) = inputs
+                .get("ckpt_name")
+                .or_else(|| inputs.get("unet_name"))
+                .and_then(|v| v.as_str())
+            {
+                found = true;
+                meta.model = Some(name.to_string());
+            }
+        }
+
+        if let Some(name) = inputs.get("lora_name").and_then(|v| v.as_str()) {
+            found = true;
+            push_unique(&mut meta.loras, name.to_string());
+        }
+    }
+
+    found.then_some(meta)
+}
+
+// =========================
+// 読み込み
+// =========================
+
+/// EXIF UserComment を文字コード接頭辞に従ってデコードする
+fn decode_user_comment(bytes: &[u8]) -> Option<String> {
+    let (prefix, body) = (bytes.get(..8)?, bytes.get(8..)?);
+    let text = match prefix {
+        b"UNICODE\0" => {
+            // バイト順の指定がないため、先頭が 0 なら BE とみなす
+            let big_endian = body.first() == Some(&0);
+            let units: Vec<u16> = body
+                .chunks_exact(2)
+                .map(|c| {
+                    if big_endian {
+                        u16::from_be_bytes([c[0], c[1]])
+                    } else {
+                        u16::from_le_bytes([c[0], c[1]])
+                    }
+                })
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => super::decode_text(body),
+    };
+    non_empty(text.trim_end_matches('\0'))
+}
+
+/// EXIF に埋め込まれた生成メタデータを読み込む（WebP / JPEG）
+fn read_from_exif(path: &Path) -> Option<AiMetadata> {
+    let exif = exif::read_exif(path)?;
+
+    // ComfyUI の WebP 保存は Make/Model に "prompt:{...}" を書き込む
+    for tag in [::exif::Tag::Model, ::exif::Tag::Make] {
+        if let Some(text) = exif::ascii_field(&exif, tag) {
+            if let Some(json) = text.strip_prefix("prompt:") {
+                if let Some(meta) = parse_comfyui(json) {
+                    return Some(meta);
+                }
+            }
+        }
+    }
+
+    let field = exif.get_field(::exif::Tag::UserComment, ::exif::In::PRIMARY)?;
+    match &field.value {
+        ::exif::Value::Undefined(bytes, _) => parse_a1111(&decode_user_comment(bytes)?),
+        _ => None,
+    }
+}
+
+/// 画像生成メタデータを読み込む（見つからなければ None）
+pub fn read_ai_metadata(path: &Path) -> Option<AiMetadata> {
+    let (format, mut reader) = open_container(path).ok()?;
+
+    if format == ContainerFormat::Png {
+        let texts = png::read_text_chunks(&mut reader).ok()?;
+        let find = |keyword: &str| texts.iter().find(|t| t.keyword == keyword);
+
+        if let Some(meta) = find("parameters").and_then(|t| parse_a1111(&t.text)) {
+            return Some(meta);
+        }
+        return find("prompt").and_then(|t| parse_comfyui(&t.text));
+    }
+
+    read_from_exif(path)
+}
+
+/// プロンプトをタグ単位に分割する（重み・LoRA 指定・括弧を除去）
+pub fn prompt_tags(prompt: &str) -> Vec<String> {
+    let mut without_loras = String::new();
+    let mut rest = prompt;
+    while let Some(start) = rest.find('<') {
+        without_loras.push_str(&rest[..start]);
+        match rest[start..].find('>') {
+            Some(end) => rest = &rest[start + end + 1..],
+            None => {
+                rest = "";
+            }
+        }
+    }
+    without_loras.push_str(rest);
+
+    let mut tags = Vec::new();
+    for part in without_loras.split([',', '\n', '|']) {
+        let cleaned: String = part
+            .chars()
+            .filter(|c| !matches!(c, '(' | ')' | '[' | ']' | '{' | '}'))
+            .collect();
+        // "(tag:1.2)" の重み指定を除去
+        let cleaned = match cleaned.rfind(':') {
+            Some(pos) if cleaned[pos + 1..].trim().parse::<f64>().is_ok() => {
+                cleaned[..pos].to_string()
+            }
+            _ => cleaned,
+        };
+        push_unique(&mut tags, cleaned.trim().to_string());
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const A1111_PARAMETERS: &str = "masterpiece, aria starlight, (smile:1.2), <lora:aria_v2:0.8>
+Negative prompt: lowres, bad hands
+Steps: 28, Sampler: DPM++ 2M Karras, CFG scale: 7.5, Seed: 1234567890, Size: 512x768, Model hash: abc123, Model: animeModel_v3, Lora hashes: \"aria_v2: 111, detail_tweaker: 222\", Version: v1.7.0";
+
+    const COMFY_PROMPT: &str = r#"{
+      "3": {"class_type": "KSampler", "inputs": {"seed": 42, "steps": 20, "cfg": 8.0, "sampler_name": "euler", "model": ["10", 0], "positive": ["6", 0], "negative": ["7", 0]}},
+      "4": {"class_type": "CheckpointLoaderSimple", "inputs": {"ckpt_name": "sdxl_base.safetensors"}},
+      "6": {"class_type": "CLIPTextEncode", "inputs": {"text": "leon blaze, armor", "clip": ["10", 1]}},
+      "7": {"class_type": "CLIPTextEncode", "inputs": {"text": "blurry", "clip": ["10", 1]}},
+      "10": {"class_type": "LoraLoader", "inputs": {"lora_name": "leon.safetensors", "model": ["4", 0], "clip": ["4", 1]}}
+    }"#;
+
+    #[test]
+    fn test_parse_a1111() {
+        let meta = parse_a1111(A1111_PARAMETERS).unwrap();
+
+        assert_eq!(meta.generator, AiGenerator::A1111);
+        assert_eq!(
+            meta.prompt.as_deref(),
+            Some("masterpiece, aria starlight, (smile:1.2), <lora:aria_v2:0.8>")
+        );
+        assert_eq!(meta.negative_prompt.as_deref(), Some("lowres, bad hands"));
+        assert_eq!(meta.model.as_deref(), Some("animeModel_v3"));
+        assert_eq!(meta.seed, Some(1234567890));
+        assert_eq!(meta.sampler.as_deref(), Some("DPM++ 2M Karras"));
+        assert_eq!(meta.steps, Some(28));
+        assert_eq!(meta.cfg_scale, Some(7.5));
+        assert_eq!(meta.loras, vec!["aria_v2", "detail_tweaker"]);
+    }
+
+    #[test]
+    fn test_parse_a1111_without_parameters() {
+        assert!(parse_a1111("just a comment").is_none());
+    }
+
+    #[test]
+    fn test_parse_comfyui() {
+        let meta = parse_comfyui(COMFY_PROMPT).unwrap();
+
+        assert_eq!(meta.generator, AiGenerator::ComfyUi);
+        assert_eq!(meta.prompt.as_deref(), Some("leon blaze, armor"));
+        assert_eq!(meta.negative_prompt.as_deref(), Some("blurry"));
+        assert_eq!(meta.model.as_deref(), Some("sdxl_base.safetensors"));
+        assert_eq!(meta.seed, Some(42));
+        assert_eq!(meta.sampler.as_deref(), Some("euler"));
+        assert_eq!(meta.loras, vec!["leon.safetensors"]);
+    }
+
+    #[test]
+    fn test_parse_comfyui_invalid() {
+        assert!(parse_comfyui("not json").is_none());
+        assert!(parse_comfyui("{}").is_none());
+    }
+
+    #[test]
+    fn test_decode_user_comment() {
+        let mut bytes = b"UNICODE\0".to_vec();
+        for unit in "Steps: 1".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_be_bytes());
+        }
+        assert_eq!(decode_user_comment(&bytes).as_deref(), Some("Steps: 1"));
+
+        let mut ascii = b"ASCII\0\0\0".to_vec();
+        ascii.extend_from_slice(b"hello");
+        assert_eq!(decode_user_comment(&ascii).as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn test_read_ai_metadata_png() {
+        let dir = tempdir().unwrap();
+        let a1111 = dir.path().join("a1111.png");
+        let comfy = dir.path().join("comfy.png");
+        let plain = dir.path().join("plain.png");
+
+        let mut parameters = b"parameters\0".to_vec();
+        parameters.extend_from_slice(A1111_PARAMETERS.as_bytes());
+        fs::write(&a1111, png::tests::build_png(&[(b"tEXt", parameters)])).unwrap();
+        fs::write(
+            &comfy,
+            png::tests::build_png(&[(b"iTXt", png::tests::itxt("prompt", COMFY_PROMPT))]),
+        )
+        .unwrap();
+        fs::write(&plain, png::tests::build_png(&[])).unwrap();
+
+        assert_eq!(
+            read_ai_metadata(&a1111).unwrap().generator,
+            AiGenerator::A1111
+        );
+        assert_eq!(
+            read_ai_metadata(&comfy).unwrap().generator,
+            AiGenerator::ComfyUi
+        );
+        assert!(read_ai_metadata(&plain).is_none());
+    }
+
+    #[test]
+    fn test_prompt_tags() {
+        let tags =
+            prompt_tags("masterpiece, aria starlight, (smile:1.2), <lora:aria_v2:0.8>\nleon");
+        assert_eq!(tags, vec!["masterpiece", "aria starlight", "smile", "leon"]);
+    }
+}
@@ -0,0 +1,465 @@
+use serde::Serialize;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// GPS 位置（南緯・西経は負の値）
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GpsPosition {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// 海抜（メートル、海面下は負の値）
+    pub altitude: Option<f64>,
+}
+
+/// 撮影情報
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExifMetadata {
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub lens_make: Option<String>,
+    pub lens_model: Option<String>,
+    /// 露出時間（例: "1/250"、"2.5"）
+    pub exposure_time: Option<String>,
+    pub f_number: Option<f64>,
+    pub iso: Option<u32>,
+    /// 焦点距離（mm）
+    pub focal_length: Option<f64>,
+    /// 35mm 判換算の焦点距離（mm）
+    pub focal_length_35mm: Option<u32>,
+    /// 撮影日時（例: "2025-12-20T14:30:00.120+09:00"、時差が不明な場合は付けない）
+    pub captured_at: Option<String>,
+    /// 撮影日時の時差（例: "+09:00"）
+    pub time_offset: Option<String>,
+    /// 向き（1〜8）
+    pub orientation: Option<u16>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub gps: Option<GpsPosition>,
+    pub software: Option<String>,
+}
+
+/// EXIF を読み込む（JPEG / TIFF / PNG / WebP / HEIF）
+/// 一部のフィールドが壊れている場合も読めた範囲を返す
+pub fn read_exif(path: &Path) -> Option<::exif::Exif> {
+    let file = File::open(path).ok()?;
+    ::exif::Reader::new()
+        .continue_on_error(true)
+        .read_from_container(&mut BufReader::new(file))
+        .or_else(|e| e.distill_partial_result(|_| {}))
+        .ok()
+}
+
+/// ASCII 型フィールドを文字列として取得する
+pub fn ascii_field(exif: &::exif::Exif, tag: ::exif::Tag) -> Option<String> {
+    let field = exif.get_field(tag, ::exif::In::PRIMARY)?;
+    match &field.value {
+        ::exif::Value::Ascii(values) => {
+            let text = values
+                .iter()
+                .map(|v| super::decode_text(v))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let text = text.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+            (!text.is_empty()).then(|| text.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// 符号なし整数型フィールドを取得する
+fn uint_field(exif: &::exif::Exif, tag: ::exif::Tag) -> Option<u32> {
+    exif.get_field(tag, ::exif::In::PRIMARY)?.value.get_uint(0)
+}
+
+/// 有理数型フィールドを取得する（分母が 0 の値は無視する）
+fn rationals(exif: &::exif::Exif, tag: ::exif::Tag) -> Option<Vec<f64>> {
+    let values: Vec<f64> = match &exif.get_field(tag, ::exif::In::PRIMARY)?.value {
+        ::exif::Value::Rational(values) => values
+            .iter()
+            .filter(|v| v.denom != 0)
+            .map(|v| v.to_f64())
+            .collect(),
+        ::exif::Value::SRational(values) => values
+            .iter()
+            .filter(|v| v.denom != 0)
+            .map(|v| v.to_f64())
+            .collect(),
+        _ => return None,
+    };
+    (!values.is_empty()).then_some(values)
+}
+
+fn rational_field(exif: &::exif::Exif, tag: ::exif::Tag) -> Option<f64> {
+    rationals(exif, tag)?.first().copied()
+}
+
+/// 露出時間を "1/250" 形式で表す（1秒以上は秒数）
+fn format_exposure(seconds: f64) -> Option<String> {
+    if seconds <= 0.0 || !seconds.is_finite() {
+        return None;
+    }
+    if seconds >= 1.0 {
+        return Some(format!("{}", (seconds * 10.0).round() / 10.0));
+    }
+    Some(format!("1/{}", (1.0 / seconds).round()))
+}
+
+/// 撮影日時を ISO 8601 形式で取得する（DateTimeOriginal → DateTimeDigitized → DateTime の順）
+fn captured_at(exif: &::exif::Exif) -> (Option<String>, Option<String>) {
+    use ::exif::Tag;
+
+    let candidates = [
+        (
+            Tag::DateTimeOriginal,
+            Tag::SubSecTimeOriginal,
+            Tag::OffsetTimeOriginal,
+        ),
+        (
+            Tag::DateTimeDigitized,
+            Tag::SubSecTimeDigitized,
+            Tag::OffsetTimeDigitized,
+        ),
+        (Tag::DateTime, Tag::SubSecTime, Tag::OffsetTime),
+    ];
+    for (date_tag, subsec_tag, offset_tag) in candidates {
+        let Some(text) = ascii_field(exif, date_tag) else {
+            continue;
+        };
+        let Ok(mut date_time) = ::exif::DateTime::from_ascii(text.as_bytes()) else {
+            continue;
+        };
+        if let Some(subsec) = ascii_field(exif, subsec_tag) {
+            let _ = date_time.parse_subsec(subsec.as_bytes());
+        }
+        let offset =
+            ascii_field(exif, offset_tag).filter(|o| date_time.parse_offset(o.as_bytes()).is_ok());
+
+        let mut formatted = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            date_time.year,
+            date_time.month,
+            date_time.day,
+            date_time.hour,
+            date_time.minute,
+            date_time.second
+        );
+        if let Some(nanosecond) = date_time.nanosecond.filter(|n| *n > 0) {
+            formatted.push_str(&format!(".{:03}", nanosecond / 1_000_000));
+        }
+        if let Some(offset) = &offset {
+            formatted.push_str(offset);
+        }
+        return (Some(formatted), offset);
+    }
+    (None, None)
+}
+
+/// 度・分・秒の有理数を十進の度に変換する
+fn gps_coordinate(exif: &::exif::Exif, tag: ::exif::Tag, ref_tag: ::exif::Tag) -> Option<f64> {
+    let values = rationals(exif, tag)?;
+    let degrees = values
+        .iter()
+        .zip([1.0, 60.0, 3600.0])
+        .map(|(v, d)| v / d)
+        .sum::<f64>();
+    let negative = matches!(ascii_field(exif, ref_tag).as_deref(), Some("S" | "W"));
+    Some(if negative { -degrees } else { degrees })
+}
+
+fn gps_position(exif: &::exif::Exif) -> Option<GpsPosition> {
+    use ::exif::Tag;
+
+    let latitude = gps_coordinate(exif, Tag::GPSLatitude, Tag::GPSLatitudeRef)?;
+    let longitude = gps_coordinate(exif, Tag::GPSLongitude, Tag::GPSLongitudeRef)?;
+    let altitude = rational_field(exif, Tag::GPSAltitude).map(|altitude| {
+        // GPSAltitudeRef が 1 の場合は海面下
+        if uint_field(exif, Tag::GPSAltitudeRef) == Some(1) {
+            -altitude
+        } else {
+            altitude
+        }
+    });
+    Some(GpsPosition {
+        latitude,
+        longitude,
+        altitude,
+    })
+}
+
+/// 画像の向き（1〜8 以外の値は無視する）
+fn orientation(exif: &::exif::Exif) -> Option<u16> {
+    uint_field(exif, ::exif::Tag::Orientation)
+        .filter(|o| (1..=8).contains(o))
+        .map(|o| o as u16)
+}
+
+/// 画像の向きを読み込む（EXIF を持たない画像の場合は None を返す）
+pub fn read_orientation(path: &Path) -> Option<u16> {
+    orientation(&read_exif(path)?)
+}
+
+/// 撮影情報を読み込む（EXIF を持たない画像の場合は None を返す）
+pub fn read_exif_metadata(path: &Path) -> Option<ExifMetadata> {
+    use ::exif::Tag;
+
+    let exif = read_exif(path)?;
+    let (captured_at, time_offset) = captured_at(&exif);
+
+    Some(ExifMetadata {
+        make: ascii_field(&exif, Tag::Make),
+        model: ascii_field(&exif, Tag::Model),
+        lens_make: ascii_field(&exif, Tag::LensMake),
+        lens_model: ascii_field(&exif, Tag::LensModel),
+        exposure_time: rational_field(&exif, Tag::ExposureTime).and_then(format_exposure),
+        f_number: rational_field(&exif, Tag::FNumber),
+        iso: uint_field(&exif, Tag::PhotographicSensitivity),
+        focal_length: rational_field(&exif, Tag::FocalLength),
+        focal_length_35mm: uint_field(&exif, Tag::FocalLengthIn35mmFilm).filter(|f| *f > 0),
+        captured_at,
+        time_offset,
+        orientation: orientation(&exif),
+        width: uint_field(&exif, Tag::PixelXDimension),
+        height: uint_field(&exif, Tag::PixelYDimension),
+        gps: gps_position(&exif),
+        software: ascii_field(&exif, Tag::Software),
+    })
+}
+
+/// EXIF ImageDescription を取得する
+pub fn image_description(path: &Path) -> Option<String> {
+    ascii_field(&read_exif(path)?, ::exif::Tag::ImageDescription)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// テスト用に ASCII タグだけを持つ APP1 (Exif) ペイロードを組み立てる
+    pub(crate) fn build_exif_app1(fields: &[(u16, &str)]) -> Vec<u8> {
+        let mut tiff = b"MM\0\x2A".to_vec();
+        tiff.extend_from_slice(&8u32.to_be_bytes());
+        tiff.extend_from_slice(&(fields.len() as u16).to_be_bytes());
+
+        let mut data_offset = 8 + 2 + 12 * fields.len() as u32 + 4;
+        let mut data = Vec::new();
+        for (tag, value) in fields {
+            let count = value.len() as u32 + 1;
+            tiff.extend_from_slice(&tag.to_be_bytes());
+            tiff.extend_from_slice(&2u16.to_be_bytes());
+            tiff.extend_from_slice(&count.to_be_bytes());
+            tiff.extend_from_slice(&data_offset.to_be_bytes());
+            data.extend_from_slice(value.as_bytes());
+            data.push(0);
+            data_offset += count;
+        }
+        tiff.extend_from_slice(&0u32.to_be_bytes());
+        tiff.extend(data);
+
+        let mut app1 = b"Exif\0\0".to_vec();
+        app1.extend(tiff);
+        app1
+    }
+
+    /// テスト用の IFD エントリの値
+    #[derive(Clone)]
+    pub(crate) enum TestValue<'a> {
+        Ascii(&'a str),
+        Short(u16),
+        Long(u32),
+        Rational(&'a [(u32, u32)]),
+    }
+
+    /// IFD を組み立てる（start は TIFF 先頭からの IFD の位置）
+    fn build_ifd(entries: &[(u16, TestValue)], start: u32) -> Vec<u8> {
+        let mut ifd = (entries.len() as u16).to_be_bytes().to_vec();
+        let mut data = Vec::new();
+        let data_start = start + 2 + 12 * entries.len() as u32 + 4;
+
+        for (tag, value) in entries {
+            let (kind, count, mut bytes) = match value {
+                TestValue::Ascii(text) => {
+                    let mut bytes = text.as_bytes().to_vec();
+                    bytes.push(0);
+                    (2u16, bytes.len() as u32, bytes)
+                }
+                TestValue::Short(v) => (3, 1, v.to_be_bytes().to_vec()),
+                TestValue::Long(v) => (4, 1, v.to_be_bytes().to_vec()),
+                TestValue::Rational(values) => (
+                    5,
+                    values.len() as u32,
+                    values
+                        .iter()
+                        .flat_map(|(n, d)| [n.to_be_bytes(), d.to_be_bytes()].concat())
+                        .collect(),
+                ),
+            };
+            ifd.extend_from_slice(&tag.to_be_bytes());
+            ifd.extend_from_slice(&kind.to_be_bytes());
+            ifd.extend_from_slice(&count.to_be_bytes());
+            if bytes.len() <= 4 {
+                bytes.resize(4, 0);
+                ifd.extend(bytes);
+            } else {
+                ifd.extend_from_slice(&(data_start + data.len() as u32).to_be_bytes());
+                data.extend(bytes);
+                if data.len() % 2 == 1 {
+                    data.push(0);
+                }
+            }
+        }
+        ifd.extend_from_slice(&0u32.to_be_bytes());
+        ifd.extend(data);
+        ifd
+    }
+
+    /// IFD0・Exif IFD・GPS IFD を持つ TIFF を組み立てる
+    pub(crate) fn build_tiff(
+        primary: &[(u16, TestValue)],
+        exif_ifd: &[(u16, TestValue)],
+        gps_ifd: &[(u16, TestValue)],
+    ) -> Vec<u8> {
+        let build_primary = |exif_start: u32, gps_start: u32| {
+            let mut entries = primary.to_vec();
+            entries.push((0x8769, TestValue::Long(exif_start)));
+            entries.push((0x8825, TestValue::Long(gps_start)));
+            build_ifd(&entries, 8)
+        };
+        // IFD の大きさは値によらないため、仮の位置で組み立てて後続の IFD の位置を決める
+        let exif_start = 8 + build_primary(0, 0).len() as u32;
+        let gps_start = exif_start + build_ifd(exif_ifd, 0).len() as u32;
+
+        let mut tiff = b"MM\0\x2A".to_vec();
+        tiff.extend_from_slice(&8u32.to_be_bytes());
+        tiff.extend(build_primary(exif_start, gps_start));
+        tiff.extend(build_ifd(exif_ifd, exif_start));
+        tiff.extend(build_ifd(gps_ifd, gps_start));
+        tiff
+    }
+
+    fn sample_tiff() -> Vec<u8> {
+        build_tiff(
+            &[
+                (0x010F, TestValue::Ascii("Sony")),
+                (0x0110, TestValue::Ascii("ILCE-7M3")),
+                (0x0112, TestValue::Short(6)),
+                (0x0131, TestValue::Ascii("picSort 1.0")),
+            ],
+            &[
+                (0x829A, TestValue::Rational(&[(1, 250)])),
+                (0x829D, TestValue::Rational(&[(28, 10)])),
+                (0x8827, TestValue::Short(400)),
+                (0x9003, TestValue::Ascii("2025:12:20 14:30:00")),
+                (0x9011, TestValue::Ascii("+09:00")),
+                (0x9291, TestValue::Ascii("12")),
+                (0x920A, TestValue::Rational(&[(35, 1)])),
+                (0xA434, TestValue::Ascii("FE 24-70mm F2.8 GM")),
+            ],
+            &[
+                (0x0001, TestValue::Ascii("N")),
+                (
+                    0x0002,
+                    TestValue::Rational(&[(35, 1), (40, 1), (5232, 100)]),
+                ),
+                (0x0003, TestValue::Ascii("W")),
+                (
+                    0x0004,
+                    TestValue::Rational(&[(139, 1), (46, 1), (148, 100)]),
+                ),
+                (0x0006, TestValue::Rational(&[(40, 1)])),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_read_exif_metadata_jpeg() {
+        // Arrange
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("photo.jpg");
+        let mut app1 = b"Exif\0\0".to_vec();
+        app1.extend(sample_tiff());
+        std::fs::write(
+            &path,
+            super::super::jpeg::tests::build_jpeg(&[(0xE1, &app1)]),
+        )
+        .unwrap();
+
+        // Act
+        let meta = read_exif_metadata(&path).unwrap();
+
+        // Assert
+        assert_eq!(meta.make.as_deref(), Some("Sony"));
+        assert_eq!(meta.model.as_deref(), Some("ILCE-7M3"));
+        assert_eq!(meta.lens_model.as_deref(), Some("FE 24-70mm F2.8 GM"));
+        assert_eq!(meta.exposure_time.as_deref(), Some("1/250"));
+        assert_eq!(meta.f_number, Some(2.8));
+        assert_eq!(meta.iso, Some(400));
+        assert_eq!(meta.focal_length, Some(35.0));
+        assert_eq!(
+            meta.captured_at.as_deref(),
+            Some("2025-12-20T14:30:00.120+09:00")
+        );
+        assert_eq!(meta.time_offset.as_deref(), Some("+09:00"));
+        assert_eq!(meta.orientation, Some(6));
+        assert_eq!(meta.software.as_deref(), Some("picSort 1.0"));
+
+        let gps = meta.gps.unwrap();
+        assert!((gps.latitude - 35.681_2).abs() < 1e-4);
+        assert!((gps.longitude + 139.767_1).abs() < 1e-4);
+        assert_eq!(gps.altitude, Some(40.0));
+    }
+
+    #[test]
+    fn test_read_exif_metadata_png_exif_chunk() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("image.png");
+        let bytes = super::super::png::tests::build_png(&[(b"eXIf", sample_tiff())]);
+        std::fs::write(&path, bytes).unwrap();
+
+        let meta = read_exif_metadata(&path).unwrap();
+
+        assert_eq!(meta.model.as_deref(), Some("ILCE-7M3"));
+        assert_eq!(meta.iso, Some(400));
+    }
+
+    #[test]
+    fn test_read_exif_metadata_without_exif() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("empty.jpg");
+        std::fs::write(&path, super::super::jpeg::tests::build_jpeg(&[])).unwrap();
+        let gif = dir.path().join("anim.gif");
+        std::fs::write(&gif, b"GIF89a").unwrap();
+
+        assert!(read_exif_metadata(&path).is_none());
+        assert!(read_exif_metadata(&gif).is_none());
+        assert_eq!(format_exposure(2.5).as_deref(), Some("2.5"));
+        assert_eq!(format_exposure(0.0), None);
+    }
+
+    #[test]
+    fn test_image_description() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("photo.jpg");
+        let app1 = build_exif_app1(&[(0x010E, "Aria Starlight")]);
+        std::fs::write(
+            &path,
+            super::super::jpeg::tests::build_jpeg(&[(0xE1, &app1)]),
+        )
+        .unwrap();
+
+        assert_eq!(image_description(&path).as_deref(), Some("Aria Starlight"));
+    }
+
+    #[test]
+    fn test_image_description_missing() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("empty.jpg");
+        std::fs::write(&path, super::super::jpeg::tests::build_jpeg(&[])).unwrap();
+
+        assert!(image_description(&path).is_none());
+        assert!(image_description(&dir.path().join("missing.jpg")).is_none());
+    }
+}
@@ -0,0 +1,127 @@
+/// JPEG APP13 に Photoshop リソースを格納する際の識別子
+pub const PHOTOSHOP_IDENTIFIER: &[u8] = b"Photoshop 3.0\0";
+
+/// IPTC-NAA レコードを格納する Photoshop リソース ID
+const IPTC_RESOURCE_ID: u16 = 0x0404;
+
+/// IPTC IIM のデータセット
+#[derive(Debug, Clone, PartialEq)]
+pub struct IptcDataSet {
+    pub record: u8,
+    pub dataset: u8,
+    pub data: Vec<u8>,
+}
+
+/// Photoshop リソースブロックから IPTC-NAA データを取り出す
+fn find_iptc_resource(payload: &[u8]) -> Option<&[u8]> {
+    let mut pos = 0;
+    while pos + 12 <= payload.len() {
+        if &payload[pos..pos + 4] != b"8BIM" {
+            return None;
+        }
+        let id = u16::from_be_bytes([payload[pos + 4], payload[pos + 5]]);
+        pos += 6;
+
+        // パスカル文字列の名前（長さバイトを含めて偶数に揃える）
+        let name_len = *payload.get(pos)? as usize;
+        let mut name_total = name_len + 1;
+        if name_total % 2 == 1 {
+            name_total += 1;
+        }
+        pos += name_total;
+
+        let size_bytes = payload.get(pos..pos + 4)?;
+        let size = u32::from_be_bytes([size_bytes[0], size_bytes[1], size_bytes[2], size_bytes[3]])
+            as usize;
+        pos += 4;
+
+        let data = payload.get(pos..pos + size)?;
+        if id == IPTC_RESOURCE_ID {
+            return Some(data);
+        }
+        pos += size + size % 2;
+    }
+    None
+}
+
+/// IPTC IIM のデータセットを解析する
+pub fn parse_datasets(iim: &[u8]) -> Vec<IptcDataSet> {
+    let mut datasets = Vec::new();
+    let mut pos = 0;
+
+    while pos + 5 <= iim.len() && iim[pos] == 0x1C {
+        let record = iim[pos + 1];
+        let dataset = iim[pos + 2];
+        let size = u16::from_be_bytes([iim[pos + 3], iim[pos + 4]]);
+        pos += 5;
+
+        // 拡張データセット（32KB超）は扱わない
+        if size & 0x8000 != 0 {
+            break;
+        }
+        let Some(data) = iim.get(pos..pos + size as usize) else {
+            break;
+        };
+        datasets.push(IptcDataSet {
+            record,
+            dataset,
+            data: data.to_vec(),
+        });
+        pos += size as usize;
+    }
+
+    datasets
+}
+
+/// APP13 ペイロード（識別子の後ろ）から IPTC キーワード (2:25) を取得する
+pub fn parse_keywords(payload: &[u8]) -> Vec<String> {
+    let Some(iim) = find_iptc_resource(payload) else {
+        return Vec::new();
+    };
+    parse_datasets(iim)
+        .into_iter()
+        .filter(|d| d.record == 2 && d.dataset == 25)
+        .map(|d| super::decode_text(&d.data).trim().to_string())
+        .filter(|k| !k.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// テスト用にキーワードを含む Photoshop リソースを組み立てる
+    pub(crate) fn build_payload(keywords: &[&str]) -> Vec<u8> {
+        let mut iim = vec![0x1C, 2, 0, 0, 2, 0, 4];
+        for keyword in keywords {
+            iim.extend_from_slice(&[0x1C, 2, 25]);
+            iim.extend_from_slice(&(keyword.len() as u16).to_be_bytes());
+            iim.extend_from_slice(keyword.as_bytes());
+        }
+
+        let mut payload = b"8BIM".to_vec();
+        payload.extend_from_slice(&0x03EDu16.to_be_bytes());
+        payload.extend_from_slice(&[0, 0]);
+        payload.extend_from_slice(&1u32.to_be_bytes());
+        payload.extend_from_slice(&[0, 0]);
+
+        payload.extend_from_slice(b"8BIM");
+        payload.extend_from_slice(&IPTC_RESOURCE_ID.to_be_bytes());
+        payload.extend_from_slice(&[0, 0]);
+        payload.extend_from_slice(&(iim.len() as u32).to_be_bytes());
+        payload.extend(iim);
+        payload
+    }
+
+    #[test]
+    fn test_parse_keywords() {
+        let payload = build_payload(&["Aria Starlight", "桜"]);
+        assert_eq!(parse_keywords(&payload), vec!["Aria Starlight", "桜"]);
+    }
+
+    #[test]
+    fn test_parse_keywords_invalid() {
+        assert!(parse_keywords(b"garbage").is_empty());
+        assert!(parse_keywords(&[]).is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+use std::io::{self, Read};
+
+const MARKER_SOS: u8 = 0xDA;
+const MARKER_EOI: u8 = 0xD9;
+
+/// JPEG のマーカーセグメント（APPn / COM のみ保持）
+#[derive(Debug, Clone)]
+pub struct JpegSegment {
+    pub marker: u8,
+    pub data: Vec<u8>,
+}
+
+/// 長さフィールドを持たないマーカーか
+fn is_standalone(marker: u8) -> bool {
+    marker == 0x01 || (0xD0..=0xD7).contains(&marker)
+}
+
+/// APPn / COM セグメントか
+fn is_metadata_marker(marker: u8) -> bool {
+    (0xE0..=0xEF).contains(&marker) || marker == 0xFE
+}
+
+/// 画像データ開始（SOS）までのメタデータセグメントを読み込む
+pub fn read_segments<R: Read>(reader: &mut R) -> io::Result<Vec<JpegSegment>> {
+    let mut soi = [0u8; 2];
+    reader.read_exact(&mut soi)?;
+    if soi != [0xFF, 0xD8] {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Not a JPEG file",
+        ));
+    }
+
+    let mut segments = Vec::new();
+    let mut byte = [0u8; 1];
+
+    loop {
+        reader.read_exact(&mut byte)?;
+        if byte[0] != 0xFF {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid JPEG marker",
+            ));
+        }
+        // フィルバイト（連続する 0xFF）を読み飛ばす
+        let mut marker = 0xFF;
+        while marker == 0xFF {
+            reader.read_exact(&mut byte)?;
+            marker = byte[0];
+        }
+
+        if marker == MARKER_SOS || marker == MARKER_EOI {
+            break;
+        }
+        if is_standalone(marker) {
+            continue;
+        }
+
+        let mut len = [0u8; 2];
+        reader.read_exact(&mut len)?;
+        let len = u16::from_be_bytes(len) as usize;
+        if len < 2 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid JPEG segment length",
+            ));
+        }
+
+        if is_metadata_marker(marker) {
+            let mut data = vec![0u8; len - 2];
+            reader.read_exact(&mut data)?;
+            segments.push(JpegSegment { marker, data });
+        } else {
+            io::copy(&mut reader.by_ref().take((len - 2) as u64), &mut io::sink())?;
+        }
+    }
+
+    Ok(segments)
+}
+
+/// 指定した APPn マーカーかつ識別子で始まるセグメントのペイロードを返す
+pub fn find_app_payload<'a>(
+    segments: &'a [JpegSegment],
+    marker: u8,
+    identifier: &[u8],
+) -> Option<&'a [u8]> {
+    segments
+        .iter()
+        .find(|s| s.marker == marker && s.data.starts_with(identifier))
+        .map(|s| &s.data[identifier.len()..])
+}
+
+/// 指定した APPn マーカーかつ識別子のセグメントを置き換えたバイト列を返す
+/// 既存のセグメントがなければ APP0 / APP1 の直後に挿入する
+pub fn replace_app_segment(
+    bytes: &[u8],
+    marker: u8,
+    identifier: &[u8],
+    payload: &[u8],
+) -> io::Result<Vec<u8>> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return Err(invalid("Not a JPEG file"));
+    }
+
+    let mut segment = identifier.to_vec();
+    segment.extend_from_slice(payload);
+    if segment.len() + 2 > u16::MAX as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "JPEG segment too large",
+        ));
+    }
+    let mut new_segment = vec![0xFF, marker];
+    new_segment.extend_from_slice(&((segment.len() + 2) as u16).to_be_bytes());
+    new_segment.extend(segment);
+
+    let mut out = vec![0xFF, 0xD8];
+    let mut pos = 2;
+    let mut inserted = false;
+
+    loop {
+        if bytes.get(pos) != Some(&0xFF) {
+            return Err(invalid("Invalid JPEG marker"));
+        }
+        // フィルバイト（連続する 0xFF）を読み飛ばす
+        let mut marker_pos = pos + 1;
+        while bytes.get(marker_pos) == Some(&0xFF) {
+            marker_pos += 1;
+        }
+        let current = *bytes
+            .get(marker_pos)
+            .ok_or_else(|| invalid("Unexpected end of JPEG"))?;
+
+        if !inserted && current != 0xE0 && current != 0xE1 {
+            out.extend_from_slice(&new_segment);
+            inserted = true;
+        }
+        if current == MARKER_SOS || current == MARKER_EOI {
+            // 画像データ以降はそのままコピー
+            out.extend_from_slice(&bytes[pos..]);
+            return Ok(out);
+        }
+        if is_standalone(current) {
+            out.extend_from_slice(&bytes[pos..=marker_pos]);
+            pos = marker_pos + 1;
+            continue;
+        }
+
+        let len = bytes
+            .get(marker_pos + 1..marker_pos + 3)
+            .map(|l| u16::from_be_bytes([l[0], l[1]]) as usize)
+            .ok_or_else(|| invalid("Unexpected end of JPEG"))?;
+        let end = marker_pos + 1 + len;
+        if len < 2 || end > bytes.len() {
+            return Err(invalid("Invalid JPEG segment length"));
+        }
+
+        let is_replaced = current == marker && bytes[marker_pos + 3..end].starts_with(identifier);
+        if !is_replaced {
+            out.extend_from_slice(&bytes[pos..end]);
+        }
+        pos = end;
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// テスト用に最小限の JPEG バイト列を組み立てる
+    pub(crate) fn build_jpeg(segments: &[(u8, &[u8])]) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xD8];
+        for (marker, data) in segments {
+            bytes.extend_from_slice(&[0xFF, *marker]);
+            bytes.extend_from_slice(&((data.len() + 2) as u16).to_be_bytes());
+            bytes.extend_from_slice(data);
+        }
+        bytes.extend_from_slice(&[0xFF, MARKER_SOS, 0x00, 0x02, 0x12, 0x34, 0xFF, MARKER_EOI]);
+        bytes
+    }
+
+    #[test]
+    fn test_read_segments() {
+        let bytes = build_jpeg(&[(0xE1, b"Exif\0\0abc"), (0xDB, b"quant"), (0xFE, b"comment")]);
+
+        let segments = read_segments(&mut Cursor::new(bytes)).unwrap();
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].marker, 0xE1);
+        assert_eq!(segments[1].data, b"comment");
+        assert_eq!(
+            find_app_payload(&segments, 0xE1, b"Exif\0\0"),
+            Some(&b"abc"[..])
+        );
+    }
+
+    #[test]
+    fn test_read_segments_rejects_non_jpeg() {
+        assert!(read_segments(&mut Cursor::new(b"\x89PNG".to_vec())).is_err());
+    }
+
+    #[test]
+    fn test_replace_app_segment() {
+        let bytes = build_jpeg(&[(0xE0, b"JFIF\0"), (0xE1, b"XMP\0old"), (0xDB, b"quant")]);
+
+        let replaced = replace_app_segment(&bytes, 0xE1, b"XMP\0", b"new").unwrap();
+        let segments = read_segments(&mut Cursor::new(&replaced)).unwrap();
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(
+            find_app_payload(&segments, 0xE1, b"XMP\0"),
+            Some(&b"new"[..])
+        );
+        // 画像データは変更しない
+        assert!(replaced.ends_with(&bytes[bytes.len() - 15..]));
+
+        // セグメントがなければ APP0 の直後に挿入
+        let bytes = build_jpeg(&[(0xE0, b"JFIF\0"), (0xDB, b"quant")]);
+        let inserted = replace_app_segment(&bytes, 0xE1, b"XMP\0", b"new").unwrap();
+        let segments = read_segments(&mut Cursor::new(&inserted)).unwrap();
+        assert_eq!(segments[1].marker, 0xE1);
+    }
+}
@@ -0,0 +1,90 @@
+pub mod ai;
+pub mod exif;
+pub mod iptc;
+pub mod jpeg;
+pub mod png;
+pub mod webp;
+pub mod xattrs;
+pub mod xmp;
+
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// メタデータを格納するコンテナ形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerFormat {
+    Jpeg,
+    Png,
+    WebP,
+    Other,
+}
+
+/// 先頭バイトからコンテナ形式を判定
+pub fn detect_format(header: &[u8]) -> ContainerFormat {
+    if header.starts_with(&[0xFF, 0xD8]) {
+        ContainerFormat::Jpeg
+    } else if header.starts_with(png::SIGNATURE) {
+        ContainerFormat::Png
+    } else if header.len() >= 12 && &header[0..4] == b"RIFF" && &header[8..12] == b"WEBP" {
+        ContainerFormat::WebP
+    } else {
+        ContainerFormat::Other
+    }
+}
+
+/// ファイルを開き、形式を判定して先頭に戻したリーダーを返す
+pub fn open_container(path: &Path) -> io::Result<(ContainerFormat, BufReader<File>)> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut header = [0u8; 12];
+    let read = reader.read(&mut header)?;
+    reader.seek(SeekFrom::Start(0))?;
+    Ok((detect_format(&header[..read]), reader))
+}
+
+/// チャンクのデータを読み込む
+/// 長さはファイルに書かれた値のため、先に確保せず実際に読めた分だけ読み込む（壊れたファイルで巨大な確保をしない）
+pub fn read_chunk_data<R: Read>(reader: &mut R, len: u64) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    reader.take(len).read_to_end(&mut data)?;
+    if (data.len() as u64) < len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "Chunk is truncated",
+        ));
+    }
+    Ok(data)
+}
+
+/// バイト列を UTF-8 として解釈し、失敗した場合は Latin-1 とみなす
+pub fn decode_text(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s.to_string(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            detect_format(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            ContainerFormat::Jpeg
+        );
+        assert_eq!(detect_format(png::SIGNATURE), ContainerFormat::Png);
+        assert_eq!(
+            detect_format(b"RIFF\0\0\0\0WEBPVP8X"),
+            ContainerFormat::WebP
+        );
+        assert_eq!(detect_format(b"GIF89a"), ContainerFormat::Other);
+    }
+
+    #[test]
+    fn test_decode_text_latin1_fallback() {
+        assert_eq!(decode_text("桜".as_bytes()), "桜");
+        assert_eq!(decode_text(&[0x63, 0x61, 0x66, 0xE9]), "café");
+    }
+}
@@ -0,0 +1,271 @@
+use super::read_chunk_data;
+use flate2::read::ZlibDecoder;
+use flate2::Crc;
+use std::io::{self, Read, Seek, SeekFrom};
+
+pub const SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// XMP パケットを格納する iTXt のキーワード
+pub const XMP_KEYWORD: &str = "XML:com.adobe.xmp";
+
+/// PNG チャンク
+#[derive(Debug, Clone)]
+pub struct PngChunk {
+    pub kind: [u8; 4],
+    pub data: Vec<u8>,
+}
+
+/// PNG のテキストチャンク（tEXt / zTXt / iTXt）
+#[derive(Debug, Clone, PartialEq)]
+pub struct PngText {
+    pub keyword: String,
+    pub text: String,
+}
+
+/// 指定した種類のチャンクを読み込む（それ以外はシークで読み飛ばす）
+pub fn read_chunks<R: Read + Seek>(
+    reader: &mut R,
+    wanted: &[&[u8; 4]],
+) -> io::Result<Vec<PngChunk>> {
+    let mut signature = [0u8; 8];
+    reader.read_exact(&mut signature)?;
+    if signature != SIGNATURE {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a PNG file"));
+    }
+
+    let mut chunks = Vec::new();
+    loop {
+        let mut header = [0u8; 8];
+        match reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e),
+        }
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+        let kind = [header[4], header[5], header[6], header[7]];
+
+        if &kind == b"IEND" {
+            break;
+        }
+
+        if wanted.iter().any(|w| **w == kind) {
+            let data = read_chunk_data(reader, len)?;
+            chunks.push(PngChunk { kind, data });
+            // CRC
+            reader.seek(SeekFrom::Current(4))?;
+        } else {
+            reader.seek(SeekFrom::Current(len as i64 + 4))?;
+        }
+    }
+
+    Ok(chunks)
+}
+
+fn inflate(data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    ZlibDecoder::new(data).read_to_end(&mut out).ok()?;
+    Some(out)
+}
+
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+/// テキストチャンクを解析する
+fn parse_text_chunk(chunk: &PngChunk) -> Option<PngText> {
+    let data = &chunk.data;
+    let null = data.iter().position(|&b| b == 0)?;
+    let keyword = latin1(&data[..null]);
+    let rest = &data[null + 1..];
+
+    let text = match &chunk.kind {
+        b"tEXt" => latin1(rest),
+        b"zTXt" => {
+            // 圧縮方式(1) + 圧縮データ
+            latin1(&inflate(rest.get(1..)?)?)
+        }
+        b"iTXt" => {
+            // 圧縮フラグ(1) + 圧縮方式(1) + 言語タグ\0 + 翻訳キーワード\0 + テキスト
+            let compressed = *rest.first()? == 1;
+            let rest = rest.get(2..)?;
+            let lang_end = rest.iter().position(|&b| b == 0)?;
+            let rest = &rest[lang_end + 1..];
+            let translated_end = rest.iter().position(|&b| b == 0)?;
+            let body = &rest[translated_end + 1..];
+            if compressed {
+                String::from_utf8_lossy(&inflate(body)?).to_string()
+            } else {
+                String::from_utf8_lossy(body).to_string()
+            }
+        }
+        _ => return None,
+    };
+
+    Some(PngText { keyword, text })
+}
+
+/// PNG のテキストチャンクをすべて読み込む
+pub fn read_text_chunks<R: Read + Seek>(reader: &mut R) -> io::Result<Vec<PngText>> {
+    let chunks = read_chunks(reader, &[b"tEXt", b"zTXt", b"iTXt"])?;
+    Ok(chunks.iter().filter_map(parse_text_chunk).collect())
+}
+
+/// チャンクをバイト列に書き出す（CRC を計算する）
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8], data: &[u8]) {
+    let mut crc = Crc::new();
+    crc.update(kind);
+    crc.update(data);
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    out.extend_from_slice(&crc.sum().to_be_bytes());
+}
+
+/// 指定キーワードのテキストチャンクを非圧縮の iTXt で置き換えたバイト列を返す
+/// 既存のチャンクがなければ最初の IDAT の直前に挿入する
+pub fn replace_itxt(bytes: &[u8], keyword: &str, text: &str) -> io::Result<Vec<u8>> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    if !bytes.starts_with(SIGNATURE) {
+        return Err(invalid("Not a PNG file"));
+    }
+
+    // キーワード\0 + 圧縮フラグ(0) + 圧縮方式(0) + 言語タグ\0 + 翻訳キーワード\0 + テキスト
+    let mut itxt = keyword.as_bytes().to_vec();
+    itxt.extend_from_slice(&[0, 0, 0, 0, 0]);
+    itxt.extend_from_slice(text.as_bytes());
+
+    let mut out = SIGNATURE.to_vec();
+    let mut pos = SIGNATURE.len();
+    let mut inserted = false;
+
+    while pos < bytes.len() {
+        let header = bytes
+            .get(pos..pos + 8)
+            .ok_or_else(|| invalid("Unexpected end of PNG"))?;
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let kind = &header[4..8];
+        let end = pos + 12 + len;
+        if end > bytes.len() {
+            return Err(invalid("Invalid PNG chunk length"));
+        }
+
+        if !inserted && (kind == b"IDAT" || kind == b"IEND") {
+            write_chunk(&mut out, b"iTXt", &itxt);
+            inserted = true;
+        }
+
+        let data = &bytes[pos + 8..pos + 8 + len];
+        let is_replaced = matches!(kind, b"tEXt" | b"zTXt" | b"iTXt")
+            && data.iter().position(|&b| b == 0) == Some(keyword.len())
+            && data.starts_with(keyword.as_bytes());
+        if !is_replaced {
+            out.extend_from_slice(&bytes[pos..end]);
+        }
+        pos = end;
+    }
+
+    if !inserted {
+        return Err(invalid("Missing PNG IEND chunk"));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// テスト用に最小限の PNG バイト列を組み立てる（CRC は検証しないため 0）
+    pub(crate) fn build_png(chunks: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = SIGNATURE.to_vec();
+        for (kind, data) in chunks {
+            bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(*kind);
+            bytes.extend_from_slice(data);
+            bytes.extend_from_slice(&[0, 0, 0, 0]);
+        }
+        bytes.extend_from_slice(&[0, 0, 0, 0]);
+        bytes.extend_from_slice(b"IEND");
+        bytes.extend_from_slice(&[0, 0, 0, 0]);
+        bytes
+    }
+
+    pub(crate) fn itxt(keyword: &str, text: &str) -> Vec<u8> {
+        let mut data = keyword.as_bytes().to_vec();
+        data.extend_from_slice(&[0, 0, 0, 0, 0]);
+        data.extend_from_slice(text.as_bytes());
+        data
+    }
+
+    #[test]
+    fn test_read_text_chunks() {
+        let bytes = build_png(&[
+            (b"IHDR", vec![0; 13]),
+            (b"tEXt", b"Title\0Aria Starlight".to_vec()),
+            (b"iTXt", itxt("Description", "桜と月影")),
+            (b"IDAT", vec![1, 2, 3]),
+        ]);
+
+        let texts = read_text_chunks(&mut Cursor::new(bytes)).unwrap();
+
+        assert_eq!(texts.len(), 2);
+        assert_eq!(texts[0].keyword, "Title");
+        assert_eq!(texts[0].text, "Aria Starlight");
+        assert_eq!(texts[1].text, "桜と月影");
+    }
+
+    #[test]
+    fn test_read_compressed_text_chunk() {
+        use flate2::write::ZlibEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"Leon Blaze").unwrap();
+        let mut data = b"Comment\0\0".to_vec();
+        data.extend(encoder.finish().unwrap());
+
+        let bytes = build_png(&[(b"zTXt", data)]);
+        let texts = read_text_chunks(&mut Cursor::new(bytes)).unwrap();
+
+        assert_eq!(texts[0].text, "Leon Blaze");
+    }
+
+    #[test]
+    fn test_truncated_chunk_is_rejected() {
+        // Arrange: 長さが 4GiB 近くあると書かれた途中で終わるチャンク
+        let mut bytes = SIGNATURE.to_vec();
+        bytes.extend_from_slice(&u32::MAX.to_be_bytes());
+        bytes.extend_from_slice(b"tEXt");
+        bytes.extend_from_slice(b"Title\0Aria");
+
+        // Act
+        let result = read_text_chunks(&mut Cursor::new(bytes));
+
+        // Assert: 巨大な確保をせずにエラーを返す
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_replace_itxt() {
+        let bytes = build_png(&[
+            (b"IHDR", vec![0; 13]),
+            (b"iTXt", itxt(XMP_KEYWORD, "old")),
+            (b"tEXt", b"Title\0Aria".to_vec()),
+            (b"IDAT", vec![1, 2, 3]),
+        ]);
+
+        let replaced = replace_itxt(&bytes, XMP_KEYWORD, "new").unwrap();
+        let texts = read_text_chunks(&mut Cursor::new(&replaced)).unwrap();
+
+        assert_eq!(texts.len(), 2);
+        assert_eq!(texts[0].keyword, "Title");
+        assert_eq!(texts[1].keyword, XMP_KEYWORD);
+        assert_eq!(texts[1].text, "new");
+
+        // 挿入したチャンクには CRC が付く
+        let mut expected = Vec::new();
+        write_chunk(&mut expected, b"iTXt", &itxt(XMP_KEYWORD, "new"));
+        assert!(replaced.windows(expected.len()).any(|w| w == expected));
+    }
+}
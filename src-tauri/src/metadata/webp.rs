@@ -0,0 +1,252 @@
+use super::read_chunk_data;
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// RIFF チャンク
+#[derive(Debug, Clone)]
+pub struct RiffChunk {
+    pub fourcc: [u8; 4],
+    pub data: Vec<u8>,
+}
+
+/// 指定した種類のチャンクを読み込む（それ以外はシークで読み飛ばす）
+pub fn read_chunks<R: Read + Seek>(
+    reader: &mut R,
+    wanted: &[&[u8; 4]],
+) -> io::Result<Vec<RiffChunk>> {
+    let mut header = [0u8; 12];
+    reader.read_exact(&mut header)?;
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WEBP" {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Not a WebP file",
+        ));
+    }
+
+    let mut chunks = Vec::new();
+    loop {
+        let mut chunk_header = [0u8; 8];
+        match reader.read_exact(&mut chunk_header) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e),
+        }
+        let fourcc = [
+            chunk_header[0],
+            chunk_header[1],
+            chunk_header[2],
+            chunk_header[3],
+        ];
+        let len = u32::from_le_bytes([
+            chunk_header[4],
+            chunk_header[5],
+            chunk_header[6],
+            chunk_header[7],
+        ]) as u64;
+        // チャンクは偶数バイト境界に揃えられる
+        let padding = len % 2;
+
+        if wanted.iter().any(|w| **w == fourcc) {
+            let data = read_chunk_data(reader, len)?;
+            chunks.push(RiffChunk { fourcc, data });
+            reader.seek(SeekFrom::Current(padding as i64))?;
+        } else {
+            reader.seek(SeekFrom::Current((len + padding) as i64))?;
+        }
+    }
+
+    Ok(chunks)
+}
+
+/// VP8X の XMP フラグ
+const FLAG_XMP: u8 = 0x04;
+/// VP8X のアルファフラグ
+const FLAG_ALPHA: u8 = 0x10;
+
+/// 単純形式（VP8 / VP8L）のビットストリームからキャンバスサイズとアルファの有無を取得する
+fn simple_canvas(fourcc: &[u8], data: &[u8]) -> Option<(u32, u32, bool)> {
+    match fourcc {
+        b"VP8 " => {
+            // フレームタグ(3) + スタートコード(3) + 幅(2) + 高さ(2)
+            if data.get(3..6)? != [0x9D, 0x01, 0x2A] {
+                return None;
+            }
+            let width = u16::from_le_bytes([*data.get(6)?, *data.get(7)?]) & 0x3FFF;
+            let height = u16::from_le_bytes([*data.get(8)?, *data.get(9)?]) & 0x3FFF;
+            if width == 0 || height == 0 {
+                return None;
+            }
+            Some((width as u32, height as u32, false))
+        }
+        b"VP8L" => {
+            // シグネチャ(0x2F) + 幅-1(14bit) + 高さ-1(14bit) + アルファ(1bit)
+            if *data.first()? != 0x2F {
+                return None;
+            }
+            let bits = u32::from_le_bytes(data.get(1..5)?.try_into().ok()?);
+            let width = (bits & 0x3FFF) + 1;
+            let height = ((bits >> 14) & 0x3FFF) + 1;
+            Some((width, height, (bits >> 28) & 1 == 1))
+        }
+        _ => None,
+    }
+}
+
+fn push_chunk(out: &mut Vec<u8>, fourcc: &[u8], data: &[u8]) {
+    out.extend_from_slice(fourcc);
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+/// XMP チャンクを置き換えたバイト列を返す
+/// 単純形式の場合は VP8X チャンクを追加して拡張形式に変換する
+pub fn replace_xmp_chunk(bytes: &[u8], packet: &[u8]) -> io::Result<Vec<u8>> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WEBP" {
+        return Err(invalid("Not a WebP file"));
+    }
+
+    let mut chunks: Vec<(&[u8], &[u8])> = Vec::new();
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let fourcc = &bytes[pos..pos + 4];
+        let len = u32::from_le_bytes([
+            bytes[pos + 4],
+            bytes[pos + 5],
+            bytes[pos + 6],
+            bytes[pos + 7],
+        ]) as usize;
+        let end = pos + 8 + len;
+        if end > bytes.len() {
+            return Err(invalid("Invalid WebP chunk length"));
+        }
+        if fourcc != b"XMP " {
+            chunks.push((fourcc, &bytes[pos + 8..end]));
+        }
+        pos = end + len % 2;
+    }
+
+    let mut body = b"WEBP".to_vec();
+    let mut rest = &chunks[..];
+    match chunks.first() {
+        Some((b"VP8X", data)) if !data.is_empty() => {
+            let mut vp8x = data.to_vec();
+            vp8x[0] |= FLAG_XMP;
+            push_chunk(&mut body, b"VP8X", &vp8x);
+            rest = &chunks[1..];
+        }
+        Some((fourcc, data)) => {
+            let (width, height, alpha) =
+                simple_canvas(fourcc, data).ok_or_else(|| invalid("Unsupported WebP bitstream"))?;
+            let mut vp8x = vec![FLAG_XMP | if alpha { FLAG_ALPHA } else { 0 }, 0, 0, 0];
+            vp8x.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+            vp8x.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+            push_chunk(&mut body, b"VP8X", &vp8x);
+        }
+        None => return Err(invalid("Empty WebP file")),
+    }
+    for (fourcc, data) in rest {
+        push_chunk(&mut body, fourcc, data);
+    }
+    // XMP はファイル末尾に置く
+    push_chunk(&mut body, b"XMP ", packet);
+
+    let mut out = b"RIFF".to_vec();
+    out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    out.extend(body);
+    Ok(out)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// テスト用に最小限の WebP バイト列を組み立てる
+    pub(crate) fn build_webp(chunks: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut body = b"WEBP".to_vec();
+        for (fourcc, data) in chunks {
+            body.extend_from_slice(*fourcc);
+            body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            body.extend_from_slice(data);
+            if data.len() % 2 == 1 {
+                body.push(0);
+            }
+        }
+        let mut bytes = b"RIFF".to_vec();
+        bytes.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        bytes.extend(body);
+        bytes
+    }
+
+    #[test]
+    fn test_read_chunks() {
+        let bytes = build_webp(&[
+            (b"VP8X", vec![0; 10]),
+            (b"VP8 ", vec![1, 2, 3]),
+            (b"XMP ", b"<x:xmpmeta/>".to_vec()),
+        ]);
+
+        let chunks = read_chunks(&mut Cursor::new(bytes), &[b"XMP "]).unwrap();
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].data, b"<x:xmpmeta/>");
+    }
+
+    #[test]
+    fn test_truncated_chunk_is_rejected() {
+        // Arrange: 長さが 4GiB 近くあると書かれた途中で終わるチャンク
+        let mut bytes = b"RIFF\0\0\0\0WEBPXMP ".to_vec();
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(b"<x:xmpmeta");
+
+        // Act
+        let result = read_chunks(&mut Cursor::new(bytes), &[b"XMP "]);
+
+        // Assert: 巨大な確保をせずにエラーを返す
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_replace_xmp_chunk_extended() {
+        let bytes = build_webp(&[
+            (b"VP8X", vec![0; 10]),
+            (b"VP8 ", vec![1, 2, 3]),
+            (b"XMP ", b"old".to_vec()),
+        ]);
+
+        let replaced = replace_xmp_chunk(&bytes, b"new").unwrap();
+        let chunks = read_chunks(&mut Cursor::new(&replaced), &[b"VP8X", b"XMP "]).unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].data[0] & FLAG_XMP, FLAG_XMP);
+        assert_eq!(chunks[1].data, b"new");
+    }
+
+    #[test]
+    fn test_replace_xmp_chunk_simple_lossless() {
+        // 幅 3 / 高さ 2 / アルファあり の VP8L ヘッダー
+        let bits: u32 = 2 | (1 << 14) | (1 << 28);
+        let mut vp8l = vec![0x2F];
+        vp8l.extend_from_slice(&bits.to_le_bytes());
+        let bytes = build_webp(&[(b"VP8L", vp8l)]);
+
+        let replaced = replace_xmp_chunk(&bytes, b"<x:xmpmeta/>").unwrap();
+        let chunks =
+            read_chunks(&mut Cursor::new(&replaced), &[b"VP8X", b"VP8L", b"XMP "]).unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(
+            chunks[0].data,
+            vec![FLAG_XMP | FLAG_ALPHA, 0, 0, 0, 2, 0, 0, 1, 0, 0]
+        );
+        assert_eq!(&chunks[1].fourcc, b"VP8L");
+        assert_eq!(chunks[2].data, b"<x:xmpmeta/>");
+        assert_eq!(
+            u32::from_le_bytes(replaced[4..8].try_into().unwrap()) as usize,
+            replaced.len() - 8
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+/// ファイルマネージャー共通のタグ属性（カンマ区切り）
+pub const XDG_TAGS: &str = "user.xdg.tags";
+
+/// picSort 独自属性の接頭辞（例: user.picsort.key）
+pub const PICSORT_PREFIX: &str = "user.picsort.";
+
+/// 拡張属性のタグ
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTags {
+    /// user.xdg.tags の値
+    pub tags: Vec<String>,
+    /// user.picsort.* の値（接頭辞を除いた名前）
+    pub attributes: BTreeMap<String, String>,
+}
+
+/// 拡張属性の書き込み結果（Undo時に元へ戻すための情報を含む）
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TagChange {
+    /// 実際に追加したタグ
+    pub tags: Vec<String>,
+    /// 設定した picSort 属性
+    pub attributes: BTreeMap<String, String>,
+    /// 上書きした picSort 属性の以前の値
+    pub previous: BTreeMap<String, String>,
+}
+
+impl FileTags {
+    /// picSort で仕分け済みか（user.picsort.key が設定されているか）
+    pub fn is_sorted(&self) -> bool {
+        self.attributes.contains_key("key")
+    }
+}
+
+#[cfg(unix)]
+fn get_attr(path: &Path, name: &str) -> io::Result<Option<Vec<u8>>> {
+    xattr::get(path, name)
+}
+
+#[cfg(unix)]
+fn set_attr(path: &Path, name: &str, value: &[u8]) -> io::Result<()> {
+    xattr::set(path, name, value)
+}
+
+#[cfg(unix)]
+fn remove_attr(path: &Path, name: &str) -> io::Result<()> {
+    // 存在しない属性の削除はエラーにしない
+    if xattr::get(path, name)?.is_none() {
+        return Ok(());
+    }
+    xattr::remove(path, name)
+}
+
+#[cfg(unix)]
+fn list_attrs(path: &Path) -> io::Result<Vec<String>> {
+    Ok(xattr::list(path)?
+        .map(|name| name.to_string_lossy().to_string())
+        .collect())
+}
+
+#[cfg(not(unix))]
+fn unsupported<T>() -> io::Result<T> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Extended attributes are not supported on this platform",
+    ))
+}
+
+#[cfg(not(unix))]
+fn get_attr(_path: &Path, _name: &str) -> io::Result<Option<Vec<u8>>> {
+    unsupported()
+}
+
+#[cfg(not(unix))]
+fn set_attr(_path: &Path, _name: &str, _value: &[u8]) -> io::Result<()> {
+    unsupported()
+}
+
+#[cfg(not(unix))]
+fn remove_attr(_path: &Path, _name: &str) -> io::Result<()> {
+    unsupported()
+}
+
+#[cfg(not(unix))]
+fn list_attrs(_path: &Path) -> io::Result<Vec<String>> {
+    unsupported()
+}
+
+/// カンマ区切りのタグを分割する
+fn parse_tags(value: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(value)
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// タグ名として使えない文字（区切りのカンマ）を除き、連続する空白を1つにまとめる
+fn sanitize_tag(tag: &str) -> String {
+    tag.replace(',', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn write_xdg_tags(path: &Path, tags: &[String]) -> io::Result<()> {
+    if tags.is_empty() {
+        remove_attr(path, XDG_TAGS)
+    } else {
+        set_attr(path, XDG_TAGS, tags.join(",").as_bytes())
+    }
+}
+
+/// 拡張属性のタグを読み込む
+pub fn read_tags(path: &Path) -> io::Result<FileTags> {
+    let tags = get_attr(path, XDG_TAGS)?
+        .map(|value| parse_tags(&value))
+        .unwrap_or_default();
+
+    let mut attributes = BTreeMap::new();
+    for name in list_attrs(path)? {
+        let Some(key) = name.strip_prefix(PICSORT_PREFIX) else {
+            continue;
+        };
+        if let Some(value) = get_attr(path, &name)? {
+            attributes.insert(key.to_string(), String::from_utf8_lossy(&value).to_string());
+        }
+    }
+
+    Ok(FileTags { tags, attributes })
+}
+
+/// タグを追加し、picSort 属性を設定する（実際に追加したタグ、設定した属性と上書き前の値を返す）
+pub fn write_tags(
+    path: &Path,
+    tags: &[String],
+    attributes: &BTreeMap<String, String>,
+) -> io::Result<TagChange> {
+    let mut current = get_attr(path, XDG_TAGS)?
+        .map(|value| parse_tags(&value))
+        .unwrap_or_default();
+
+    let mut added = Vec::new();
+    for tag in tags.iter().map(|t| sanitize_tag(t)) {
+        if !tag.is_empty() && !current.contains(&tag) {
+            current.push(tag.clone());
+            added.push(tag);
+        }
+    }
+    if !added.is_empty() {
+        write_xdg_tags(path, &current)?;
+    }
+
+    let mut previous = BTreeMap::new();
+    for (key, value) in attributes {
+        let name = format!("{}{}", PICSORT_PREFIX, key);
+        if let Some(old) = get_attr(path, &name)? {
+            previous.insert(key.clone(), String::from_utf8_lossy(&old).to_string());
+        }
+        set_attr(path, &name, value.as_bytes())?;
+    }
+
+    Ok(TagChange {
+        tags: added,
+        attributes: attributes.clone(),
+        previous,
+    })
+}
+
+/// タグと picSort 属性を取り除く（previous にある属性は以前の値に戻し、残った拡張属性を返す）
+pub fn remove_tags(
+    path: &Path,
+    tags: &[String],
+    attributes: &[String],
+    previous: &BTreeMap<String, String>,
+) -> io::Result<FileTags> {
+    let current = get_attr(path, XDG_TAGS)?
+        .map(|value| parse_tags(&value))
+        .unwrap_or_default();
+    let remaining: Vec<String> = current
+        .iter()
+        .filter(|t| !tags.contains(t))
+        .cloned()
+        .collect();
+    if remaining.len() != current.len() {
+        write_xdg_tags(path, &remaining)?;
+    }
+
+    for key in attributes {
+        let name = format!("{}{}", PICSORT_PREFIX, key);
+        match previous.get(key) {
+            Some(value) => set_attr(path, &name, value.as_bytes())?,
+            None => remove_attr(path, &name)?,
+        }
+    }
+
+    read_tags(path)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    fn attributes(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_write_read_remove_tags() {
+        // Arrange: 既存のタグを持つファイル
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("photo.jpg");
+        File::create(&path).unwrap();
+        if set_attr(&path, XDG_TAGS, b"favorite").is_err() {
+            // 拡張属性に対応していないファイルシステム
+            return;
+        }
+
+        // Act
+        let added = write_tags(
+            &path,
+            &["favorite".to_string(), "Aria, Starlight".to_string()],
+            &attributes(&[("key", "1"), ("destination", "/dest/1")]),
+        )
+        .unwrap();
+        let tags = read_tags(&path).unwrap();
+
+        // Assert: 既存のタグは重複しない
+        assert_eq!(added.tags, vec!["Aria Starlight"]);
+        assert_eq!(tags.tags, vec!["favorite", "Aria Starlight"]);
+        assert_eq!(tags.attributes.get("key").map(String::as_str), Some("1"));
+        assert!(tags.is_sorted());
+
+        // Act: 追加分を取り除く
+        let remaining = remove_tags(
+            &path,
+            &added.tags,
+            &["key".to_string(), "destination".to_string()],
+            &added.previous,
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(remaining.tags, vec!["favorite"]);
+        assert!(remaining.attributes.is_empty());
+        assert!(!remaining.is_sorted());
+    }
+
+    #[test]
+    fn test_remove_tags_restores_previous_attributes() {
+        // Arrange: 以前の仕分けで記録した属性を持つファイル
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("photo.jpg");
+        File::create(&path).unwrap();
+        if set_attr(&path, "user.picsort.key", b"2").is_err() {
+            return;
+        }
+        set_attr(&path, "user.picsort.source", b"/inbox").unwrap();
+
+        // Act: 上書きしてから元に戻す
+        let change = write_tags(
+            &path,
+            &[],
+            &attributes(&[("key", "1"), ("sorted_at", "100")]),
+        )
+        .unwrap();
+        let restored = remove_tags(
+            &path,
+            &change.tags,
+            &change.attributes.keys().cloned().collect::<Vec<_>>(),
+            &change.previous,
+        )
+        .unwrap();
+
+        // Assert: 上書きした値は戻り、新しく設定した属性は消える
+        assert_eq!(change.previous, attributes(&[("key", "2")]));
+        assert_eq!(
+            restored.attributes,
+            attributes(&[("key", "2"), ("source", "/inbox")])
+        );
+    }
+
+    #[test]
+    fn test_sanitize_tag() {
+        assert_eq!(sanitize_tag("Aria, Starlight"), "Aria Starlight");
+        assert_eq!(sanitize_tag(" a ,, b "), "a b");
+        assert_eq!(sanitize_tag(","), "");
+    }
+}
@@ -0,0 +1,910 @@
+use super::{detect_format, jpeg, open_container, png, webp, ContainerFormat};
+use crate::config::options::{ColorLabel, XmpLocation};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// JPEG APP1 に XMP を格納する際の識別子
+pub const JPEG_XMP_IDENTIFIER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+
+/// Dublin Core の名前空間
+const DC_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+
+/// XMP Basic の名前空間（xmp:Rating / xmp:Label）
+const XMP_NAMESPACE: &str = "http://ns.adobe.com/xap/1.0/";
+
+/// 新規作成する XMP パケット（dc:subject は空）
+const EMPTY_PACKET: &str = concat!(
+    "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n",
+    "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n",
+    " <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n",
+    "  <rdf:Description rdf:about=\"\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n",
+    "   <dc:subject><rdf:Bag></rdf:Bag></dc:subject>\n",
+    "  </rdf:Description>\n",
+    " </rdf:RDF>\n",
+    "</x:xmpmeta>\n",
+    "<?xpacket end=\"w\"?>",
+);
+
+/// 評価（0〜5、0 は未評価）と色ラベル
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingLabel {
+    pub rating: u8,
+    pub label: Option<ColorLabel>,
+}
+
+/// キーワード追加の結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubjectChange {
+    /// 実際に追加したキーワード（既存のものは含まない）
+    pub keywords: Vec<String>,
+    /// サイドカーファイルを新規作成したか
+    pub sidecar_created: bool,
+    /// 実際の書き込み先（埋め込みに対応していない形式はサイドカー）
+    pub location: XmpLocation,
+}
+
+/// XMP サイドカーファイルのパス（photo.jpg → photo.jpg.xmp）
+/// 拡張子を残し、同じフォルダの photo.jpg と photo.png が同じサイドカーを共有しないようにする
+pub fn sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".xmp");
+    PathBuf::from(name)
+}
+
+/// 画像に埋め込まれた XMP パケットを読み込む
+pub fn read_embedded_packet(path: &Path) -> std::io::Result<Option<String>> {
+    let (format, mut reader) = open_container(path)?;
+
+    let packet = match format {
+        ContainerFormat::Jpeg => {
+            let segments = jpeg::read_segments(&mut reader)?;
+            jpeg::find_app_payload(&segments, 0xE1, JPEG_XMP_IDENTIFIER)
+                .map(|data| String::from_utf8_lossy(data).to_string())
+        }
+        ContainerFormat::Png => png::read_text_chunks(&mut reader)?
+            .into_iter()
+            .find(|t| t.keyword == png::XMP_KEYWORD)
+            .map(|t| t.text),
+        ContainerFormat::WebP => webp::read_chunks(&mut reader, &[b"XMP "])?
+            .into_iter()
+            .find(|c| &c.fourcc == b"XMP ")
+            .map(|c| String::from_utf8_lossy(&c.data).to_string()),
+        ContainerFormat::Other => None,
+    };
+
+    Ok(packet)
+}
+
+/// サイドカーファイルの XMP パケットを読み込む
+pub fn read_sidecar_packet(path: &Path) -> Option<String> {
+    fs::read_to_string(sidecar_path(path)).ok()
+}
+
+/// XML エンティティを元の文字に戻す
+pub fn unescape_xml(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(amp) = rest.find('&') {
+        result.push_str(&rest[..amp]);
+        let after = &rest[amp..];
+        let Some(semi) = after.find(';') else {
+            result.push_str(after);
+            return result;
+        };
+        let entity = &after[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16)
+                .ok()
+                .and_then(char::from_u32),
+            _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+        match decoded {
+            Some(c) => {
+                result.push(c);
+                rest = &after[semi + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &after[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 文字列を XML テキストとしてエスケープする
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 要素の位置（開始タグ先頭〜終了タグ末尾、本体の範囲。空要素の場合は本体なし）
+struct ElementSpan {
+    start: usize,
+    end: usize,
+    body: Option<(usize, usize)>,
+}
+
+/// 指定プロパティ（例: "dc:subject"）の要素の位置を返す
+fn find_element(packet: &str, property: &str) -> Option<ElementSpan> {
+    let open = format!("<{}", property);
+    let close = format!("</{}>", property);
+
+    let mut search_from = 0;
+    while let Some(pos) = packet[search_from..].find(&open) {
+        let start = search_from + pos;
+        let name_end = start + open.len();
+        // "<dc:subjectX" のような別名の要素を除外
+        match packet[name_end..].chars().next() {
+            Some(c) if c == '>' || c == '/' || c.is_whitespace() => {}
+            _ => {
+                search_from = name_end;
+                continue;
+            }
+        }
+        let tag_end = name_end + packet[name_end..].find('>')?;
+        if packet[..tag_end].ends_with('/') {
+            return Some(ElementSpan {
+                start,
+                end: tag_end + 1,
+                body: None,
+            });
+        }
+        let body_start = tag_end + 1;
+        let body_end = body_start + packet[body_start..].find(&close)?;
+        return Some(ElementSpan {
+            start,
+            end: body_end + close.len(),
+            body: Some((body_start, body_end)),
+        });
+    }
+    None
+}
+
+/// 属性の位置（前の空白〜閉じ引用符、値の範囲）
+struct AttributeSpan {
+    start: usize,
+    end: usize,
+    value: (usize, usize),
+}
+
+/// 属性形式のプロパティ（例: xmp:Rating="3"）の位置を返す
+fn find_attribute(packet: &str, property: &str) -> Option<AttributeSpan> {
+    let pattern = format!("{}=", property);
+
+    let mut search_from = 0;
+    while let Some(pos) = packet[search_from..].find(&pattern) {
+        let name_start = search_from + pos;
+        let quote_pos = name_start + pattern.len();
+        search_from = quote_pos;
+        // "myxmp:Rating=" のような別名の属性を除外
+        if !packet[..name_start].ends_with(char::is_whitespace) {
+            continue;
+        }
+        let quote = match packet[quote_pos..].chars().next() {
+            Some(q @ ('"' | '\'')) => q,
+            _ => continue,
+        };
+        let value_start = quote_pos + 1;
+        let value_end = value_start + packet[value_start..].find(quote)?;
+        return Some(AttributeSpan {
+            start: packet[..name_start].trim_end().len(),
+            end: value_end + 1,
+            value: (value_start, value_end),
+        });
+    }
+    None
+}
+
+/// 指定プロパティ（例: "dc:subject"）の要素本体を返す
+fn find_element_body<'a>(packet: &'a str, property: &str) -> Option<&'a str> {
+    let span = find_element(packet, property)?;
+    Some(span.body.map_or("", |(start, end)| &packet[start..end]))
+}
+
+/// rdf:li 要素の位置（要素全体の範囲）と値を返す
+fn list_items(body: &str) -> Vec<(usize, usize, String)> {
+    let mut items = Vec::new();
+    let mut offset = 0;
+    while let Some(pos) = body[offset..].find("<rdf:li") {
+        let start = offset + pos;
+        let after = start + "<rdf:li".len();
+        let Some(tag_end) = body[after..].find('>').map(|i| after + i) else {
+            break;
+        };
+        if body[..tag_end].ends_with('/') {
+            offset = tag_end + 1;
+            continue;
+        }
+        let Some(end) = body[tag_end + 1..]
+            .find("</rdf:li>")
+            .map(|i| tag_end + 1 + i)
+        else {
+            break;
+        };
+        let value = unescape_xml(body[tag_end + 1..end].trim());
+        offset = end + "</rdf:li>".len();
+        items.push((start, offset, value));
+    }
+    items
+}
+
+/// rdf:Bag / rdf:Seq / rdf:Alt 形式のプロパティ値を取得する
+pub fn parse_list(packet: &str, property: &str) -> Vec<String> {
+    let Some(body) = find_element_body(packet, property) else {
+        return Vec::new();
+    };
+
+    list_items(body)
+        .into_iter()
+        .map(|(_, _, value)| value)
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// 単純値のプロパティを取得する（属性形式・要素形式のどちらにも対応）
+pub fn parse_property(packet: &str, property: &str) -> Option<String> {
+    if let Some(attribute) = find_attribute(packet, property) {
+        let (start, end) = attribute.value;
+        return Some(unescape_xml(&packet[start..end]));
+    }
+    let (start, end) = find_element(packet, property)?.body?;
+    Some(unescape_xml(packet[start..end].trim()))
+}
+
+/// xmp:Rating / xmp:Label を取得する（-1 の「除外」は未評価とみなす）
+pub fn parse_rating_label(packet: &str) -> (Option<u8>, Option<ColorLabel>) {
+    let rating = parse_property(packet, "xmp:Rating")
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .map(|value| value.clamp(0.0, 5.0).round() as u8);
+    let label = parse_property(packet, "xmp:Label").and_then(|value| ColorLabel::parse(&value));
+    (rating, label)
+}
+
+/// dc:subject キーワードを取得する
+pub fn parse_subjects(packet: &str) -> Vec<String> {
+    parse_list(packet, "dc:subject")
+}
+
+/// 画像とサイドカーの dc:subject キーワードを取得する（重複除去）
+pub fn read_subjects(path: &Path) -> Vec<String> {
+    let mut subjects = Vec::new();
+    let packets = [
+        read_embedded_packet(path).ok().flatten(),
+        read_sidecar_packet(path),
+    ];
+    for packet in packets.iter().flatten() {
+        for subject in parse_subjects(packet) {
+            if !subjects.contains(&subject) {
+                subjects.push(subject);
+            }
+        }
+    }
+    subjects
+}
+
+/// 画像とサイドカーの評価・色ラベルを取得する（サイドカーを優先）
+pub fn read_rating_label(path: &Path) -> RatingLabel {
+    let packets = [
+        read_sidecar_packet(path),
+        read_embedded_packet(path).ok().flatten(),
+    ];
+    let (mut rating, mut label) = (None, None);
+    for packet in packets.iter().flatten() {
+        let (r, l) = parse_rating_label(packet);
+        rating = rating.or(r);
+        label = label.or(l);
+    }
+    RatingLabel {
+        rating: rating.unwrap_or(0),
+        label,
+    }
+}
+
+/// 文字列の指定範囲を置き換える
+fn splice(text: &str, start: usize, end: usize, replacement: &str) -> String {
+    format!("{}{}{}", &text[..start], replacement, &text[end..])
+}
+
+/// rdf:RDF の末尾に rdf:Description を追加する
+fn append_description(
+    packet: &str,
+    prefix: &str,
+    namespace: &str,
+    body: &str,
+) -> io::Result<String> {
+    let close = packet
+        .rfind("</rdf:RDF>")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Unsupported XMP packet"))?;
+    let description = format!(
+        "<rdf:Description rdf:about=\"\" xmlns:{}=\"{}\">{}</rdf:Description>",
+        prefix, namespace, body
+    );
+    Ok(splice(packet, close, close, &description))
+}
+
+/// 単純値のプロパティを設定したパケットを返す（None の場合は取り除く）
+/// パケットがなければ新規に作成する
+pub fn set_property(
+    packet: Option<&str>,
+    property: &str,
+    namespace: &str,
+    value: Option<&str>,
+) -> io::Result<String> {
+    let packet = packet.unwrap_or(EMPTY_PACKET);
+
+    if let Some(attribute) = find_attribute(packet, property) {
+        return Ok(match value {
+            Some(value) => splice(
+                packet,
+                attribute.value.0,
+                attribute.value.1,
+                &escape_xml(value),
+            ),
+            None => splice(packet, attribute.start, attribute.end, ""),
+        });
+    }
+
+    let element = value.map(|value| format!("<{0}>{1}</{0}>", property, escape_xml(value)));
+    match (find_element(packet, property), element) {
+        (Some(span), element) => Ok(splice(
+            packet,
+            span.start,
+            span.end,
+            element.as_deref().unwrap_or_default(),
+        )),
+        (None, Some(element)) => {
+            let prefix = property.split(':').next().unwrap_or_default();
+            append_description(packet, prefix, namespace, &element)
+        }
+        (None, None) => Ok(packet.to_string()),
+    }
+}
+
+/// dc:subject にキーワードを追加したパケットと、実際に追加したキーワードを返す
+/// パケットがなければ新規に作成する
+pub fn add_subjects(
+    packet: Option<&str>,
+    keywords: &[String],
+) -> io::Result<(String, Vec<String>)> {
+    let packet = packet.unwrap_or(EMPTY_PACKET);
+    let existing = parse_subjects(packet);
+
+    let mut added: Vec<String> = Vec::new();
+    for keyword in keywords.iter().map(|k| k.trim()) {
+        if !keyword.is_empty()
+            && !existing.iter().any(|e| e == keyword)
+            && !added.iter().any(|a| a == keyword)
+        {
+            added.push(keyword.to_string());
+        }
+    }
+    if added.is_empty() {
+        return Ok((packet.to_string(), added));
+    }
+
+    let items: String = added
+        .iter()
+        .map(|k| format!("<rdf:li>{}</rdf:li>", escape_xml(k)))
+        .collect();
+    let bag = format!("<rdf:Bag>{}</rdf:Bag>", items);
+
+    let edited = match find_element(packet, "dc:subject") {
+        Some(ElementSpan {
+            body: Some((start, end)),
+            ..
+        }) => {
+            let body = &packet[start..end];
+            match ["</rdf:Bag>", "</rdf:Seq>"]
+                .iter()
+                .find_map(|close| body.rfind(close))
+            {
+                Some(close) => splice(packet, start + close, start + close, &items),
+                None => splice(packet, start, end, &bag),
+            }
+        }
+        Some(span) => splice(
+            packet,
+            span.start,
+            span.end,
+            &format!("<dc:subject>{}</dc:subject>", bag),
+        ),
+        None => {
+            // dc:subject がなければ rdf:Description を追加する
+            append_description(
+                packet,
+                "dc",
+                DC_NAMESPACE,
+                &format!("<dc:subject>{}</dc:subject>", bag),
+            )?
+        }
+    };
+    Ok((edited, added))
+}
+
+/// dc:subject から指定したキーワードを取り除いたパケットと、実際に取り除いたキーワードを返す
+pub fn remove_subjects(packet: &str, keywords: &[String]) -> (String, Vec<String>) {
+    let Some(ElementSpan {
+        body: Some((start, end)),
+        ..
+    }) = find_element(packet, "dc:subject")
+    else {
+        return (packet.to_string(), Vec::new());
+    };
+
+    let body = &packet[start..end];
+    let mut remaining: Vec<&String> = keywords.iter().collect();
+    let mut removed = Vec::new();
+    let mut edited = packet[..start].to_string();
+    let mut last = 0;
+
+    for (item_start, item_end, value) in list_items(body) {
+        if let Some(i) = remaining.iter().position(|k| **k == value) {
+            remaining.remove(i);
+            edited.push_str(&body[last..item_start]);
+            last = item_end;
+            removed.push(value);
+        }
+    }
+    edited.push_str(&body[last..]);
+    edited.push_str(&packet[end..]);
+    (edited, removed)
+}
+
+/// 一時ファイルに書き込んでから置き換える
+fn write_replacing(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    fs::write(&temp_path, bytes)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
+/// 画像に XMP パケットを埋め込む（JPEG APP1 / PNG iTXt / WebP XMP チャンク）
+pub fn write_embedded_packet(path: &Path, packet: &str) -> io::Result<()> {
+    let bytes = fs::read(path)?;
+    let updated = match detect_format(&bytes) {
+        ContainerFormat::Jpeg => {
+            jpeg::replace_app_segment(&bytes, 0xE1, JPEG_XMP_IDENTIFIER, packet.as_bytes())?
+        }
+        ContainerFormat::Png => png::replace_itxt(&bytes, png::XMP_KEYWORD, packet)?,
+        ContainerFormat::WebP => webp::replace_xmp_chunk(&bytes, packet.as_bytes())?,
+        ContainerFormat::Other => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Embedded XMP is not supported for this format",
+            ))
+        }
+    };
+    write_replacing(path, &updated)
+}
+
+/// XMP の dc:subject にキーワードを追加する（既にあるキーワードは追加しない）
+/// 埋め込みに対応していない形式はサイドカーに書き込む
+pub fn add_keywords(
+    path: &Path,
+    keywords: &[String],
+    location: XmpLocation,
+) -> io::Result<SubjectChange> {
+    let location = writable_location(path, location);
+    let existing = match location {
+        XmpLocation::Sidecar => read_sidecar_packet(path),
+        XmpLocation::Embedded => read_embedded_packet(path)?,
+    };
+    let (packet, added) = add_subjects(existing.as_deref(), keywords)?;
+
+    if !added.is_empty() {
+        match location {
+            XmpLocation::Sidecar => fs::write(sidecar_path(path), &packet)?,
+            XmpLocation::Embedded => write_embedded_packet(path, &packet)?,
+        }
+    }
+
+    Ok(SubjectChange {
+        sidecar_created: location == XmpLocation::Sidecar
+            && existing.is_none()
+            && !added.is_empty(),
+        keywords: added,
+        location,
+    })
+}
+
+/// add_keywords で追加したキーワードを取り除く
+/// 新規作成したサイドカーが空に戻った場合は削除する
+pub fn remove_keywords(
+    path: &Path,
+    keywords: &[String],
+    location: XmpLocation,
+    sidecar_created: bool,
+) -> io::Result<Vec<String>> {
+    let existing = match location {
+        XmpLocation::Sidecar => read_sidecar_packet(path),
+        XmpLocation::Embedded => read_embedded_packet(path)?,
+    };
+    let Some(existing) = existing else {
+        return Ok(Vec::new());
+    };
+    let (packet, removed) = remove_subjects(&existing, keywords);
+    if removed.is_empty() {
+        return Ok(removed);
+    }
+
+    match location {
+        XmpLocation::Sidecar if sidecar_created && packet == EMPTY_PACKET => {
+            fs::remove_file(sidecar_path(path))?
+        }
+        XmpLocation::Sidecar => fs::write(sidecar_path(path), &packet)?,
+        XmpLocation::Embedded => write_embedded_packet(path, &packet)?,
+    }
+    Ok(removed)
+}
+
+/// XMP を埋め込める形式か（JPEG / PNG / WebP）
+fn supports_embedded(path: &Path) -> bool {
+    open_container(path).is_ok_and(|(format, _)| format != ContainerFormat::Other)
+}
+
+/// 実際に書き込める場所（埋め込みに対応していない形式はサイドカー）
+fn writable_location(path: &Path, location: XmpLocation) -> XmpLocation {
+    match location {
+        XmpLocation::Embedded if !supports_embedded(path) => XmpLocation::Sidecar,
+        location => location,
+    }
+}
+
+/// 単純値のプロパティを書き込み、実際の書き込み先を返す
+/// 埋め込みに対応していない形式はサイドカーに書き込む
+fn write_property(
+    path: &Path,
+    property: &str,
+    namespace: &str,
+    value: Option<&str>,
+    location: XmpLocation,
+) -> io::Result<XmpLocation> {
+    let location = writable_location(path, location);
+    let sidecar = read_sidecar_packet(path);
+
+    if location == XmpLocation::Embedded {
+        let existing = read_embedded_packet(path)?;
+        if existing.is_some() || value.is_some() {
+            let packet = set_property(existing.as_deref(), property, namespace, value)?;
+            if existing.as_deref() != Some(packet.as_str()) {
+                write_embedded_packet(path, &packet)?;
+            }
+        }
+        // サイドカーの値が優先されるため、残っていれば取り除く
+        let Some(sidecar) = sidecar.filter(|p| parse_property(p, property).is_some()) else {
+            return Ok(location);
+        };
+        let packet = set_property(Some(&sidecar), property, namespace, None)?;
+        fs::write(sidecar_path(path), packet)?;
+        return Ok(location);
+    }
+
+    if sidecar.is_some() || value.is_some() {
+        let packet = set_property(sidecar.as_deref(), property, namespace, value)?;
+        if sidecar.as_deref() != Some(packet.as_str()) {
+            fs::write(sidecar_path(path), packet)?;
+        }
+    }
+    Ok(location)
+}
+
+/// xmp:Rating を書き込む（0 は未評価としてプロパティを取り除く）
+pub fn write_rating(path: &Path, rating: u8, location: XmpLocation) -> io::Result<XmpLocation> {
+    let value = (rating > 0).then(|| rating.min(5).to_string());
+    write_property(
+        path,
+        "xmp:Rating",
+        XMP_NAMESPACE,
+        value.as_deref(),
+        location,
+    )
+}
+
+/// xmp:Label を書き込む（None の場合はプロパティを取り除く）
+pub fn write_label(
+    path: &Path,
+    label: Option<ColorLabel>,
+    location: XmpLocation,
+) -> io::Result<XmpLocation> {
+    write_property(
+        path,
+        "xmp:Label",
+        XMP_NAMESPACE,
+        label.map(ColorLabel::as_str),
+        location,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    const PACKET: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/">
+   <dc:subject>
+    <rdf:Bag>
+     <rdf:li>Aria Starlight</rdf:li>
+     <rdf:li>Tom &amp; Jerry</rdf:li>
+    </rdf:Bag>
+   </dc:subject>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>"#;
+
+    #[test]
+    fn test_parse_subjects() {
+        assert_eq!(
+            parse_subjects(PACKET),
+            vec!["Aria Starlight", "Tom & Jerry"]
+        );
+        assert!(parse_subjects("<x:xmpmeta/>").is_empty());
+        assert!(parse_subjects("<dc:subject/>").is_empty());
+    }
+
+    #[test]
+    fn test_unescape_xml() {
+        assert_eq!(unescape_xml("a &lt;b&gt; &#x6851; &#26716;"), "a <b> 桑 桜");
+        assert_eq!(unescape_xml("R&D"), "R&D");
+        assert_eq!(unescape_xml(&escape_xml("<\"&\">")), "<\"&\">");
+    }
+
+    #[test]
+    fn test_read_subjects_from_jpeg_and_sidecar() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("photo.jpg");
+
+        let mut app1 = JPEG_XMP_IDENTIFIER.to_vec();
+        app1.extend_from_slice(PACKET.as_bytes());
+        let bytes = jpeg::tests::build_jpeg(&[(0xE1, &app1)]);
+        File::create(&path).unwrap().write_all(&bytes).unwrap();
+
+        let sidecar = PACKET.replace("Aria Starlight", "Leon Blaze");
+        fs::write(sidecar_path(&path), sidecar).unwrap();
+
+        let subjects = read_subjects(&path);
+
+        assert_eq!(
+            subjects,
+            vec!["Aria Starlight", "Tom & Jerry", "Leon Blaze"]
+        );
+    }
+
+    #[test]
+    fn test_read_embedded_packet_png() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("image.png");
+        let bytes = png::tests::build_png(&[(b"iTXt", png::tests::itxt(png::XMP_KEYWORD, PACKET))]);
+        fs::write(&path, bytes).unwrap();
+
+        let packet = read_embedded_packet(&path).unwrap().unwrap();
+
+        assert_eq!(parse_subjects(&packet).len(), 2);
+    }
+
+    fn keywords(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_add_and_remove_subjects() {
+        // Act: 既存のキーワードは追加しない
+        let (packet, added) = add_subjects(
+            Some(PACKET),
+            &keywords(&["Leon Blaze", "Aria Starlight", "<月影>"]),
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(added, vec!["Leon Blaze", "<月影>"]);
+        assert_eq!(
+            parse_subjects(&packet),
+            vec!["Aria Starlight", "Tom & Jerry", "Leon Blaze", "<月影>"]
+        );
+
+        // Act: 追加したキーワードだけを取り除く
+        let (restored, removed) = remove_subjects(&packet, &added);
+
+        // Assert: 元のパケットに戻る
+        assert_eq!(removed, added);
+        assert_eq!(restored, PACKET);
+    }
+
+    #[test]
+    fn test_add_subjects_without_dc_subject() {
+        let packet = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description rdf:about=""/></rdf:RDF></x:xmpmeta>"#;
+
+        let (edited, _) = add_subjects(Some(packet), &keywords(&["Aria"])).unwrap();
+        let (from_empty, _) = add_subjects(Some("<dc:subject/>"), &keywords(&["Aria"])).unwrap();
+
+        assert_eq!(parse_subjects(&edited), vec!["Aria"]);
+        assert!(edited.contains(DC_NAMESPACE));
+        assert_eq!(parse_subjects(&from_empty), vec!["Aria"]);
+        assert!(add_subjects(Some("<x:xmpmeta/>"), &keywords(&["Aria"])).is_err());
+    }
+
+    #[test]
+    fn test_keywords_in_sidecar_roundtrip() {
+        // Arrange
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("photo.gif");
+        fs::write(&path, b"GIF89a").unwrap();
+
+        // Act: サイドカーを新規作成してキーワードを追加
+        let change = add_keywords(&path, &keywords(&["Aria"]), XmpLocation::Sidecar).unwrap();
+
+        // Assert
+        assert!(change.sidecar_created);
+        assert_eq!(read_subjects(&path), vec!["Aria"]);
+
+        // Act: 元に戻すとサイドカーも削除される
+        let removed = remove_keywords(&path, &change.keywords, XmpLocation::Sidecar, true).unwrap();
+
+        // Assert
+        assert_eq!(removed, vec!["Aria"]);
+        assert!(!sidecar_path(&path).exists());
+    }
+
+    #[test]
+    fn test_sidecars_are_per_file() {
+        // Arrange: 拡張子だけが異なるファイル
+        let dir = tempdir().unwrap();
+        let gif = dir.path().join("photo.gif");
+        let bmp = dir.path().join("photo.bmp");
+        fs::write(&gif, b"GIF89a").unwrap();
+        fs::write(&bmp, b"BM").unwrap();
+
+        // Act
+        add_keywords(&gif, &keywords(&["Aria"]), XmpLocation::Sidecar).unwrap();
+        let change = add_keywords(&bmp, &keywords(&["Leon"]), XmpLocation::Sidecar).unwrap();
+        remove_keywords(&bmp, &change.keywords, XmpLocation::Sidecar, true).unwrap();
+
+        // Assert: 一方のサイドカーを削除しても、もう一方のキーワードは残る
+        assert_eq!(sidecar_path(&gif), dir.path().join("photo.gif.xmp"));
+        assert!(change.sidecar_created);
+        assert_eq!(read_subjects(&gif), vec!["Aria"]);
+        assert!(read_subjects(&bmp).is_empty());
+    }
+
+    #[test]
+    fn test_keywords_embedded_in_jpeg() {
+        // Arrange: 既存の XMP を持つ JPEG
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("photo.jpg");
+        let mut app1 = JPEG_XMP_IDENTIFIER.to_vec();
+        app1.extend_from_slice(PACKET.as_bytes());
+        fs::write(&path, jpeg::tests::build_jpeg(&[(0xE1, &app1)])).unwrap();
+
+        // Act
+        let change =
+            add_keywords(&path, &keywords(&["Leon Blaze"]), XmpLocation::Embedded).unwrap();
+        let tagged = read_subjects(&path);
+        remove_keywords(&path, &change.keywords, XmpLocation::Embedded, false).unwrap();
+
+        // Assert: 追加分だけが取り除かれる
+        assert!(!change.sidecar_created);
+        assert_eq!(change.location, XmpLocation::Embedded);
+        assert_eq!(tagged, vec!["Aria Starlight", "Tom & Jerry", "Leon Blaze"]);
+        assert_eq!(read_subjects(&path), vec!["Aria Starlight", "Tom & Jerry"]);
+        assert!(!sidecar_path(&path).exists());
+    }
+
+    #[test]
+    fn test_embedded_unsupported_format_uses_sidecar() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("photo.gif");
+        fs::write(&path, b"GIF89a").unwrap();
+
+        let change = add_keywords(&path, &keywords(&["Aria"]), XmpLocation::Embedded).unwrap();
+
+        assert_eq!(change.location, XmpLocation::Sidecar);
+        assert!(change.sidecar_created);
+        assert_eq!(read_subjects(&path), vec!["Aria"]);
+        assert_eq!(fs::read(&path).unwrap(), b"GIF89a");
+    }
+
+    #[test]
+    fn test_parse_rating_label_attribute_and_element() {
+        let attribute = r#"<rdf:Description rdf:about="" xmp:Rating="4" xmp:Label='Red'/>"#;
+        let element = "<xmp:Rating>-1</xmp:Rating><xmp:Label>purple</xmp:Label>";
+
+        assert_eq!(
+            parse_rating_label(attribute),
+            (Some(4), Some(ColorLabel::Red))
+        );
+        assert_eq!(
+            parse_rating_label(element),
+            (Some(0), Some(ColorLabel::Purple))
+        );
+        assert_eq!(parse_rating_label(r#"<d myxmp:Rating="3"/>"#), (None, None));
+    }
+
+    #[test]
+    fn test_set_property() {
+        let attribute = r#"<rdf:Description rdf:about="" xmp:Rating="4"/>"#;
+
+        // 属性形式は値だけを置き換え、取り除く場合は属性ごと削除する
+        let updated =
+            set_property(Some(attribute), "xmp:Rating", XMP_NAMESPACE, Some("2")).unwrap();
+        let removed = set_property(Some(attribute), "xmp:Rating", XMP_NAMESPACE, None).unwrap();
+        assert_eq!(updated, r#"<rdf:Description rdf:about="" xmp:Rating="2"/>"#);
+        assert_eq!(removed, r#"<rdf:Description rdf:about=""/>"#);
+
+        // プロパティがなければ rdf:Description を追加する
+        let added = set_property(Some(PACKET), "xmp:Label", XMP_NAMESPACE, Some("Green")).unwrap();
+        assert!(added.contains(XMP_NAMESPACE));
+        assert_eq!(
+            parse_property(&added, "xmp:Label").as_deref(),
+            Some("Green")
+        );
+        assert_eq!(parse_subjects(&added), parse_subjects(PACKET));
+        let cleared = set_property(Some(&added), "xmp:Label", XMP_NAMESPACE, None).unwrap();
+        assert_eq!(parse_property(&cleared, "xmp:Label"), None);
+    }
+
+    #[test]
+    fn test_rating_label_roundtrip() {
+        // Arrange: サイドカーに評価を持つ JPEG と、埋め込みに対応しない GIF
+        let dir = tempdir().unwrap();
+        let jpeg_path = dir.path().join("photo.jpg");
+        fs::write(&jpeg_path, jpeg::tests::build_jpeg(&[])).unwrap();
+        fs::write(
+            sidecar_path(&jpeg_path),
+            PACKET.replace("rdf:about=\"\"", "rdf:about=\"\" xmp:Rating=\"1\""),
+        )
+        .unwrap();
+        let gif_path = dir.path().join("anim.gif");
+        fs::write(&gif_path, b"GIF89a").unwrap();
+
+        // Act
+        let jpeg_location = write_rating(&jpeg_path, 5, XmpLocation::Embedded).unwrap();
+        write_label(&jpeg_path, Some(ColorLabel::Blue), XmpLocation::Embedded).unwrap();
+        let gif_location = write_rating(&gif_path, 3, XmpLocation::Embedded).unwrap();
+
+        // Assert: サイドカーの古い評価は取り除かれ、埋め込みの値が読める
+        assert_eq!(jpeg_location, XmpLocation::Embedded);
+        assert_eq!(
+            read_rating_label(&jpeg_path),
+            RatingLabel {
+                rating: 5,
+                label: Some(ColorLabel::Blue)
+            }
+        );
+        let sidecar = read_sidecar_packet(&jpeg_path).unwrap();
+        assert_eq!(parse_property(&sidecar, "xmp:Rating"), None);
+        assert_eq!(parse_subjects(&sidecar).len(), 2);
+
+        // GIF はサイドカーに書き込まれる
+        assert_eq!(gif_location, XmpLocation::Sidecar);
+        assert_eq!(read_rating_label(&gif_path).rating, 3);
+
+        // Act: 0 は未評価として取り除く
+        write_rating(&gif_path, 0, XmpLocation::Sidecar).unwrap();
+
+        // Assert
+        assert_eq!(read_rating_label(&gif_path), RatingLabel::default());
+    }
+}
@@ -0,0 +1,259 @@
+use crate::config::options::AnimationLimits;
+use image::codecs::gif::{GifDecoder, GifEncoder, Repeat};
+use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::{AnimationDecoder, Delay, DynamicImage, Frame, Frames, ImageFormat};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use tokio_util::sync::CancellationToken;
+
+/// 表示時間が極端に短いフレームはブラウザと同じく 100ms として扱う
+const MIN_DELAY_MS: u64 = 20;
+const DEFAULT_DELAY_MS: u64 = 100;
+
+/// GIF 書き出し時の減色の速さ（1〜30、大きいほど速く粗い）
+const GIF_SPEED: i32 = 10;
+
+const CANCELLED: &str = "Animated preview cancelled";
+
+/// 動く画像の情報
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationInfo {
+    /// 元画像のフレーム数
+    pub frame_count: u32,
+    /// 元画像の1ループの長さ（ミリ秒）
+    pub duration_ms: u64,
+    /// プレビューに含めたフレーム数
+    pub preview_frame_count: u32,
+    /// プレビューの1ループの長さ（ミリ秒）
+    pub preview_duration_ms: u64,
+}
+
+/// 動く画像になりうる形式か（GIF / APNG / WebP）
+pub fn is_animation_candidate(path: &Path) -> bool {
+    matches!(
+        ImageFormat::from_path(path),
+        Ok(ImageFormat::Gif | ImageFormat::Png | ImageFormat::WebP)
+    )
+}
+
+/// フレームを読み込む（APNG・WebP はアニメーションでなければ None）
+fn open_frames(path: &Path) -> Result<Option<Frames<'static>>, String> {
+    let open = || {
+        File::open(path)
+            .map(BufReader::new)
+            .map_err(|e| format!("Failed to open image: {}", e))
+    };
+    let decode_error = |e: image::ImageError| format!("Failed to decode animation: {}", e);
+
+    let frames = match ImageFormat::from_path(path) {
+        Ok(ImageFormat::Gif) => GifDecoder::new(open()?)
+            .map_err(decode_error)?
+            .into_frames(),
+        Ok(ImageFormat::Png) => {
+            let decoder = PngDecoder::new(open()?).map_err(decode_error)?;
+            if !decoder.is_apng().map_err(decode_error)? {
+                return Ok(None);
+            }
+            decoder.apng().map_err(decode_error)?.into_frames()
+        }
+        Ok(ImageFormat::WebP) => {
+            let decoder = WebPDecoder::new(open()?).map_err(decode_error)?;
+            if !decoder.has_animation() {
+                return Ok(None);
+            }
+            decoder.into_frames()
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(frames))
+}
+
+/// フレームの表示時間（ミリ秒）
+fn delay_ms(delay: Delay) -> u64 {
+    let (numerator, denominator) = delay.numer_denom_ms();
+    let ms = numerator as u64 / denominator.max(1) as u64;
+    if ms < MIN_DELAY_MS {
+        DEFAULT_DELAY_MS
+    } else {
+        ms
+    }
+}
+
+/// 縮小した動くプレビューを GIF で書き出す
+/// 元画像の全フレームを数えて情報を返す。2フレーム以上ない場合は書き出さずに None を返す
+/// token がキャンセルされた場合はフレームの間で中断する
+pub fn write_preview(
+    src_path: &Path,
+    preview_path: &Path,
+    size: u32,
+    limits: &AnimationLimits,
+    token: &CancellationToken,
+) -> Result<Option<AnimationInfo>, String> {
+    let Some(frames) = open_frames(src_path)? else {
+        return Ok(None);
+    };
+
+    let mut preview = Vec::new();
+    let mut info = AnimationInfo {
+        frame_count: 0,
+        duration_ms: 0,
+        preview_frame_count: 0,
+        preview_duration_ms: 0,
+    };
+    for frame in frames {
+        if token.is_cancelled() {
+            return Err(CANCELLED.to_string());
+        }
+        let frame = frame.map_err(|e| format!("Failed to decode animation: {}", e))?;
+        let delay = delay_ms(frame.delay());
+        info.frame_count += 1;
+        info.duration_ms += delay;
+
+        if info.preview_frame_count < limits.max_frames.max(1)
+            && info.preview_duration_ms < limits.max_duration_ms
+        {
+            let small = DynamicImage::from(frame.into_buffer())
+                .thumbnail(size, size)
+                .to_rgba8();
+            preview.push(Frame::from_parts(
+                small,
+                0,
+                0,
+                Delay::from_numer_denom_ms(delay as u32, 1),
+            ));
+            info.preview_frame_count += 1;
+            info.preview_duration_ms += delay;
+        }
+    }
+    if info.frame_count < 2 {
+        return Ok(None);
+    }
+
+    let file = File::create(preview_path).map_err(|e| format!("Failed to save preview: {}", e))?;
+    let mut encoder = GifEncoder::new_with_speed(BufWriter::new(file), GIF_SPEED);
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .and_then(|_| encoder.encode_frames(preview))
+        .map_err(|e| format!("Failed to save preview: {}", e))?;
+    Ok(Some(info))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+    use tempfile::tempdir;
+
+    /// テスト用の動く GIF（各フレーム 50ms）
+    fn write_gif(path: &Path, frames: u32) {
+        let file = File::create(path).unwrap();
+        let mut encoder = GifEncoder::new(file);
+        encoder
+            .encode_frames((0..frames).map(|i| {
+                Frame::from_parts(
+                    RgbaImage::from_pixel(40, 20, Rgba([(i * 10) as u8, 0, 0, 255])),
+                    0,
+                    0,
+                    Delay::from_numer_denom_ms(50, 1),
+                )
+            }))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_write_preview_limits_frames() {
+        // Arrange
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("anim.gif");
+        write_gif(&src, 10);
+        let preview = dir.path().join("preview.gif");
+        let limits = AnimationLimits {
+            max_frames: 4,
+            max_duration_ms: 1000,
+        };
+        let token = CancellationToken::new();
+
+        // Act
+        let info = write_preview(&src, &preview, 10, &limits, &token)
+            .unwrap()
+            .unwrap();
+
+        // Assert: 元画像の情報は全フレーム分、プレビューは上限まで
+        assert_eq!(info.frame_count, 10);
+        assert_eq!(info.duration_ms, 500);
+        assert_eq!(info.preview_frame_count, 4);
+        assert_eq!(info.preview_duration_ms, 200);
+        let frames = GifDecoder::new(BufReader::new(File::open(&preview).unwrap()))
+            .unwrap()
+            .into_frames()
+            .collect_frames()
+            .unwrap();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0].buffer().dimensions(), (10, 5));
+    }
+
+    #[test]
+    fn test_write_preview_limits_duration() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("anim.gif");
+        write_gif(&src, 10);
+        let limits = AnimationLimits {
+            max_frames: 60,
+            max_duration_ms: 120,
+        };
+        let token = CancellationToken::new();
+
+        let info = write_preview(&src, &dir.path().join("p.gif"), 10, &limits, &token)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(info.preview_frame_count, 3);
+    }
+
+    #[test]
+    fn test_still_images_have_no_preview() {
+        // Arrange: 1フレームの GIF と通常の PNG
+        let dir = tempdir().unwrap();
+        let gif = dir.path().join("still.gif");
+        write_gif(&gif, 1);
+        let png = dir.path().join("still.png");
+        RgbaImage::new(4, 4).save(&png).unwrap();
+        let preview = dir.path().join("preview.gif");
+        let limits = AnimationLimits::default();
+        let token = CancellationToken::new();
+
+        // Act & Assert
+        assert_eq!(
+            write_preview(&gif, &preview, 10, &limits, &token).unwrap(),
+            None
+        );
+        assert_eq!(
+            write_preview(&png, &preview, 10, &limits, &token).unwrap(),
+            None
+        );
+        assert!(!preview.exists());
+        assert!(!is_animation_candidate(Path::new("/a.jpg")));
+    }
+
+    #[test]
+    fn test_write_preview_stops_when_cancelled() {
+        // Arrange: キャンセル済みのトークン
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("anim.gif");
+        write_gif(&src, 10);
+        let preview = dir.path().join("preview.gif");
+        let token = CancellationToken::new();
+        token.cancel();
+
+        // Act
+        let result = write_preview(&src, &preview, 10, &AnimationLimits::default(), &token);
+
+        // Assert: デコードを中断し、プレビューを書き出さない
+        assert_eq!(result, Err(CANCELLED.to_string()));
+        assert!(!preview.exists());
+    }
+}
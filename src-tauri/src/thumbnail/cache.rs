@@ -0,0 +1,453 @@
+use super::content::{ContentIndex, SourceStamp, PATHS_FILE};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+/// アクセス記録を保存するファイル名（キャッシュディレクトリ内）
+pub const INDEX_FILE: &str = "index.json";
+
+/// 既定の容量上限（MB）
+pub const DEFAULT_MAX_SIZE_MB: u64 = 500;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// この回数だけ更新したらアクセス記録を保存する
+const SAVE_INTERVAL: u32 = 32;
+
+/// キャッシュ済みサムネイルの記録
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    bytes: u64,
+    /// 最終アクセス日時（UNIX秒）
+    accessed_at: u64,
+    /// アクセス順（大きいほど最近）
+    tick: u64,
+}
+
+/// index.json の内容
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct CacheIndex {
+    #[serde(default)]
+    max_bytes: Option<u64>,
+    #[serde(default)]
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// キャッシュの統計
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub entries: usize,
+    pub total_bytes: u64,
+    pub max_bytes: u64,
+    /// 起動後のキャッシュヒット数
+    pub hits: u64,
+    /// 起動後のキャッシュミス数
+    pub misses: u64,
+    /// ヒット率（参照がなければ 0）
+    pub hit_rate: f64,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// サムネイルキャッシュ（容量上限を超えたら最近使われていない順に削除する）
+/// ファイルの更新日時ではなく、参照のたびに記録したアクセス順で判断する
+pub struct ThumbnailCache {
+    dir: PathBuf,
+    entries: HashMap<String, CacheEntry>,
+    /// アクセス順（tick → ファイル名）
+    order: BTreeMap<u64, String>,
+    total_bytes: u64,
+    max_bytes: u64,
+    next_tick: u64,
+    hits: u64,
+    misses: u64,
+    unsaved: u32,
+    /// パス → 内容のハッシュの索引
+    paths: ContentIndex,
+}
+
+impl ThumbnailCache {
+    /// キャッシュディレクトリを開く
+    /// アクセス記録にないファイル（以前のバージョンで生成したもの）は最も古いものとして扱う
+    pub fn open(dir: &Path) -> Self {
+        let index: CacheIndex = fs::read_to_string(dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+
+        let mut cache = Self {
+            dir: dir.to_path_buf(),
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            total_bytes: 0,
+            max_bytes: index
+                .max_bytes
+                .unwrap_or(DEFAULT_MAX_SIZE_MB * BYTES_PER_MB),
+            next_tick: 0,
+            hits: 0,
+            misses: 0,
+            unsaved: 0,
+            paths: ContentIndex::open(dir),
+        };
+
+        let mut tracked = Vec::new();
+        let mut untracked = Vec::new();
+        for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name == INDEX_FILE || name == PATHS_FILE || name.starts_with('.') {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            match index.entries.get(&name) {
+                Some(recorded) => tracked.push((
+                    name,
+                    CacheEntry {
+                        bytes: metadata.len(),
+                        ..recorded.clone()
+                    },
+                )),
+                None => {
+                    let modified = metadata
+                        .modified()
+                        .ok()
+                        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                        .map(|d| d.as_secs())
+                        .unwrap_or(0);
+                    untracked.push((modified, name, metadata.len()));
+                }
+            }
+        }
+
+        // 記録にないファイルを更新日時の古い順に置き、その後に記録済みのものをアクセス順に並べる
+        untracked.sort();
+        tracked.sort_by_key(|(_, entry)| entry.tick);
+        for (accessed_at, name, bytes) in untracked {
+            let tick = cache.next_tick;
+            cache.track(
+                name,
+                CacheEntry {
+                    bytes,
+                    accessed_at,
+                    tick,
+                },
+            );
+        }
+        for (name, entry) in tracked {
+            let tick = cache.next_tick;
+            cache.track(name, CacheEntry { tick, ..entry });
+        }
+
+        debug!(
+            "Opened thumbnail cache: {:?} ({} entries, {} bytes)",
+            dir,
+            cache.entries.len(),
+            cache.total_bytes
+        );
+        cache
+    }
+
+    fn track(&mut self, name: String, entry: CacheEntry) {
+        self.untrack(&name);
+        self.next_tick = self.next_tick.max(entry.tick + 1);
+        self.total_bytes += entry.bytes;
+        self.order.insert(entry.tick, name.clone());
+        self.entries.insert(name, entry);
+    }
+
+    fn untrack(&mut self, name: &str) -> Option<CacheEntry> {
+        let entry = self.entries.remove(name)?;
+        self.order.remove(&entry.tick);
+        self.total_bytes -= entry.bytes;
+        Some(entry)
+    }
+
+    fn mark_dirty(&mut self) {
+        self.unsaved += 1;
+        if self.unsaved >= SAVE_INTERVAL {
+            if let Err(e) = self.save() {
+                warn!("Failed to save thumbnail cache index: {}", e);
+            }
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 候補のうち記録のあるものを参照する（出力形式が元画像によって変わる場合に使う）
+    pub fn lookup_any(&mut self, names: &[String]) -> Option<PathBuf> {
+        match names.iter().find(|name| self.entries.contains_key(name.as_str())) {
+            Some(name) => self.lookup(name),
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// キャッシュを参照する（あればアクセスを記録してパスを返す）
+    pub fn lookup(&mut self, name: &str) -> Option<PathBuf> {
+        let path = self.dir.join(name);
+        let Some(entry) = self.entries.get(name).cloned() else {
+            self.misses += 1;
+            return None;
+        };
+        if !path.exists() {
+            self.untrack(name);
+            self.misses += 1;
+            return None;
+        }
+
+        self.hits += 1;
+        let tick = self.next_tick;
+        self.track(
+            name.to_string(),
+            CacheEntry {
+                accessed_at: now_secs(),
+                tick,
+                ..entry
+            },
+        );
+        self.mark_dirty();
+        Some(path)
+    }
+
+    /// 生成したサムネイルを登録し、容量上限を超えた分を古い順に削除する（削除したファイル名を返す）
+    pub fn insert(&mut self, name: &str) -> io::Result<Vec<String>> {
+        let bytes = fs::metadata(self.dir.join(name))?.len();
+        let tick = self.next_tick;
+        self.track(
+            name.to_string(),
+            CacheEntry {
+                bytes,
+                accessed_at: now_secs(),
+                tick,
+            },
+        );
+        let evicted = self.evict(Some(name));
+        self.mark_dirty();
+        Ok(evicted)
+    }
+
+    /// エントリとファイルを削除する
+    pub fn remove(&mut self, name: &str) {
+        if self.untrack(name).is_some() {
+            let _ = fs::remove_file(self.dir.join(name));
+            self.mark_dirty();
+        }
+    }
+
+    /// 容量上限を変更する
+    pub fn set_max_size_mb(&mut self, max_size_mb: u64) {
+        self.max_bytes = max_size_mb * BYTES_PER_MB;
+        self.mark_dirty();
+    }
+
+    /// 容量上限を超えた分を最近使われていない順に削除する（keep は削除しない）
+    pub fn evict(&mut self, keep: Option<&str>) -> Vec<String> {
+        let mut evicted = Vec::new();
+        while self.total_bytes > self.max_bytes {
+            let Some(name) = self
+                .order
+                .values()
+                .find(|name| Some(name.as_str()) != keep)
+                .cloned()
+            else {
+                break;
+            };
+            self.untrack(&name);
+            if let Err(e) = fs::remove_file(self.dir.join(&name)) {
+                if e.kind() != io::ErrorKind::NotFound {
+                    warn!("Failed to remove thumbnail {}: {}", name, e);
+                }
+            }
+            evicted.push(name);
+        }
+        if !evicted.is_empty() {
+            debug!("Evicted {} thumbnails over the size budget", evicted.len());
+        }
+        evicted
+    }
+
+    /// 指定期間アクセスのないサムネイルを削除する（削除した件数を返す）
+    pub fn remove_older_than(&mut self, max_age: Duration) -> usize {
+        let threshold = now_secs().saturating_sub(max_age.as_secs());
+        let expired: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.accessed_at < threshold)
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &expired {
+            self.remove(name);
+        }
+        expired.len()
+    }
+
+    /// 記録済みの内容のハッシュを返す（サイズと更新日時が変わっていれば None）
+    pub fn known_hash(&self, path: &str, stamp: SourceStamp) -> Option<String> {
+        self.paths.get(path, stamp).map(str::to_string)
+    }
+
+    /// 内容のハッシュを記録する
+    pub fn record_hash(&mut self, path: &str, stamp: SourceStamp, hash: String) {
+        self.paths.insert(path, stamp, hash);
+        self.mark_dirty();
+    }
+
+    /// ファイル移動を索引に反映する
+    pub fn record_move(&mut self, from: &str, to: &str) {
+        if self.paths.rename(from, to) {
+            self.mark_dirty();
+        }
+    }
+
+    /// 存在しなくなったファイルの索引を削除する
+    pub fn prune_paths(&mut self) -> usize {
+        let pruned = self.paths.prune();
+        if pruned > 0 {
+            self.mark_dirty();
+        }
+        pruned
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let lookups = self.hits + self.misses;
+        CacheStats {
+            entries: self.entries.len(),
+            total_bytes: self.total_bytes,
+            max_bytes: self.max_bytes,
+            hits: self.hits,
+            misses: self.misses,
+            hit_rate: if lookups == 0 {
+                0.0
+            } else {
+                self.hits as f64 / lookups as f64
+            },
+        }
+    }
+
+    /// アクセス記録を保存する
+    pub fn save(&mut self) -> io::Result<()> {
+        let index = CacheIndex {
+            max_bytes: Some(self.max_bytes),
+            entries: self.entries.clone(),
+        };
+        let json = serde_json::to_string(&index)?;
+        let temp_path = self.dir.join(format!(".{}.tmp", INDEX_FILE));
+        fs::write(&temp_path, json)?;
+        fs::rename(&temp_path, self.dir.join(INDEX_FILE))?;
+        self.paths.save()?;
+        self.unsaved = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_thumbnail(dir: &Path, name: &str, bytes: usize) {
+        fs::write(dir.join(name), vec![0u8; bytes]).unwrap();
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        // Arrange: 上限 1MB に 400KB のサムネイルを 2 つ
+        let dir = tempdir().unwrap();
+        let mut cache = ThumbnailCache::open(dir.path());
+        cache.set_max_size_mb(1);
+        for name in ["a.jpg", "b.jpg"] {
+            write_thumbnail(dir.path(), name, 400 * 1024);
+            cache.insert(name).unwrap();
+        }
+
+        // Act: a を参照してから 3 つ目を追加
+        assert!(cache.lookup("a.jpg").is_some());
+        write_thumbnail(dir.path(), "c.jpg", 400 * 1024);
+        let evicted = cache.insert("c.jpg").unwrap();
+
+        // Assert: 先に追加した a ではなく、参照されていない b が削除される
+        assert_eq!(evicted, vec!["b.jpg"]);
+        assert!(!dir.path().join("b.jpg").exists());
+        assert!(dir.path().join("a.jpg").exists());
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.total_bytes, 800 * 1024);
+    }
+
+    #[test]
+    fn test_stats_hit_rate() {
+        let dir = tempdir().unwrap();
+        let mut cache = ThumbnailCache::open(dir.path());
+        write_thumbnail(dir.path(), "a.jpg", 10);
+        cache.insert("a.jpg").unwrap();
+
+        cache.lookup("a.jpg");
+        cache.lookup("a.jpg");
+        cache.lookup("a.jpg");
+        cache.lookup("missing.jpg");
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (3, 1));
+        assert!((stats.hit_rate - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_access_order_persists_across_reopen() {
+        // Arrange: 記録にない古いファイルと、記録済みのファイル
+        let dir = tempdir().unwrap();
+        write_thumbnail(dir.path(), "legacy.jpg", 10);
+        let mut cache = ThumbnailCache::open(dir.path());
+        write_thumbnail(dir.path(), "b.jpg", 10);
+        write_thumbnail(dir.path(), "a.jpg", 10);
+        cache.insert("b.jpg").unwrap();
+        cache.insert("a.jpg").unwrap();
+        cache.lookup("b.jpg");
+        cache.save().unwrap();
+
+        // Act: 開き直してすべて削除する
+        let mut reopened = ThumbnailCache::open(dir.path());
+        reopened.max_bytes = 0;
+        let evicted = reopened.evict(None);
+
+        // Assert: 記録にないファイルから削除され、アクセス順が保たれる
+        assert_eq!(evicted, vec!["legacy.jpg", "a.jpg", "b.jpg"]);
+        assert_eq!(reopened.stats().total_bytes, 0);
+    }
+
+    #[test]
+    fn test_remove_older_than() {
+        let dir = tempdir().unwrap();
+        let mut cache = ThumbnailCache::open(dir.path());
+        write_thumbnail(dir.path(), "old.jpg", 10);
+        write_thumbnail(dir.path(), "new.jpg", 10);
+        cache.insert("old.jpg").unwrap();
+        cache.insert("new.jpg").unwrap();
+        cache.entries.get_mut("old.jpg").unwrap().accessed_at = 0;
+
+        let removed = cache.remove_older_than(Duration::from_secs(24 * 60 * 60));
+
+        assert_eq!(removed, 1);
+        assert!(!dir.path().join("old.jpg").exists());
+        assert!(dir.path().join("new.jpg").exists());
+    }
+}
@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// パスと内容のハッシュの対応を保存するファイル名（キャッシュディレクトリ内）
+pub const PATHS_FILE: &str = "paths.json";
+
+/// 先頭・中央・末尾から読み込む量
+const SAMPLE_BYTES: u64 = 64 * 1024;
+
+/// 元ファイルの識別情報（内容を読まずに変更を検出する）
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceStamp {
+    pub len: u64,
+    /// 更新日時（UNIXエポックからのナノ秒）
+    pub modified: u64,
+}
+
+impl SourceStamp {
+    pub fn of(path: &Path) -> io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Ok(Self {
+            len: metadata.len(),
+            modified,
+        })
+    }
+}
+
+/// 内容のハッシュ（ファイルサイズ + 先頭・中央・末尾 64KiB）
+/// 全体を読まずに、移動やコピーされた同じファイルを識別する
+pub fn partial_hash(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    let mut hasher = Sha256::new();
+    hasher.update(len.to_le_bytes());
+
+    let mut offsets = vec![0];
+    if len > SAMPLE_BYTES {
+        offsets.push((len - SAMPLE_BYTES) / 2);
+        offsets.push(len - SAMPLE_BYTES);
+    }
+    let mut buffer = Vec::with_capacity(SAMPLE_BYTES as usize);
+    for offset in offsets {
+        file.seek(SeekFrom::Start(offset))?;
+        buffer.clear();
+        (&mut file).take(SAMPLE_BYTES).read_to_end(&mut buffer)?;
+        hasher.update(&buffer);
+    }
+
+    Ok(hex::encode(&hasher.finalize()[..16]))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct IndexEntry {
+    hash: String,
+    stamp: SourceStamp,
+}
+
+/// パス → 内容のハッシュの索引
+/// ファイル移動時に付け替えることで、移動先でもハッシュを求め直さずにサムネイルを再利用できる
+pub struct ContentIndex {
+    file: PathBuf,
+    entries: HashMap<String, IndexEntry>,
+}
+
+impl ContentIndex {
+    pub fn open(dir: &Path) -> Self {
+        let file = dir.join(PATHS_FILE);
+        let entries = fs::read_to_string(&file)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self { file, entries }
+    }
+
+    /// 記録済みのハッシュを返す（サイズと更新日時が変わっていれば None）
+    pub fn get(&self, path: &str, stamp: SourceStamp) -> Option<&str> {
+        self.entries
+            .get(path)
+            .filter(|entry| entry.stamp == stamp)
+            .map(|entry| entry.hash.as_str())
+    }
+
+    pub fn insert(&mut self, path: &str, stamp: SourceStamp, hash: String) {
+        self.entries
+            .insert(path.to_string(), IndexEntry { hash, stamp });
+    }
+
+    /// ファイル移動に合わせて記録を付け替える
+    pub fn rename(&mut self, from: &str, to: &str) -> bool {
+        match self.entries.remove(from) {
+            Some(entry) => {
+                self.entries.insert(to.to_string(), entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 存在しなくなったファイルの記録を削除する（削除した件数を返す）
+    pub fn prune(&mut self) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|path, _| Path::new(path).exists());
+        before - self.entries.len()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let json = serde_json::to_string(&self.entries)?;
+        let file_name = self
+            .file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let temp_path = self.file.with_file_name(format!(".{}.tmp", file_name));
+        fs::write(&temp_path, json)?;
+        fs::rename(&temp_path, &self.file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_partial_hash_identifies_content() {
+        // Arrange: 同じ内容のファイルと、中央だけが異なるファイル
+        let dir = tempdir().unwrap();
+        let content: Vec<u8> = (0..300_000u32).map(|i| (i % 251) as u8).collect();
+        let mut changed = content.clone();
+        changed[150_000] ^= 0xFF;
+        fs::write(dir.path().join("a.jpg"), &content).unwrap();
+        fs::write(dir.path().join("b.jpg"), &content).unwrap();
+        fs::write(dir.path().join("c.jpg"), &changed).unwrap();
+        fs::write(dir.path().join("small.jpg"), b"tiny").unwrap();
+
+        // Act
+        let a = partial_hash(&dir.path().join("a.jpg")).unwrap();
+        let b = partial_hash(&dir.path().join("b.jpg")).unwrap();
+        let c = partial_hash(&dir.path().join("c.jpg")).unwrap();
+        let small = partial_hash(&dir.path().join("small.jpg")).unwrap();
+
+        // Assert
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(a, small);
+        assert_eq!(a.len(), 32);
+    }
+
+    #[test]
+    fn test_content_index_rename_and_persist() {
+        // Arrange
+        let dir = tempdir().unwrap();
+        let stamp = SourceStamp {
+            len: 10,
+            modified: 1,
+        };
+        let mut index = ContentIndex::open(dir.path());
+        index.insert("/src/a.jpg", stamp, "hash".to_string());
+
+        // Act: 移動に合わせて付け替えてから保存・再読み込み
+        assert!(index.rename("/src/a.jpg", "/dest/a.jpg"));
+        assert!(!index.rename("/src/missing.jpg", "/dest/missing.jpg"));
+        index.save().unwrap();
+        let reopened = ContentIndex::open(dir.path());
+
+        // Assert: 更新日時が変わった場合は使わない
+        assert_eq!(reopened.get("/dest/a.jpg", stamp), Some("hash"));
+        assert_eq!(reopened.get("/src/a.jpg", stamp), None);
+        assert_eq!(
+            reopened.get(
+                "/dest/a.jpg",
+                SourceStamp {
+                    modified: 2,
+                    ..stamp
+                }
+            ),
+            None
+        );
+    }
+}
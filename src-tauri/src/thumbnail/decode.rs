@@ -0,0 +1,406 @@
+use crate::metadata::exif::read_exif;
+use image::{DynamicImage, GrayImage, ImageFormat, RgbImage};
+use jpeg_decoder::{CodingProcess, PixelFormat};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// 1ファイル内で辿る IFD の上限（壊れたファイルでの無限ループ対策）
+const MAX_IFDS: usize = 32;
+
+/// 1エントリから読む値の上限（ストリップの多い画像データ本体は対象外のため）
+const MAX_VALUES: usize = 64;
+
+/// TIFF タグ
+const TAG_COMPRESSION: u16 = 0x0103;
+const TAG_STRIP_OFFSETS: u16 = 0x0111;
+const TAG_STRIP_BYTE_COUNTS: u16 = 0x0117;
+const TAG_SUB_IFDS: u16 = 0x014A;
+const TAG_JPEG_OFFSET: u16 = 0x0201;
+const TAG_JPEG_LENGTH: u16 = 0x0202;
+
+/// 埋め込みプレビュー（ファイル内の JPEG データ）
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Preview {
+    offset: u64,
+    len: u64,
+    width: u32,
+    height: u32,
+}
+
+/// サムネイル用に画像を読み込む
+/// 1. 要求サイズ以上の埋め込みプレビュー（EXIF サムネイル・RAW のプレビュー JPEG）があれば使う
+/// 2. JPEG は DCT の段階で縮小しながらデコードする
+/// 3. それ以外は全体をデコードする（デコードできない RAW は最大のプレビューを使う）
+pub fn open_for_thumbnail(path: &Path, size: u32) -> Result<DynamicImage, String> {
+    let previews = embedded_previews(path);
+    if let Some(preview) = previews
+        .iter()
+        .filter(|p| p.width.max(p.height) >= size)
+        .min_by_key(|p| p.width as u64 * p.height as u64)
+    {
+        if let Some(img) = decode_preview(path, preview, size) {
+            return Ok(img);
+        }
+    }
+
+    if ImageFormat::from_path(path).ok() == Some(ImageFormat::Jpeg) {
+        let scaled = File::open(path)
+            .ok()
+            .and_then(|file| decode_jpeg_scaled(BufReader::new(file), size));
+        if let Some(img) = scaled {
+            return Ok(img);
+        }
+    }
+
+    image::open(path).or_else(|e| {
+        previews
+            .iter()
+            .max_by_key(|p| p.width as u64 * p.height as u64)
+            .and_then(|preview| decode_preview(path, preview, size))
+            .ok_or_else(|| format!("Failed to open image: {}", e))
+    })
+}
+
+/// JPEG を DCT の段階で縮小しながらデコードする（1/2, 1/4, 1/8）
+/// 長辺が size 以上になる範囲で最も小さくする。対応していない形式は None
+fn decode_jpeg_scaled(reader: impl Read, size: u32) -> Option<DynamicImage> {
+    let mut decoder = jpeg_decoder::Decoder::new(reader);
+    decoder.read_info().ok()?;
+    let info = decoder.info()?;
+    if info.coding_process == CodingProcess::Lossless {
+        return None;
+    }
+
+    let requested = size.min(u16::MAX as u32) as u16;
+    let (width, height) = decoder.scale(requested, requested).ok()?;
+    let pixels = decoder.decode().ok()?;
+    let (width, height) = (width as u32, height as u32);
+    match info.pixel_format {
+        PixelFormat::L8 => GrayImage::from_raw(width, height, pixels).map(DynamicImage::from),
+        PixelFormat::RGB24 => RgbImage::from_raw(width, height, pixels).map(DynamicImage::from),
+        // CMYK などは image クレートでデコードする
+        _ => None,
+    }
+}
+
+/// 埋め込みプレビューを縮小デコードする
+fn decode_preview(path: &Path, preview: &Preview, size: u32) -> Option<DynamicImage> {
+    let bytes = match ImageFormat::from_path(path).ok() {
+        // JPEG の EXIF サムネイルは APP1 内の位置を指す
+        Some(ImageFormat::Jpeg) => {
+            let exif = read_exif(path)?;
+            let start = preview.offset as usize;
+            exif.buf()
+                .get(start..start + preview.len as usize)?
+                .to_vec()
+        }
+        _ => {
+            let mut file = File::open(path).ok()?;
+            file.seek(SeekFrom::Start(preview.offset)).ok()?;
+            let mut bytes = Vec::new();
+            file.take(preview.len).read_to_end(&mut bytes).ok()?;
+            bytes
+        }
+    };
+    decode_jpeg_scaled(Cursor::new(bytes), size)
+}
+
+/// ファイルに埋め込まれたプレビュー JPEG を列挙する
+/// JPEG は EXIF の IFD1 を、TIFF 形式の RAW（DNG / CR2 / NEF / ARW など）は全 IFD とサブ IFD を探す
+fn embedded_previews(path: &Path) -> Vec<Preview> {
+    let found = match ImageFormat::from_path(path).ok() {
+        Some(ImageFormat::Jpeg) => match read_exif(path) {
+            Some(exif) => find_previews(Cursor::new(exif.buf())),
+            None => Ok(Vec::new()),
+        },
+        _ => File::open(path).and_then(|file| find_previews(BufReader::new(file))),
+    };
+    found.unwrap_or_default()
+}
+
+/// TIFF 構造を辿ってプレビュー JPEG を探す（非可逆 JPEG として読めるものだけを返す）
+fn find_previews<R: Read + Seek>(reader: R) -> io::Result<Vec<Preview>> {
+    let Some(mut tiff) = TiffReader::new(reader)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut pending = vec![tiff.first_ifd];
+    let mut visited = HashSet::new();
+    let mut locations = Vec::new();
+    while let Some(offset) = pending.pop() {
+        if offset == 0 || visited.len() >= MAX_IFDS || !visited.insert(offset) {
+            continue;
+        }
+        let Ok((entries, next)) = tiff.ifd(offset) else {
+            continue;
+        };
+        pending.push(next);
+
+        let value = |tag: u16| entries.iter().find(|e| e.tag == tag).map(|e| &e.values);
+        let first = |tag: u16| value(tag).and_then(|v| v.first().copied());
+        if let (Some(offset), Some(len)) = (first(TAG_JPEG_OFFSET), first(TAG_JPEG_LENGTH)) {
+            locations.push((offset, len));
+        }
+        // JPEG 圧縮の1ストリップ画像（6: 旧 JPEG, 7: JPEG）
+        if matches!(first(TAG_COMPRESSION), Some(6 | 7)) {
+            if let (Some([offset]), Some([len])) = (
+                value(TAG_STRIP_OFFSETS).map(Vec::as_slice),
+                value(TAG_STRIP_BYTE_COUNTS).map(Vec::as_slice),
+            ) {
+                locations.push((*offset, *len));
+            }
+        }
+        if let Some(sub_ifds) = value(TAG_SUB_IFDS) {
+            pending.extend(sub_ifds);
+        }
+    }
+
+    let mut previews = Vec::new();
+    for (offset, len) in locations {
+        if len == 0 || previews.iter().any(|p: &Preview| p.offset == offset as u64) {
+            continue;
+        }
+        let (offset, len) = (offset as u64, len as u64);
+        let reader = &mut tiff.reader;
+        reader.seek(SeekFrom::Start(offset))?;
+        let mut decoder = jpeg_decoder::Decoder::new(reader.take(len));
+        if decoder.read_info().is_err() {
+            continue;
+        }
+        if let Some(info) = decoder.info() {
+            if info.coding_process != CodingProcess::Lossless {
+                previews.push(Preview {
+                    offset,
+                    len,
+                    width: info.width as u32,
+                    height: info.height as u32,
+                });
+            }
+        }
+    }
+    Ok(previews)
+}
+
+struct IfdEntry {
+    tag: u16,
+    values: Vec<u32>,
+}
+
+/// IFD を読むための最小限の TIFF リーダー
+struct TiffReader<R> {
+    reader: R,
+    big_endian: bool,
+    first_ifd: u32,
+}
+
+impl<R: Read + Seek> TiffReader<R> {
+    /// TIFF ヘッダーを読む（TIFF でない場合は None）
+    /// ORF ("IIRO") と RW2 ("IIU\0") も TIFF と同じ構造として扱う
+    fn new(mut reader: R) -> io::Result<Option<Self>> {
+        let mut header = [0u8; 8];
+        if reader.read_exact(&mut header).is_err() {
+            return Ok(None);
+        }
+        let big_endian = match &header[..4] {
+            b"II*\0" | b"IIRO" | b"IIU\0" => false,
+            b"MM\0*" => true,
+            _ => return Ok(None),
+        };
+        let mut tiff = Self {
+            reader,
+            big_endian,
+            first_ifd: 0,
+        };
+        tiff.first_ifd = tiff.u32(&header[4..8]);
+        Ok(Some(tiff))
+    }
+
+    fn u16(&self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    fn u32(&self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+
+    /// IFD のエントリ（SHORT / LONG / IFD 型のみ）と次の IFD の位置を読む
+    fn ifd(&mut self, offset: u32) -> io::Result<(Vec<IfdEntry>, u32)> {
+        self.reader.seek(SeekFrom::Start(offset as u64))?;
+        let mut count = [0u8; 2];
+        self.reader.read_exact(&mut count)?;
+        let count = self.u16(&count) as usize;
+        let mut raw = vec![0u8; count * 12 + 4];
+        self.reader.read_exact(&mut raw)?;
+        let next = self.u32(&raw[count * 12..]);
+
+        let mut entries = Vec::new();
+        for entry in raw[..count * 12].chunks_exact(12) {
+            let tag = self.u16(&entry[0..2]);
+            let width = match self.u16(&entry[2..4]) {
+                3 => 2,
+                4 | 13 => 4,
+                _ => continue,
+            };
+            // 値が4バイトを超える場合は別の位置に格納されている（大きすぎるものは読まない）
+            let count = (self.u32(&entry[4..8]) as usize).min(MAX_VALUES);
+            let data = if count * width <= 4 {
+                entry[8..8 + count * width].to_vec()
+            } else {
+                let position = self.reader.stream_position()?;
+                self.reader
+                    .seek(SeekFrom::Start(self.u32(&entry[8..12]) as u64))?;
+                let mut data = vec![0u8; count * width];
+                self.reader.read_exact(&mut data)?;
+                self.reader.seek(SeekFrom::Start(position))?;
+                data
+            };
+            let values = data
+                .chunks_exact(width)
+                .map(|v| {
+                    if width == 2 {
+                        self.u16(v) as u32
+                    } else {
+                        self.u32(v)
+                    }
+                })
+                .collect();
+            entries.push(IfdEntry { tag, values });
+        }
+        Ok((entries, next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::GenericImageView;
+    use tempfile::tempdir;
+
+    fn encode_jpeg(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Cursor::new(Vec::new());
+        RgbImage::new(width, height)
+            .write_to(&mut bytes, ImageFormat::Jpeg)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    /// テスト用の IFD（リトルエンディアン、値はすべて LONG）
+    fn build_ifd(entries: &[(u16, u32)], next: u32) -> Vec<u8> {
+        let mut ifd = (entries.len() as u16).to_le_bytes().to_vec();
+        for (tag, value) in entries {
+            ifd.extend_from_slice(&tag.to_le_bytes());
+            ifd.extend_from_slice(&4u16.to_le_bytes());
+            ifd.extend_from_slice(&1u32.to_le_bytes());
+            ifd.extend_from_slice(&value.to_le_bytes());
+        }
+        ifd.extend_from_slice(&next.to_le_bytes());
+        ifd
+    }
+
+    fn ifd_len(entries: usize) -> u32 {
+        2 + 12 * entries as u32 + 4
+    }
+
+    #[test]
+    fn test_raw_preview_from_sub_ifd() {
+        // Arrange: IFD0 に小さいプレビュー、サブ IFD に大きいプレビューを持つ RAW
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("photo.dng");
+        let small = encode_jpeg(32, 24);
+        let large = encode_jpeg(128, 96);
+        let sub_ifd = 8 + ifd_len(3);
+        let small_at = sub_ifd + ifd_len(3);
+        let large_at = small_at + small.len() as u32;
+
+        let mut bytes = b"II*\0".to_vec();
+        bytes.extend_from_slice(&8u32.to_le_bytes());
+        bytes.extend(build_ifd(
+            &[
+                (TAG_SUB_IFDS, sub_ifd),
+                (TAG_JPEG_OFFSET, small_at),
+                (TAG_JPEG_LENGTH, small.len() as u32),
+            ],
+            0,
+        ));
+        bytes.extend(build_ifd(
+            &[
+                (TAG_COMPRESSION, 7),
+                (TAG_STRIP_OFFSETS, large_at),
+                (TAG_STRIP_BYTE_COUNTS, large.len() as u32),
+            ],
+            0,
+        ));
+        bytes.extend(small);
+        bytes.extend(large);
+        std::fs::write(&path, bytes).unwrap();
+
+        // Act
+        let fits = open_for_thumbnail(&path, 100).unwrap();
+        let too_large = open_for_thumbnail(&path, 512).unwrap();
+
+        // Assert: 要求サイズ以上の最小のプレビューを使い、足りなければ最大のものを使う
+        assert_eq!(embedded_previews(&path).len(), 2);
+        assert_eq!(fits.dimensions(), (128, 96));
+        assert_eq!(too_large.dimensions(), (128, 96));
+    }
+
+    #[test]
+    fn test_jpeg_exif_thumbnail() {
+        // Arrange: IFD1 に 200x150 のサムネイルを持つ 1000x750 の JPEG
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("photo.jpg");
+        let thumbnail = encode_jpeg(200, 150);
+        let ifd1 = 8 + ifd_len(1);
+        let data_at = ifd1 + ifd_len(2);
+
+        let mut app1 = b"Exif\0\0II*\0".to_vec();
+        app1.extend_from_slice(&8u32.to_le_bytes());
+        app1.extend(build_ifd(&[(0x0112, 1)], ifd1));
+        app1.extend(build_ifd(
+            &[
+                (TAG_JPEG_OFFSET, data_at),
+                (TAG_JPEG_LENGTH, thumbnail.len() as u32),
+            ],
+            0,
+        ));
+        app1.extend(thumbnail);
+        let jpeg = encode_jpeg(1000, 750);
+        let mut bytes = jpeg[..2].to_vec();
+        bytes.extend_from_slice(&[0xFF, 0xE1]);
+        bytes.extend_from_slice(&(app1.len() as u16 + 2).to_be_bytes());
+        bytes.extend(app1);
+        bytes.extend_from_slice(&jpeg[2..]);
+        std::fs::write(&path, bytes).unwrap();
+
+        // Act
+        let preview = open_for_thumbnail(&path, 150).unwrap();
+        let decoded = open_for_thumbnail(&path, 300).unwrap();
+
+        // Assert: サムネイルが小さすぎる場合は本体を DCT 縮小でデコードする
+        assert_eq!(preview.dimensions(), (200, 150));
+        assert_eq!(decoded.dimensions(), (500, 375));
+    }
+
+    #[test]
+    fn test_decode_jpeg_scaled() {
+        let jpeg = encode_jpeg(800, 600);
+
+        let img = decode_jpeg_scaled(Cursor::new(&jpeg), 100).unwrap();
+        assert_eq!(img.dimensions(), (100, 75));
+
+        let img = decode_jpeg_scaled(Cursor::new(&jpeg), 1000).unwrap();
+        assert_eq!(img.dimensions(), (800, 600));
+    }
+}
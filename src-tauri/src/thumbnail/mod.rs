@@ -0,0 +1,5 @@
+pub mod animation;
+pub mod cache;
+pub mod content;
+pub mod decode;
+pub mod queue;
@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::oneshot;
+
+/// サムネイル生成の優先度（高いものから順に処理する）
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum Priority {
+    /// 先読み
+    Low,
+    #[default]
+    Normal,
+    /// 画面に表示中
+    Visible,
+}
+
+/// 優先度の付け替え結果
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReprioritizeSummary {
+    /// 表示中として先頭に移した件数
+    pub promoted: usize,
+    /// 不要になり取り消した件数
+    pub dropped: usize,
+}
+
+struct Job {
+    id: u64,
+    path: String,
+    video: bool,
+    priority: Priority,
+    grant: oneshot::Sender<Slot>,
+}
+
+struct QueueState {
+    /// 同時に実行できる数（画像, 動画）
+    limits: (usize, usize),
+    running: (usize, usize),
+    waiting: Vec<Job>,
+    next_id: u64,
+}
+
+impl QueueState {
+    fn has_capacity(&self, video: bool) -> bool {
+        if video {
+            self.running.1 < self.limits.1
+        } else {
+            self.running.0 < self.limits.0
+        }
+    }
+
+    fn running_mut(&mut self, video: bool) -> &mut usize {
+        if video {
+            &mut self.running.1
+        } else {
+            &mut self.running.0
+        }
+    }
+}
+
+/// サムネイル生成の待ち行列
+/// 空きができると、優先度が高く（同じなら先に登録された）ジョブから実行枠を割り当てる
+#[derive(Clone)]
+pub struct JobQueue {
+    state: Arc<Mutex<QueueState>>,
+}
+
+/// 実行枠（破棄すると次のジョブに枠を渡す）
+pub struct Slot {
+    queue: Option<JobQueue>,
+    video: bool,
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.release(self.video);
+        }
+    }
+}
+
+impl JobQueue {
+    pub fn new(image_limit: usize, video_limit: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(QueueState {
+                limits: (image_limit.max(1), video_limit.max(1)),
+                running: (0, 0),
+                waiting: Vec::new(),
+                next_id: 0,
+            })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// ジョブを登録する（実行枠が割り当てられると受信できる。取り消された場合はエラー）
+    pub fn enqueue(&self, path: &str, video: bool, priority: Priority) -> oneshot::Receiver<Slot> {
+        let (grant, receiver) = oneshot::channel();
+        let mut state = self.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.waiting.push(Job {
+            id,
+            path: path.to_string(),
+            video,
+            priority,
+            grant,
+        });
+        self.dispatch(&mut state);
+        receiver
+    }
+
+    /// 表示中のパスのジョブを先頭に移す
+    /// drop_hidden の場合は、それ以外の待機中ジョブを取り消す
+    pub fn reprioritize(&self, visible_paths: &[String], drop_hidden: bool) -> ReprioritizeSummary {
+        let visible: HashSet<&str> = visible_paths.iter().map(String::as_str).collect();
+        let mut state = self.lock();
+        let mut summary = ReprioritizeSummary::default();
+
+        state.waiting.retain_mut(|job| {
+            if visible.contains(job.path.as_str()) {
+                job.priority = Priority::Visible;
+                summary.promoted += 1;
+                true
+            } else if drop_hidden {
+                summary.dropped += 1;
+                false
+            } else {
+                // 表示外になったものは通常の優先度に戻す
+                job.priority = job.priority.min(Priority::Normal);
+                true
+            }
+        });
+        summary
+    }
+
+    /// 待機中のジョブ数
+    #[cfg(test)]
+    fn waiting(&self) -> usize {
+        self.lock().waiting.len()
+    }
+
+    fn release(&self, video: bool) {
+        let mut state = self.lock();
+        *state.running_mut(video) -= 1;
+        self.dispatch(&mut state);
+    }
+
+    fn dispatch(&self, state: &mut QueueState) {
+        // 待つのをやめたジョブは除く
+        state.waiting.retain(|job| !job.grant.is_closed());
+
+        loop {
+            let next = state
+                .waiting
+                .iter()
+                .enumerate()
+                .filter(|(_, job)| state.has_capacity(job.video))
+                .max_by_key(|(_, job)| (job.priority, Reverse(job.id)))
+                .map(|(index, _)| index);
+            let Some(index) = next else {
+                break;
+            };
+
+            let job = state.waiting.swap_remove(index);
+            *state.running_mut(job.video) += 1;
+            let slot = Slot {
+                queue: Some(self.clone()),
+                video: job.video,
+            };
+            if let Err(mut slot) = job.grant.send(slot) {
+                // 受信側がいなくなっていた場合は枠を戻す（ロック中のため Drop では戻さない）
+                slot.queue = None;
+                *state.running_mut(job.video) -= 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn granted(receiver: &mut oneshot::Receiver<Slot>) -> Option<Slot> {
+        receiver.try_recv().ok()
+    }
+
+    #[test]
+    fn test_visible_jobs_run_first() {
+        // Arrange: 実行枠1つを使用中にして、3件を待たせる
+        let queue = JobQueue::new(1, 1);
+        let mut first = queue.enqueue("/a.jpg", false, Priority::Normal);
+        let running = granted(&mut first).unwrap();
+        let mut low = queue.enqueue("/low.jpg", false, Priority::Low);
+        let mut old = queue.enqueue("/old.jpg", false, Priority::Normal);
+        let mut shown = queue.enqueue("/shown.jpg", false, Priority::Normal);
+
+        // Act: 表示中のものを先頭に移してから枠を空ける
+        let summary = queue.reprioritize(&["/shown.jpg".to_string()], false);
+        drop(running);
+
+        // Assert: 表示中 → 通常（登録順）→ 先読み の順
+        assert_eq!(
+            summary,
+            ReprioritizeSummary {
+                promoted: 1,
+                dropped: 0
+            }
+        );
+        let slot = granted(&mut shown).unwrap();
+        assert!(granted(&mut old).is_none());
+        drop(slot);
+        let slot = granted(&mut old).unwrap();
+        assert!(granted(&mut low).is_none());
+        drop(slot);
+        assert!(granted(&mut low).is_some());
+    }
+
+    #[test]
+    fn test_drop_hidden_jobs() {
+        // Arrange
+        let queue = JobQueue::new(1, 1);
+        let mut first = queue.enqueue("/a.jpg", false, Priority::Normal);
+        let running = granted(&mut first).unwrap();
+        let mut hidden = queue.enqueue("/hidden.jpg", false, Priority::Visible);
+        let mut shown = queue.enqueue("/shown.jpg", false, Priority::Normal);
+
+        // Act
+        let summary = queue.reprioritize(&["/shown.jpg".to_string()], true);
+
+        // Assert: 取り消されたジョブは待機側でエラーになる
+        assert_eq!(summary.dropped, 1);
+        assert_eq!(queue.waiting(), 1);
+        assert!(matches!(
+            hidden.try_recv(),
+            Err(oneshot::error::TryRecvError::Closed)
+        ));
+        drop(running);
+        assert!(granted(&mut shown).is_some());
+    }
+
+    #[test]
+    fn test_video_limit_is_separate() {
+        // Arrange: 画像と動画の枠をそれぞれ1つずつ使用中にする
+        let queue = JobQueue::new(1, 1);
+        let mut image = queue.enqueue("/a.jpg", false, Priority::Normal);
+        let _image = granted(&mut image).unwrap();
+        let mut video = queue.enqueue("/b.mp4", true, Priority::Normal);
+        let video_slot = granted(&mut video).unwrap();
+        let mut waiting_video = queue.enqueue("/c.mp4", true, Priority::Normal);
+        let abandoned = queue.enqueue("/d.jpg", false, Priority::Visible);
+
+        // Act: 待つのをやめたジョブがあっても、動画の枠が空けば次の動画を実行する
+        drop(abandoned);
+        drop(video_slot);
+
+        // Assert
+        assert!(granted(&mut waiting_video).is_some());
+        assert_eq!(queue.waiting(), 0);
+    }
+}
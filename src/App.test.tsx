@@ -10,6 +10,8 @@ vi.mock('@tauri-apps/api/core', () => ({
 
 vi.mock('@tauri-apps/plugin-dialog', () => ({
   open: vi.fn(),
+  save: vi.fn(),
+  confirm: vi.fn(() => Promise.resolve(true)),
 }))
 
 const mockInvoke = vi.mocked(invoke)
@@ -71,3 +73,625 @@ describe('App', () => {
     expect(screen.getByText('次へ')).toBeInTheDocument()
   })
 })
+
+// コマンド名ごとの応答を返す invoke のモック
+function mockCommands(responses: Record<string, unknown>) {
+  mockInvoke.mockImplementation(async (cmd: string) => responses[cmd])
+}
+
+const settings = {
+  destinations: { '1': '/dest/1', '2': '/dest/2' },
+  theme: 'system',
+  language: 'ja',
+  showWelcome: false,
+  sourceFolder: '/src',
+}
+const images = [
+  { path: '/src/aria_starlight_01.jpg', name: 'aria_starlight_01.jpg' },
+  { path: '/src/IMG_0001.jpg', name: 'IMG_0001.jpg' },
+]
+
+// 設定と分別元の画像を読み込んだ状態で表示する
+async function renderLoaded() {
+  render(<App />)
+  await waitFor(() => {
+    expect(screen.getByText('1 / 2')).toBeInTheDocument()
+  })
+}
+
+describe('App 自動仕分け', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({
+      load_settings: settings,
+      scan_images: images,
+      plan_auto_sort: {
+        moves: [
+          {
+            sourcePath: '/src/aria_starlight_01.jpg',
+            key: '1',
+            destination: '/dest/1',
+            canonical: 'Aria Starlight',
+            rule: 'B',
+            source: 'fileName',
+          },
+        ],
+        skipped: [{ path: '/src/IMG_0001.jpg', reason: 'unmatched', keys: [] }],
+      },
+      run_auto_sort: {
+        moved: [
+          {
+            sourcePath: '/src/aria_starlight_01.jpg',
+            sourceFolder: '/src',
+            destPath: '/dest/1/aria_starlight_01.jpg',
+            key: '1',
+            extraPaths: [],
+          },
+        ],
+        skipped: [{ path: '/src/IMG_0001.jpg', reason: 'unmatched', keys: [] }],
+      },
+      undo_move: '/src/aria_starlight_01.jpg',
+    })
+  })
+
+  it('計画を確認して実行し、移動したファイルを元に戻せる', async () => {
+    await renderLoaded()
+
+    // Act: 計画を表示して実行
+    fireEvent.click(screen.getByRole('button', { name: '自動仕分け' }))
+    fireEvent.click(await screen.findByText('1件を移動'))
+
+    // Assert: 移動したファイルが一覧から除かれる
+    await waitFor(() => {
+      expect(screen.getByText('1 / 1')).toBeInTheDocument()
+    })
+    expect(mockInvoke).toHaveBeenCalledWith('run_auto_sort', expect.anything())
+
+    // Act: 元に戻す
+    fireEvent.keyDown(window, { key: 'z', ctrlKey: true })
+
+    // Assert: 履歴に積まれた移動が戻される
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('undo_move', {
+        currentPath: '/dest/1/aria_starlight_01.jpg',
+        originalFolder: '/src',
+      })
+    })
+  })
+})
+
+describe('App 別名候補', () => {
+  const proposal = {
+    key: '1',
+    type: 'character',
+    canonical: 'Aria Starlight',
+    alias: 'IMG',
+    aliasNormalized: 'img',
+    nearestKeyNormalized: 'aria',
+    score: 0.7,
+    occurrences: 1,
+    exampleFiles: ['/src/IMG_0001.jpg'],
+  }
+
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({
+      load_settings: settings,
+      scan_images: images,
+      plan_auto_sort: {
+        moves: [],
+        skipped: [{ path: '/src/IMG_0001.jpg', reason: 'unmatched', keys: [] }],
+      },
+      mine_aliases: { proposals: [proposal], frequentTerms: [] },
+      accept_aliases: { fileName: 'list.csv', sourcePath: '/lists/list.csv', entries: [] },
+    })
+  })
+
+  it('マッチしなかったファイルの別名候補を単語リストに追加する', async () => {
+    await renderLoaded()
+
+    // Act: 自動仕分けの計画から別名候補を探して追加
+    fireEvent.click(screen.getByRole('button', { name: '自動仕分け' }))
+    fireEvent.click(await screen.findByText('マッチしなかった1件から別名候補を探す'))
+    fireEvent.click(await screen.findByText('1件を追加'))
+
+    // Assert
+    await waitFor(() => {
+      expect(screen.getByText('単語リストに別名を1件追加しました')).toBeInTheDocument()
+    })
+    expect(mockInvoke).toHaveBeenCalledWith('mine_aliases', {
+      paths: ['/src/IMG_0001.jpg'],
+      configPath: '/mock/app/data/config.json',
+    })
+    expect(mockInvoke).toHaveBeenCalledWith('accept_aliases', {
+      key: '1',
+      aliases: [proposal],
+      configPath: '/mock/app/data/config.json',
+    })
+  })
+})
+
+describe('App タグモードの自動仕分け', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({ load_settings: { ...settings, operationMode: 'tag' }, scan_images: images })
+  })
+
+  it('ファイルを移動する自動仕分けは使えない', async () => {
+    await renderLoaded()
+
+    expect(screen.getByRole('button', { name: '自動仕分け' })).toBeDisabled()
+  })
+})
+
+describe('App 分別先の提案', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({
+      load_settings: settings,
+      scan_images: images,
+      suggest_destination: [
+        {
+          key: '2',
+          destination: '/dest/2',
+          origin: 'wordList',
+          confirmed: true,
+          canonical: 'Aria Starlight',
+        },
+      ],
+      move_file: '/dest/2/aria_starlight_01.jpg',
+    })
+  })
+
+  it('提案された分別先を強調表示し、Enterで移動する', async () => {
+    await renderLoaded()
+
+    // Assert: 提案された分別先に根拠が表示される
+    expect(await screen.findByText('Aria Starlight')).toBeInTheDocument()
+    expect(mockInvoke).toHaveBeenCalledWith('suggest_destination', {
+      path: '/src/aria_starlight_01.jpg',
+      configPath: '/mock/app/data/config.json',
+    })
+
+    // Act
+    fireEvent.keyDown(window, { key: 'Enter' })
+
+    // Assert
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('move_file', {
+        src: '/src/aria_starlight_01.jpg',
+        destFolder: '/dest/2',
+      })
+    })
+  })
+
+  it('Eキーで提案された分別先のマッチング過程を表示する', async () => {
+    mockCommands({
+      load_settings: settings,
+      scan_images: images,
+      suggest_destination: [
+        { key: '2', destination: '/dest/2', origin: 'wordList', confirmed: true, canonical: 'Aria Starlight' },
+      ],
+      explain_match: {
+        filePath: '/src/aria_starlight_01.jpg',
+        targets: [],
+        result: {
+          filePath: '/src/aria_starlight_01.jpg',
+          targetText: 'aria_starlight_01',
+          targetNormalized: 'ariastarlight01',
+          confirmed: [],
+          candidates: [],
+          unmatched: true,
+        },
+        dropped: [],
+      },
+    })
+    await renderLoaded()
+    await screen.findByText('Aria Starlight')
+
+    // Act
+    fireEvent.keyDown(window, { key: 'e' })
+
+    // Assert
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('explain_match', {
+        path: '/src/aria_starlight_01.jpg',
+        key: '2',
+        configPath: '/mock/app/data/config.json',
+      })
+    })
+    expect(await screen.findByText('マッチング過程: aria_starlight_01.jpg')).toBeInTheDocument()
+  })
+})
+
+describe('App 分別結果の学習', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({
+      load_settings: settings,
+      scan_images: images,
+      move_file: '/dest/1/aria_starlight_01.jpg',
+      undo_move: '/src/aria_starlight_01.jpg',
+    })
+  })
+
+  it('移動を元に戻すと学習も取り消す', async () => {
+    await renderLoaded()
+
+    // Act: 分別先1へ移動してから元に戻す
+    fireEvent.keyDown(window, { key: '1' })
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('train_classifier', {
+        path: '/dest/1/aria_starlight_01.jpg',
+        key: '1',
+        configPath: '/mock/app/data/config.json',
+      })
+    })
+    fireEvent.keyDown(window, { key: 'z', ctrlKey: true })
+
+    // Assert
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('untrain_classifier', {
+        path: '/dest/1/aria_starlight_01.jpg',
+        key: '1',
+        configPath: '/mock/app/data/config.json',
+      })
+    })
+    expect(mockInvoke).toHaveBeenCalledWith('undo_move', {
+      currentPath: '/dest/1/aria_starlight_01.jpg',
+      originalFolder: '/src',
+    })
+  })
+
+  it('学習に失敗した移動は元に戻しても学習を取り消さない', async () => {
+    mockInvoke.mockImplementation(async (cmd: string) => {
+      if (cmd === 'train_classifier') throw new Error('model is locked')
+      return {
+        load_settings: settings,
+        scan_images: images,
+        move_file: '/dest/1/aria_starlight_01.jpg',
+        undo_move: '/src/aria_starlight_01.jpg',
+      }[cmd]
+    })
+    await renderLoaded()
+
+    // Act
+    fireEvent.keyDown(window, { key: '1' })
+    await waitFor(() => {
+      expect(screen.getByText('1 / 1')).toBeInTheDocument()
+    })
+    fireEvent.keyDown(window, { key: 'z', ctrlKey: true })
+
+    // Assert
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('undo_move', expect.anything())
+    })
+    expect(mockInvoke).not.toHaveBeenCalledWith('untrain_classifier', expect.anything())
+  })
+})
+
+describe('App 拡張属性の記録', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({
+      load_settings: { ...settings, xattrTagging: true },
+      scan_images: images,
+      move_file: '/dest/1/aria_starlight_01.jpg',
+      record_sort_tags: {
+        tags: ['1'],
+        attributes: { key: '1', source: '/src' },
+        previous: { key: '2' },
+      },
+      undo_move: '/src/aria_starlight_01.jpg',
+      remove_file_tags: { tags: [], attributes: { key: '2' } },
+    })
+  })
+
+  it('元に戻すと以前の仕分けで記録した属性を復元する', async () => {
+    await renderLoaded()
+
+    // Act: 以前に分別先2へ仕分けたファイルを分別先1へ移動してから元に戻す
+    fireEvent.keyDown(window, { key: '1' })
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('record_sort_tags', expect.anything())
+    })
+    fireEvent.keyDown(window, { key: 'z', ctrlKey: true })
+
+    // Assert: 上書きした値を渡して取り除く
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('remove_file_tags', {
+        path: '/src/aria_starlight_01.jpg',
+        tags: ['1'],
+        attributes: ['key', 'source'],
+        previous: { key: '2' },
+      })
+    })
+  })
+})
+
+describe('App 複数分別先への配置', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({
+      load_settings: { ...settings, fanOut: 'copy' },
+      scan_images: [
+        { path: '/src/aria_leon_01.jpg', name: 'aria_leon_01.jpg' },
+        { path: '/src/IMG_0001.jpg', name: 'IMG_0001.jpg' },
+      ],
+      plan_fan_out: ['/dest/2'],
+      fan_out_file: {
+        destPath: '/dest/1/aria_leon_01.jpg',
+        extraPaths: ['/dest/2/aria_leon_01.jpg'],
+      },
+      undo_fan_out: '/src/aria_leon_01.jpg',
+    })
+  })
+
+  it('複数の分別先に確定したファイルを配置し、まとめて元に戻せる', async () => {
+    await renderLoaded()
+
+    // Act: 分別先1を主分別先として配置
+    fireEvent.keyDown(window, { key: '1' })
+
+    // Assert: Rust 側で求めた追加の分別先にも配置される
+    await waitFor(() => {
+      expect(screen.getByText('2件の分別先に配置しました')).toBeInTheDocument()
+    })
+    expect(mockInvoke).toHaveBeenCalledWith('plan_fan_out', {
+      path: '/src/aria_leon_01.jpg',
+      key: '1',
+      configPath: '/mock/app/data/config.json',
+    })
+    expect(mockInvoke).toHaveBeenCalledWith('fan_out_file', {
+      src: '/src/aria_leon_01.jpg',
+      destFolder: '/dest/1',
+      extraFolders: ['/dest/2'],
+      mode: 'copy',
+    })
+    expect(mockInvoke).not.toHaveBeenCalledWith('move_file', expect.anything())
+
+    // Act: 元に戻す
+    fireEvent.keyDown(window, { key: 'z', ctrlKey: true })
+
+    // Assert: 追加の配置もまとめて取り除く
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('undo_fan_out', {
+        currentPath: '/dest/1/aria_leon_01.jpg',
+        originalFolder: '/src',
+        extraPaths: ['/dest/2/aria_leon_01.jpg'],
+      })
+    })
+  })
+})
+
+describe('App 設定の保存', () => {
+  const handEdited = {
+    ...settings,
+    fanOut: 'symlink',
+    operationMode: 'tag',
+    scanOptions: { minRating: 3 },
+    animatedPreviews: { maxFrames: 30, maxDurationMs: 3000 },
+  }
+
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({ load_settings: handEdited, scan_images: images })
+  })
+
+  it('終了時の保存で設定画面のない項目を失わない', async () => {
+    await renderLoaded()
+
+    // Act
+    window.dispatchEvent(new Event('beforeunload'))
+
+    // Assert: ウィンドウ位置だけを上書きする
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('save_settings', {
+        settings: expect.objectContaining({
+          ...handEdited,
+          window: { width: 1280, height: 800, x: 100, y: 100 },
+        }),
+        configPath: '/mock/app/data/config.json',
+      })
+    })
+  })
+})
+
+describe('App 評価と色ラベル', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({
+      load_settings: settings,
+      scan_images: images,
+      read_rating_label: { rating: 2, label: null },
+      set_rating: {
+        path: '/src/aria_starlight_01.jpg',
+        rating: 4,
+        label: null,
+        location: 'sidecar',
+      },
+    })
+  })
+
+  it('表示中の画像の評価を読み込み、変更を書き込む', async () => {
+    await renderLoaded()
+
+    // Assert: スキャン時に読み込んでいない評価を表示時に読み込む
+    await waitFor(() => {
+      expect(screen.getByRole('button', { name: '★2' })).toHaveAttribute('aria-pressed', 'true')
+    })
+    expect(mockInvoke).toHaveBeenCalledWith('read_rating_label', {
+      path: '/src/aria_starlight_01.jpg',
+    })
+
+    // Act
+    fireEvent.click(screen.getByRole('button', { name: '★4' }))
+
+    // Assert
+    await waitFor(() => {
+      expect(screen.getByRole('button', { name: '★4' })).toHaveAttribute('aria-pressed', 'true')
+    })
+    expect(mockInvoke).toHaveBeenCalledWith('set_rating', {
+      path: '/src/aria_starlight_01.jpg',
+      rating: 4,
+      location: 'sidecar',
+    })
+  })
+})
+
+describe('App 単語リストの検査と統合', () => {
+  const wordList = { fileName: 'characters.csv', entries: [] }
+  const incoming = { fileName: 'extra.csv', sourcePath: '/lists/extra.csv', entries: [] }
+  const conflict = {
+    kind: 'duplicateCanonical',
+    entityIds: ['character:Aria Starlight'],
+    key: null,
+    resolution: 'unitedAliases',
+  }
+
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({
+      load_settings: { ...settings, wordLists: { '1': wordList } },
+      scan_images: images,
+      lint_word_list: [{ kind: 'shortKey', entityIds: ['character:Aria Starlight'], key: 'ar' }],
+      import_word_list: incoming,
+      merge_word_lists: { wordList: { ...wordList, fileName: 'characters.csv' }, conflicts: [conflict] },
+    })
+  })
+
+  it('検査結果を表示し、選んだCSVを統合する', async () => {
+    await renderLoaded()
+
+    // Act: 設定を開いて単語リストを検査する
+    fireEvent.keyDown(window, { key: ',', ctrlKey: true })
+    fireEvent.click(await screen.findByTitle('単語リストを検査'))
+
+    // Assert
+    expect(await screen.findByText('短すぎる検索キー')).toBeInTheDocument()
+    expect(mockInvoke).toHaveBeenCalledWith('lint_word_list', { wordList })
+
+    // Act: 別のCSVと統合する
+    const { open } = await import('@tauri-apps/plugin-dialog')
+    vi.mocked(open).mockResolvedValueOnce('/lists/extra.csv')
+    fireEvent.click(screen.getByText('別のCSVと統合'))
+
+    // Assert
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('merge_word_lists', {
+        base: wordList,
+        incoming,
+        strategy: 'unionAliases',
+      })
+    })
+    expect(mockInvoke).toHaveBeenCalledWith('import_word_list', { path: '/lists/extra.csv' })
+    expect(await screen.findByText('重複したエントリ')).toBeInTheDocument()
+  })
+})
+
+describe('App メタデータの表示', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({
+      load_settings: settings,
+      scan_images: images,
+      read_metadata: {
+        make: 'Canon',
+        model: 'EOS R5',
+        lensMake: null,
+        lensModel: null,
+        exposureTime: '1/250',
+        fNumber: null,
+        iso: null,
+        focalLength: null,
+        focalLength35mm: null,
+        capturedAt: null,
+        timeOffset: null,
+        orientation: null,
+        width: null,
+        height: null,
+        gps: null,
+        software: null,
+      },
+      read_ai_metadata: {
+        generator: 'comfyUi',
+        prompt: 'aria starlight',
+        negativePrompt: null,
+        model: null,
+        seed: null,
+        sampler: null,
+        steps: null,
+        cfgScale: null,
+        loras: [],
+      },
+    })
+  })
+
+  it('Iキーで表示中の画像の撮影情報を表示する', async () => {
+    await renderLoaded()
+
+    // Act
+    fireEvent.keyDown(window, { key: 'i' })
+
+    // Assert
+    expect(await screen.findByText('Canon EOS R5')).toBeInTheDocument()
+    expect(mockInvoke).toHaveBeenCalledWith('read_metadata', { path: '/src/aria_starlight_01.jpg' })
+    expect(screen.getByText('ComfyUI')).toBeInTheDocument()
+    expect(mockInvoke).toHaveBeenCalledWith('read_ai_metadata', { path: '/src/aria_starlight_01.jpg' })
+  })
+})
+
+describe('App 分別の学習', () => {
+  beforeEach(() => {
+    vi.clearAllMocks()
+    sessionStorage.clear()
+    mockCommands({
+      load_settings: settings,
+      scan_images: images,
+    })
+  })
+
+  it('学習結果を選んだファイルに書き出す', async () => {
+    const { save } = await import('@tauri-apps/plugin-dialog')
+    vi.mocked(save).mockResolvedValueOnce('/backup/classifier.json')
+    await renderLoaded()
+
+    // Act
+    fireEvent.keyDown(window, { key: ',', ctrlKey: true })
+    fireEvent.click(await screen.findByText('学習結果を書き出す'))
+
+    // Assert
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('export_classifier', {
+        configPath: '/mock/app/data/config.json',
+        exportPath: '/backup/classifier.json',
+      })
+    })
+  })
+
+  it('確認後に学習結果をリセットする', async () => {
+    await renderLoaded()
+
+    // Act
+    fireEvent.keyDown(window, { key: ',', ctrlKey: true })
+    fireEvent.click(await screen.findByText('学習結果をリセット'))
+
+    // Assert
+    await waitFor(() => {
+      expect(mockInvoke).toHaveBeenCalledWith('reset_classifier', {
+        configPath: '/mock/app/data/config.json',
+      })
+    })
+  })
+})
@@ -1,5 +1,5 @@
 import { useCallback, useEffect, useRef, useState } from 'react'
-import { open } from '@tauri-apps/plugin-dialog'
+import { confirm, open, save } from '@tauri-apps/plugin-dialog'
 import { appDataDir, join } from '@tauri-apps/api/path'
 import { getCurrentWindow } from '@tauri-apps/api/window'
 import { listen, UnlistenFn } from '@tauri-apps/api/event'
@@ -16,9 +16,14 @@ import { ThumbnailGrid } from './components/ThumbnailGrid'
 import { StatusBar } from './components/StatusBar'
 import { WelcomeModal } from './components/WelcomeModal'
 import { SettingsModal } from './components/SettingsModal'
-import type { Settings, WordList } from './types'
+import { AutoSortModal } from './components/AutoSortModal'
+import { AliasModal } from './components/AliasModal'
+import { ExplainModal } from './components/ExplainModal'
+import { WordListReportModal } from './components/WordListReportModal'
+import { RatingBar } from './components/RatingBar'
+import { MetadataPanel } from './components/MetadataPanel'
+import type { AliasMiningReport, AliasProposal, AutoSortPlan, ColorLabel, AiMetadata, DestinationSuggestion, ExifMetadata, MatchExplanation, MergeStrategy, RatingResult, Settings, WordList, WordListChangeEvent, WordListReport, XmpLocation } from './types'
 import { getFileName } from './utils/path'
-import { loadWordListFromFile } from './utils/csv'
 import { isFileMatching } from './utils/matching'
 
 // ファイルシステム変更イベントの型
@@ -30,12 +35,16 @@ interface FsChangeEvent {
 function AppContent() {
   const { state, dispatch } = useApp()
   const { t } = useLanguage()
-  const { scanImages, moveFile, undoMove, loadSettings, saveSettings, startWatching, moveFilesBatch } = useTauriCommands()
-  const { addToHistory, undo, redo, canUndo, canRedo } = useHistory()
+  const { scanImages, moveFile, undoMove, fanOutFile, undoFanOut, tagFile, untagFile, removeFileTags, recordSortTags, loadSettings, saveSettings, startWatching, moveFilesBatch, planAutoSort, planFanOut, runAutoSort, trainClassifier, untrainClassifier, watchWordLists, unwatchWordLists, setRating, setColorLabel, readRatingLabel, mineAliases, acceptAliases, suggestDestination, explainMatch, importWordList, lintWordList, mergeWordLists, dedupeWordList, readMetadata, readAiMetadata, resetClassifier, exportClassifier } = useTauriCommands()
+  const { addToHistory, undo, redo, updateHistoryItem, canUndo, canRedo } = useHistory()
   const configPathRef = useRef<string | null>(null)
   const isInitializedRef = useRef(false)
   const [showWelcome, setShowWelcome] = useState(false)
   const [showSettings, setShowSettings] = useState(false)
+  const [autoSortPlan, setAutoSortPlan] = useState<AutoSortPlan | null>(null)
+  const [aliasReport, setAliasReport] = useState<AliasMiningReport | null>(null)
+  const [wordListReport, setWordListReport] = useState<WordListReport | null>(null)
+  const [explainState, setExplainState] = useState<{ key: string; explanation: MatchExplanation } | null>(null)
   const [isVideo, setIsVideo] = useState(false)
   const [wordLists, setWordLists] = useState<Record<string, WordList | null>>({})
   const settingsRef = useRef<Settings | null>(null)
@@ -83,7 +92,7 @@ function AppContent() {
           console.log('[Settings] restoring sourceFolder:', settings.sourceFolder)
           dispatch({ type: 'SET_SOURCE_FOLDER', payload: settings.sourceFolder })
           try {
-            const images = await scanImages(settings.sourceFolder)
+            const images = await scanImages(settings.sourceFolder, settings.scanOptions)
             dispatch({ type: 'SET_IMAGES', payload: images })
             console.log('[Settings] scanned images count:', images.length)
           } catch (e) {
@@ -110,6 +119,26 @@ function AppContent() {
     initSettings()
   }, [dispatch, loadSettings, scanImages])
 
+  // 読み込んだ設定を引き継ぎ、変更した項目だけを上書きして保存する
+  // （設定画面のない項目を手で編集した場合も失わないようにする）
+  const persistSettings = useCallback(async (changes: Partial<Settings> = {}) => {
+    if (!configPathRef.current) return
+
+    const settings: Settings = {
+      theme: 'system',
+      language: 'ja',
+      ...settingsRef.current,
+      destinations: state.destinations,
+      sourceFolder: state.sourceFolder,
+      wordLists,
+      ...changes,
+    }
+    settingsRef.current = settings
+
+    console.log('[Settings] saving settings:', JSON.stringify(settings, null, 2))
+    await saveSettings(settings, configPathRef.current)
+  }, [state.destinations, state.sourceFolder, wordLists, saveSettings])
+
   // 分別先/分別元/単語リストが変更されたら設定を保存
   useEffect(() => {
     const saveCurrentSettings = async () => {
@@ -119,20 +148,8 @@ function AppContent() {
         return
       }
 
-      const settings: Settings = {
-        destinations: state.destinations,
-        theme: 'system',
-        language: 'ja',
-        showWelcome: settingsRef.current?.showWelcome ?? true,
-        sourceFolder: state.sourceFolder,
-        window: { width: 1280, height: 800, x: null, y: null },
-        wordLists,
-      }
-
-      console.log('[Settings] saving settings:', JSON.stringify(settings, null, 2))
-
       try {
-        await saveSettings(settings, configPathRef.current)
+        await persistSettings()
         console.log('[Settings] settings saved successfully')
       } catch (e) {
         console.error('[Settings] save failed:', e)
@@ -143,7 +160,7 @@ function AppContent() {
     if (settingsLoadedRef.current) {
       saveCurrentSettings()
     }
-  }, [state.destinations, state.sourceFolder, wordLists, saveSettings])
+  }, [persistSettings])
 
   const currentImage =
     state.images.length > 0 ? state.images[state.currentIndex] : null
@@ -152,6 +169,99 @@ function AppContent() {
       ? state.images[state.currentIndex + 1]
       : null
 
+  // 表示中の画像の評価・色ラベルを読み込む（スキャン時に読み込んでいない場合のみ、読み込み中は重ねて要求しない）
+  const currentPath = currentImage?.path
+  const currentRatingLoaded = currentImage?.rating !== undefined
+  const ratingRequestRef = useRef<string | null>(null)
+  useEffect(() => {
+    if (!currentPath || currentRatingLoaded || ratingRequestRef.current === currentPath) return
+    ratingRequestRef.current = currentPath
+    readRatingLabel(currentPath)
+      .then(({ rating, label }) =>
+        dispatch({ type: 'SET_IMAGE_RATING', payload: { path: currentPath, rating, label } })
+      )
+      .catch((e) => console.warn('[Rating] read failed:', e))
+      .finally(() => {
+        ratingRequestRef.current = null
+      })
+  }, [currentPath, currentRatingLoaded, readRatingLabel, dispatch])
+
+  // 表示中の画像のメタデータ（表示中のみ読み込み、画像が変わった後に届いた結果は捨てる）
+  const [showInfo, setShowInfo] = useState(false)
+  const [metadata, setMetadata] = useState<{
+    path: string
+    exif: ExifMetadata | null
+    ai: AiMetadata | null
+  } | null>(null)
+  const metadataForRef = useRef<string | null>(null)
+  useEffect(() => {
+    if (!showInfo || !currentPath) {
+      metadataForRef.current = null
+      return
+    }
+    if (metadataForRef.current === currentPath) return
+    metadataForRef.current = currentPath
+    Promise.all([readMetadata(currentPath), readAiMetadata(currentPath)])
+      .then(([exif, ai]) => {
+        if (metadataForRef.current === currentPath) {
+          setMetadata({ path: currentPath, exif: exif ?? null, ai: ai ?? null })
+        }
+      })
+      .catch((e) => console.warn('[Metadata] read failed:', e))
+  }, [showInfo, currentPath, readMetadata, readAiMetadata])
+
+  // 表示中の画像の分別先の提案（単語リストと学習結果のうち最も有力なもの）
+  // 同じ画像・単語リストでは重ねて要求せず、表示中の画像が変わった後に届いた結果は捨てる
+  const [suggestion, setSuggestion] = useState<DestinationSuggestion | null>(null)
+  const suggestedForRef = useRef<{ path: string; wordLists: Record<string, WordList | null> } | null>(null)
+  useEffect(() => {
+    if (!currentPath || state.viewMode !== 'single' || !configPathRef.current) {
+      suggestedForRef.current = null
+      setSuggestion(null)
+      return
+    }
+    const last = suggestedForRef.current
+    if (last?.path === currentPath && last.wordLists === wordLists) return
+    suggestedForRef.current = { path: currentPath, wordLists }
+    setSuggestion(null)
+    suggestDestination(currentPath, configPathRef.current)
+      .then((suggestions) => {
+        if (suggestedForRef.current?.path === currentPath) setSuggestion(suggestions[0] ?? null)
+      })
+      .catch((e) => console.warn('[Suggest] failed:', e))
+  }, [currentPath, state.viewMode, wordLists, suggestDestination])
+
+  // 評価・色ラベルを書き込む（書き込み先はタグモードと同じ設定に従う）
+  const writeRating = useCallback(
+    async (write: (path: string, location: XmpLocation) => Promise<RatingResult>) => {
+      if (!currentPath) return
+      try {
+        const result = await write(currentPath, settingsRef.current?.tagLocation ?? 'sidecar')
+        dispatch({
+          type: 'SET_IMAGE_RATING',
+          payload: { path: result.path, rating: result.rating, label: result.label },
+        })
+      } catch (error) {
+        dispatch({
+          type: 'SET_STATUS',
+          payload: { status: 'error', message: t('status.ratingError', { error: String(error) }) },
+        })
+      }
+    },
+    [currentPath, dispatch, t]
+  )
+
+  const handleRate = useCallback(
+    (rating: number) => writeRating((path, location) => setRating(path, rating, location)),
+    [writeRating, setRating]
+  )
+
+  const handleLabel = useCallback(
+    (label: ColorLabel | null) =>
+      writeRating((path, location) => setColorLabel(path, label, location)),
+    [writeRating, setColorLabel]
+  )
+
   const handleSelectFolder = useCallback(async () => {
     const selected = await open({
       directory: true,
@@ -166,7 +276,7 @@ function AppContent() {
       })
 
       try {
-        const images = await scanImages(selected as string)
+        const images = await scanImages(selected as string, settingsRef.current?.scanOptions)
         dispatch({ type: 'SET_IMAGES', payload: images })
 
         // ファイルシステム監視を開始
@@ -209,6 +319,30 @@ function AppContent() {
     async (key: string) => {
       if (!currentImage || !state.sourceFolder) return
 
+      // タグモード: 移動せずキーワードを書き込み、次の画像へ進む
+      if (settingsRef.current?.operationMode === 'tag') {
+        if (!configPathRef.current) return
+        const path = currentImage.path
+        try {
+          const tag = await tagFile(path, key, configPathRef.current)
+          addToHistory({ sourcePath: path, sourceFolder: state.sourceFolder, destPath: path, tag })
+          dispatch({
+            type: 'SET_CURRENT_INDEX',
+            payload: Math.min(state.images.length - 1, state.currentIndex + 1),
+          })
+          dispatch({
+            type: 'SET_STATUS',
+            payload: { status: 'success', message: t('status.fileTagged', { keywords: tag.keywords.join(', ') }) },
+          })
+        } catch (error) {
+          dispatch({
+            type: 'SET_STATUS',
+            payload: { status: 'error', message: t('status.tagError', { error: String(error) }) },
+          })
+        }
+        return
+      }
+
       const destination = state.destinations[key]
       if (!destination) {
         dispatch({
@@ -225,14 +359,60 @@ function AppContent() {
       const sourceFolder = state.sourceFolder
 
       try {
-        const destPath = await moveFile(sourcePath, destination)
-        // 履歴に追加
-        addToHistory({ sourcePath, sourceFolder, destPath })
+        // 振り分け設定時は、他に確定マッチした分別先にもリンク・コピーを配置する
+        // （自動仕分けと同じく、メタデータも含めて Rust 側で照合する）
+        const fanOut = settingsRef.current?.fanOut
+        const extraFolders = fanOut && configPathRef.current
+          ? await planFanOut(sourcePath, key, configPathRef.current)
+          : []
+
+        let destPath: string
+        let extraPaths: string[] = []
+        if (fanOut && extraFolders.length > 0) {
+          const placed = await fanOutFile(sourcePath, destination, extraFolders, fanOut)
+          destPath = placed.destPath
+          extraPaths = placed.extraPaths
+        } else {
+          destPath = await moveFile(sourcePath, destination)
+        }
+        // 仕分け結果を拡張属性に記録（失敗しても移動は成功扱い）
+        let xattr
+        if (settingsRef.current?.xattrTagging && configPathRef.current) {
+          xattr = await recordSortTags(destPath, key, sourceFolder, configPathRef.current).catch((e) => {
+            console.warn('[Xattr] record failed:', e)
+            return undefined
+          })
+        }
+        // 分別結果を学習（失敗しても移動は成功扱い、学習できた場合のみ Undo 時に取り消す）
+        let trainedKey
+        if (configPathRef.current) {
+          trainedKey = await trainClassifier(destPath, key, configPathRef.current).then(
+            () => key,
+            (e) => {
+              console.warn('[Classifier] train failed:', e)
+              return undefined
+            }
+          )
+        }
+        // 履歴に追加（追加の分別先への配置もまとめて1件として戻す）
+        addToHistory({
+          sourcePath,
+          sourceFolder,
+          destPath,
+          xattr,
+          trainedKey,
+          ...(extraPaths.length > 0 ? { extraPaths, fanOutMode: fanOut } : {}),
+        })
         dispatch({ type: 'REMOVE_CURRENT_IMAGE' })
         dispatch({ type: 'SET_LAST_USED_DESTINATION', payload: key })
         dispatch({
           type: 'SET_STATUS',
-          payload: { status: 'success', message: t('status.fileMoved') },
+          payload: {
+            status: 'success',
+            message: extraPaths.length > 0
+              ? t('status.fileFannedOut', { count: extraPaths.length + 1 })
+              : t('status.fileMoved'),
+          },
         })
       } catch (error) {
         dispatch({
@@ -241,7 +421,7 @@ function AppContent() {
         })
       }
     },
-    [state.destinations, state.sourceFolder, currentImage, moveFile, dispatch, t, addToHistory]
+    [state.destinations, state.sourceFolder, state.images.length, state.currentIndex, currentImage, moveFile, fanOutFile, planFanOut, tagFile, recordSortTags, trainClassifier, dispatch, t, addToHistory]
   )
 
   const handleNavigate = useCallback(
@@ -291,7 +471,7 @@ function AppContent() {
             })
 
             try {
-              const images = await scanImages(folderPath)
+              const images = await scanImages(folderPath, settingsRef.current?.scanOptions)
               dispatch({ type: 'SET_IMAGES', payload: images })
 
               // ファイルシステム監視を開始
@@ -342,7 +522,7 @@ function AppContent() {
 
     if (selected) {
       try {
-        const wordList = await loadWordListFromFile(selected as string)
+        const wordList = await importWordList(selected as string)
         setWordLists(prev => ({ ...prev, [key]: wordList }))
         dispatch({
           type: 'SET_STATUS',
@@ -355,12 +535,130 @@ function AppContent() {
         })
       }
     }
-  }, [dispatch, t])
+  }, [importWordList, dispatch, t])
 
   const handleClearWordList = useCallback((key: string) => {
     setWordLists(prev => ({ ...prev, [key]: null }))
   }, [])
 
+  // 学習結果を JSON ファイルに書き出す
+  const handleExportClassifier = useCallback(async () => {
+    const configPath = configPathRef.current
+    if (!configPath) return
+
+    const exportPath = await save({
+      defaultPath: 'picsort-classifier.json',
+      filters: [{ name: 'JSON', extensions: ['json'] }],
+    })
+    if (!exportPath) return
+
+    try {
+      await exportClassifier(configPath, exportPath)
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'success', message: t('status.classifierExported', { path: exportPath }) },
+      })
+    } catch (error) {
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'error', message: t('status.classifierError', { error: String(error) }) },
+      })
+    }
+  }, [exportClassifier, dispatch, t])
+
+  // 学習結果を消去する（確認あり）
+  const handleResetClassifier = useCallback(async () => {
+    const configPath = configPathRef.current
+    if (!configPath) return
+
+    const confirmed = await confirm(t('settings.confirmResetClassifier'), {
+      title: t('settings.resetClassifier'),
+      kind: 'warning',
+    })
+    if (!confirmed) return
+
+    try {
+      await resetClassifier(configPath)
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'success', message: t('status.classifierReset') },
+      })
+    } catch (error) {
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'error', message: t('status.classifierError', { error: String(error) }) },
+      })
+    }
+  }, [resetClassifier, dispatch, t])
+
+  // 単語リストを検査し、問題の一覧を表示する
+  const handleCheckWordList = useCallback(async (key: string) => {
+    const wordList = wordLists[key]
+    if (!wordList) return
+
+    try {
+      const issues = await lintWordList(wordList)
+      setWordListReport({ key, fileName: wordList.fileName, issues })
+    } catch (error) {
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'error', message: t('status.wordListCheckError', { error: String(error) }) },
+      })
+    }
+  }, [wordLists, lintWordList, dispatch, t])
+
+  // 単語リスト内の重複エントリを統合する
+  const handleDedupeWordList = useCallback(async (key: string) => {
+    const wordList = wordLists[key]
+    if (!wordList) return
+
+    try {
+      const { wordList: deduped, conflicts } = await dedupeWordList(wordList)
+      setWordLists(prev => ({ ...prev, [key]: deduped }))
+      setWordListReport({ key, fileName: deduped.fileName, issues: conflicts })
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'success', message: t('status.wordListDeduped', { count: conflicts.length }) },
+      })
+    } catch (error) {
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'error', message: t('status.wordListCheckError', { error: String(error) }) },
+      })
+    }
+  }, [wordLists, dedupeWordList, dispatch, t])
+
+  // 選んだCSVを単語リストに統合する（衝突は統合方法に従って解決し、結果を表示する）
+  const handleMergeWordList = useCallback(async (key: string, strategy: MergeStrategy) => {
+    const wordList = wordLists[key]
+    if (!wordList) return
+
+    const selected = await open({
+      filters: [{ name: 'CSV', extensions: ['csv'] }],
+      multiple: false,
+    })
+    if (!selected) return
+
+    try {
+      const incoming = await importWordList(selected as string)
+      const { wordList: merged, conflicts } = await mergeWordLists(wordList, incoming, strategy)
+      setWordLists(prev => ({ ...prev, [key]: merged }))
+      setWordListReport({ key, fileName: merged.fileName, issues: conflicts })
+      dispatch({
+        type: 'SET_STATUS',
+        payload: {
+          status: 'success',
+          message: t('status.wordListMerged', { name: incoming.fileName, count: conflicts.length }),
+        },
+      })
+    } catch (error) {
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'error', message: t('status.wordListCheckError', { error: String(error) }) },
+      })
+    }
+  }, [wordLists, importWordList, mergeWordLists, dispatch, t])
+
   const handleMatchingSelect = useCallback((key: string) => {
     const wordList = wordLists[key]
     if (!wordList) return
@@ -391,27 +689,12 @@ function AppContent() {
   }, [wordLists, state.viewMode, state.images, dispatch, t])
 
   const handleDontShowWelcomeAgain = useCallback(async () => {
-    if (!configPathRef.current) return
-
-    // settingsRefを更新
-    if (settingsRef.current) {
-      settingsRef.current.showWelcome = false
-    }
-
-    const settings: Settings = {
-      destinations: state.destinations,
-      theme: 'system',
-      language: 'ja',
-      showWelcome: false,
-      sourceFolder: state.sourceFolder,
-    }
-
     try {
-      await saveSettings(settings, configPathRef.current)
+      await persistSettings({ showWelcome: false })
     } catch {
       // 保存失敗は無視
     }
-  }, [state.destinations, state.sourceFolder, saveSettings])
+  }, [persistSettings])
 
   // ファイルシステム変更イベントをリッスン
   useEffect(() => {
@@ -434,7 +717,7 @@ function AppContent() {
           // ファイルが追加された場合、画像リストを再スキャン
           if (state.sourceFolder) {
             try {
-              const images = await scanImages(state.sourceFolder)
+              const images = await scanImages(state.sourceFolder, settingsRef.current?.scanOptions)
               dispatch({ type: 'SET_IMAGES', payload: images })
               dispatch({
                 type: 'SET_STATUS',
@@ -470,6 +753,50 @@ function AppContent() {
     }
   }, [state.sourceFolder, scanImages, dispatch, t])
 
+  // 単語リストCSVの変更を監視（読み込み元パスを持つリストのみ）
+  const wordListWatchingRef = useRef(false)
+  const watchedWordListsRef = useRef<Record<string, WordList | null> | null>(null)
+  useEffect(() => {
+    // 同じ内容での再起動を避ける
+    if (watchedWordListsRef.current === wordLists) return
+    watchedWordListsRef.current = wordLists
+
+    const hasSource = Object.values(wordLists).some((list) => list?.sourcePath)
+    if (hasSource) {
+      wordListWatchingRef.current = true
+      watchWordLists(wordLists).catch((e) => console.warn('[WordList] watch failed:', e))
+    } else if (wordListWatchingRef.current) {
+      wordListWatchingRef.current = false
+      unwatchWordLists().catch((e) => console.warn('[WordList] unwatch failed:', e))
+    }
+  }, [wordLists, watchWordLists, unwatchWordLists])
+
+  // 単語リストの再読み込みイベントをリッスン
+  useEffect(() => {
+    let unlisten: UnlistenFn | null = null
+    listen<WordListChangeEvent>('word-list-changed', (event) => {
+      const { key, wordList, added, removed } = event.payload
+      setWordLists((prev) => ({ ...prev, [key]: wordList }))
+      dispatch({
+        type: 'SET_STATUS',
+        payload: {
+          status: 'idle',
+          message: t('status.wordListReloaded', {
+            name: wordList.fileName,
+            added: added.length,
+            removed: removed.length,
+          }),
+        },
+      })
+    }).then((fn) => {
+      unlisten = fn
+    })
+
+    return () => {
+      unlisten?.()
+    }
+  }, [dispatch, t])
+
   // ウィンドウ状態保存（終了時）
   useEffect(() => {
     const saveWindowState = async () => {
@@ -480,22 +807,14 @@ function AppContent() {
         const size = await window.innerSize()
         const position = await window.outerPosition()
 
-        const settings: Settings = {
-          destinations: state.destinations,
-          theme: 'system',
-          language: 'ja',
-          showWelcome: settingsRef.current?.showWelcome ?? true,
-          sourceFolder: state.sourceFolder,
+        await persistSettings({
           window: {
             width: size.width,
             height: size.height,
             x: position.x,
             y: position.y,
           },
-          wordLists,
-        }
-
-        await saveSettings(settings, configPathRef.current)
+        })
       } catch {
         // 保存失敗は無視
       }
@@ -510,7 +829,7 @@ function AppContent() {
     return () => {
       window.removeEventListener('beforeunload', handleBeforeUnload)
     }
-  }, [state.destinations, state.sourceFolder, saveSettings])
+  }, [persistSettings])
 
   const handleUndo = useCallback(async () => {
     if (!canUndo || !state.sourceFolder) return
@@ -525,9 +844,31 @@ function AppContent() {
     const currentPath = state.images[state.currentIndex]?.path || null
 
     try {
-      await undoMove(item.destPath, item.sourceFolder)
+      // 学習した分別結果を取り消す（ファイルを戻す前に特徴量を読む。失敗しても Undo は続ける）
+      if (item.trainedKey && configPathRef.current) {
+        await untrainClassifier(item.destPath, item.trainedKey, configPathRef.current).catch((e) =>
+          console.warn('[Classifier] untrain failed:', e)
+        )
+      }
+      // タグモードは追加したキーワードのみ取り除き、複数分別先への配置は追加分もまとめて戻す
+      if (item.tag) {
+        await untagFile(item.tag.path, item.tag.keywords, item.tag.location, item.tag.sidecarCreated)
+      } else if (item.extraPaths?.length) {
+        await undoFanOut(item.destPath, item.sourceFolder, item.extraPaths)
+      } else {
+        const restoredPath = await undoMove(item.destPath, item.sourceFolder)
+        // 記録した拡張属性を取り除き、上書きした以前の仕分け結果は戻す（処理済みとして隠されないように）
+        if (item.xattr) {
+          await removeFileTags(
+            restoredPath,
+            item.xattr.tags,
+            Object.keys(item.xattr.attributes),
+            item.xattr.previous
+          )
+        }
+      }
       // Rust側で再スキャンして正しいソート順を取得
-      const images = await scanImages(state.sourceFolder)
+      const images = await scanImages(state.sourceFolder, settingsRef.current?.scanOptions)
       dispatch({
         type: 'SET_IMAGES_PRESERVE_CURRENT',
         payload: { images, currentPath },
@@ -547,7 +888,7 @@ function AppContent() {
         isUndoRedoInProgress.current = false
       }, 600)
     }
-  }, [canUndo, undo, undoMove, scanImages, state.sourceFolder, state.images, state.currentIndex, dispatch, t])
+  }, [canUndo, undo, undoMove, undoFanOut, untagFile, removeFileTags, untrainClassifier, scanImages, state.sourceFolder, state.images, state.currentIndex, dispatch, t])
 
   const handleRedo = useCallback(async () => {
     if (!canRedo || !state.sourceFolder) return
@@ -565,9 +906,37 @@ function AppContent() {
     try {
       // destPath からフォルダ部分を抽出（クロスプラットフォーム対応）
       const destFolder = item.destPath.replace(/[\\/][^\\/]+$/, '')
-      await moveFile(item.sourcePath, destFolder)
+      if (item.tag) {
+        if (configPathRef.current) {
+          await tagFile(item.tag.path, item.tag.key, configPathRef.current)
+        }
+      } else {
+        let destPath
+        let extraPaths = item.extraPaths
+        if (extraPaths?.length) {
+          const extraFolders = extraPaths.map((p) => p.replace(/[\\/][^\\/]+$/, ''))
+          const placed = await fanOutFile(item.sourcePath, destFolder, extraFolders, item.fanOutMode ?? 'copy')
+          destPath = placed.destPath
+          extraPaths = placed.extraPaths
+        } else {
+          destPath = await moveFile(item.sourcePath, destFolder)
+        }
+        // Undo で取り消した学習をやり直す（失敗した場合は次の Undo で取り消さない）
+        let trainedKey = item.trainedKey
+        if (trainedKey && configPathRef.current) {
+          const key = trainedKey
+          trainedKey = await trainClassifier(destPath, key, configPathRef.current).then(
+            () => key,
+            (e) => {
+              console.warn('[Classifier] train failed:', e)
+              return undefined
+            }
+          )
+        }
+        updateHistoryItem(item.id, { destPath, extraPaths, trainedKey })
+      }
       // Rust側で再スキャンして正しいソート順を取得
-      const images = await scanImages(state.sourceFolder)
+      const images = await scanImages(state.sourceFolder, settingsRef.current?.scanOptions)
       dispatch({
         type: 'SET_IMAGES_PRESERVE_CURRENT',
         payload: { images, currentPath },
@@ -587,7 +956,7 @@ function AppContent() {
         isUndoRedoInProgress.current = false
       }, 600)
     }
-  }, [canRedo, redo, moveFile, scanImages, state.sourceFolder, state.images, state.currentIndex, dispatch, t])
+  }, [canRedo, redo, updateHistoryItem, moveFile, fanOutFile, tagFile, trainClassifier, scanImages, state.sourceFolder, state.images, state.currentIndex, dispatch, t])
 
   // Grid mode handlers
   const handleToggleViewMode = useCallback(() => {
@@ -652,8 +1021,135 @@ function AppContent() {
     }
   }, [state.selectedPaths, state.destinations, state.sourceFolder, moveFilesBatch, dispatch, t, addToHistory])
 
+  // 自動仕分け: 計画を作成して確認モーダルに表示
+  const handlePlanAutoSort = useCallback(async () => {
+    if (!configPathRef.current || state.images.length === 0) return
+
+    try {
+      const plan = await planAutoSort(state.images.map((img) => img.path), configPathRef.current)
+      // 移動できるものがなくても、マッチしなかったファイルがあれば別名候補を探せるように表示する
+      if (plan.moves.length === 0 && !plan.skipped.some((s) => s.reason === 'unmatched')) {
+        dispatch({
+          type: 'SET_STATUS',
+          payload: { status: 'warning', message: t('status.autoSortEmpty') },
+        })
+        return
+      }
+      setAutoSortPlan(plan)
+    } catch (error) {
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'error', message: t('status.autoSortError', { error: String(error) }) },
+      })
+    }
+  }, [state.images, planAutoSort, dispatch, t])
+
+  // 確認済みの計画を実行し、移動したファイルを1件ずつ履歴に追加
+  // 単語リストで確定したファイルのみを移動するため、分別結果は学習しない
+  // （単語リストで分かる以上のことを学ばず、手動で選んだ分別先の学習を薄めてしまう）
+  const handleRunAutoSort = useCallback(async (plan: AutoSortPlan) => {
+    setAutoSortPlan(null)
+
+    try {
+      const summary = await runAutoSort(plan)
+      for (const moved of summary.moved) {
+        addToHistory({
+          sourcePath: moved.sourcePath,
+          sourceFolder: moved.sourceFolder,
+          destPath: moved.destPath,
+          ...(moved.extraPaths.length > 0 ? { extraPaths: moved.extraPaths, fanOutMode: plan.fanOut } : {}),
+        })
+        dispatch({ type: 'REMOVE_IMAGE_BY_PATH', payload: moved.sourcePath })
+      }
+      dispatch({
+        type: 'SET_STATUS',
+        payload: {
+          status: 'success',
+          message: t('status.autoSortDone', { moved: summary.moved.length, skipped: summary.skipped.length }),
+        },
+      })
+    } catch (error) {
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'error', message: t('status.autoSortError', { error: String(error) }) },
+      })
+    }
+  }, [runAutoSort, addToHistory, dispatch, t])
+
+  // マッチしなかったファイル名から単語リストの別名候補を探す
+  const handleMineAliases = useCallback(async (paths: string[]) => {
+    if (!configPathRef.current) return
+    setAutoSortPlan(null)
+
+    try {
+      setAliasReport(await mineAliases(paths, configPathRef.current))
+    } catch (error) {
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'error', message: t('status.aliasError', { error: String(error) }) },
+      })
+    }
+  }, [mineAliases, dispatch, t])
+
+  // 選んだ別名候補を単語リストの読み込み元CSVに追加し、読み込み直したリストに置き換える
+  const handleAcceptAliases = useCallback(async (proposals: AliasProposal[]) => {
+    setAliasReport(null)
+    const configPath = configPathRef.current
+    if (!configPath) return
+
+    try {
+      const keys = [...new Set(proposals.map((p) => p.key))]
+      for (const key of keys) {
+        const wordList = await acceptAliases(key, proposals.filter((p) => p.key === key), configPath)
+        setWordLists((prev) => ({ ...prev, [key]: wordList }))
+      }
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'success', message: t('status.aliasesAccepted', { count: proposals.length }) },
+      })
+    } catch (error) {
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'error', message: t('status.aliasError', { error: String(error) }) },
+      })
+    }
+  }, [acceptAliases, dispatch, t])
+
+  // 単語リストが設定されている分別先（キー順）
+  const wordListKeys = (['1', '2', '3', '4', '5', '6', '7', '8', '9', '0'] as const).filter(
+    (key) => wordLists[key]
+  )
+
+  // 表示中の画像のマッチング過程を表示する（キー未指定時は提案中の分別先、なければ最初の単語リスト）
+  const handleExplainMatch = useCallback(async (key?: string) => {
+    const configPath = configPathRef.current
+    const slotKey = key ?? suggestion?.key ?? wordListKeys[0]
+    if (!currentPath || !configPath) return
+    if (!slotKey) {
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'warning', message: t('status.explainNoWordList') },
+      })
+      return
+    }
+
+    try {
+      setExplainState({ key: slotKey, explanation: await explainMatch(currentPath, slotKey, configPath) })
+    } catch (error) {
+      dispatch({
+        type: 'SET_STATUS',
+        payload: { status: 'error', message: t('status.explainError', { error: String(error) }) },
+      })
+    }
+  }, [currentPath, suggestion, wordListKeys, explainMatch, dispatch, t])
+
   useKeyboard({
     onMove: handleMove,
+    onConfirmSuggestion: () => {
+      if (suggestion) handleMove(suggestion.key)
+    },
+    onExplainMatch: () => handleExplainMatch(),
+    onToggleInfo: () => setShowInfo((prev) => !prev),
     onNavigate: handleNavigate,
     onToggleFullscreen: handleToggleFullscreen,
     onOpenSettings: handleOpenSettings,
@@ -670,6 +1166,14 @@ function AppContent() {
   })
 
   const displayIndex = state.images.length > 0 ? state.currentIndex + 1 : 0
+  // 提案の根拠（単語リストはマッチした項目名、学習は確信度）
+  const suggestionLabel = (s: DestinationSuggestion) =>
+    s.origin === 'learned'
+      ? t('destButton.learned', { confidence: Math.round((s.confidence ?? 0) * 100) })
+      : s.canonical ?? ''
+
+  // タグモードではフォルダ構成を変えないため、自動仕分け（移動）は行わない
+  const isTagMode = settingsRef.current?.operationMode === 'tag'
 
   return (
     <div className="app" onDrop={handleDrop} onDragOver={handleDragOver}>
@@ -687,12 +1191,42 @@ function AppContent() {
         onClearDestination={(key) => dispatch({ type: 'SET_DESTINATION', payload: { key, path: null } })}
         onLoadWordList={handleLoadWordList}
         onClearWordList={handleClearWordList}
+        onCheckWordList={handleCheckWordList}
+        onExportClassifier={handleExportClassifier}
+        onResetClassifier={handleResetClassifier}
+      />
+      <AutoSortModal
+        plan={autoSortPlan}
+        onRun={handleRunAutoSort}
+        onClose={() => setAutoSortPlan(null)}
+        onMineAliases={handleMineAliases}
+      />
+      <AliasModal
+        report={aliasReport}
+        onAccept={handleAcceptAliases}
+        onClose={() => setAliasReport(null)}
+      />
+      <WordListReportModal
+        report={wordListReport}
+        onDedupe={handleDedupeWordList}
+        onMerge={handleMergeWordList}
+        onClose={() => setWordListReport(null)}
+      />
+      <ExplainModal
+        explanation={explainState?.explanation ?? null}
+        slotKey={explainState?.key ?? ''}
+        slotKeys={wordListKeys}
+        onSelectSlot={handleExplainMatch}
+        onClose={() => setExplainState(null)}
       />
       <Header
         title="picSort"
         sourcePath={state.sourceFolder}
         onSelectFolder={handleSelectFolder}
         onOpenSettings={handleOpenSettings}
+        onAutoSort={handlePlanAutoSort}
+        canAutoSort={state.images.length > 0 && !isTagMode}
+        autoSortHint={isTagMode ? t('header.autoSortTagMode') : undefined}
         canUndo={canUndo}
         canRedo={canRedo}
         onUndo={handleUndo}
@@ -710,6 +1244,23 @@ function AppContent() {
               onIsVideoChange={setIsVideo}
             />
 
+            {currentImage && (
+              <RatingBar
+                rating={currentImage.rating ?? 0}
+                label={currentImage.label ?? null}
+                onRate={handleRate}
+                onLabel={handleLabel}
+                disabled={currentImage.rating === undefined}
+              />
+            )}
+
+            {currentImage && showInfo && (
+              <MetadataPanel
+                exif={metadata?.path === currentImage.path ? metadata.exif : null}
+                ai={metadata?.path === currentImage.path ? metadata.ai : null}
+              />
+            )}
+
             {state.images.length > 0 && (
               <div className="nav-buttons">
                 <button
@@ -734,6 +1285,8 @@ function AppContent() {
             onSelect={handleSelectImage}
             onDoubleClick={handleDoubleClickThumbnail}
             thumbnailSize={200}
+            thumbnailOptions={settingsRef.current?.thumbnailOptions}
+            animatedPreviews={settingsRef.current?.animatedPreviews}
           />
         )}
       </main>
@@ -763,6 +1316,7 @@ function AppContent() {
               disabled={state.viewMode === 'single' && !currentImage}
               lastUsed={state.lastUsedDestination === key}
               matching={isMatching}
+              suggestion={suggestion?.key === key ? suggestionLabel(suggestion) : undefined}
             />
           )
         })}
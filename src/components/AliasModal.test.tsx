@@ -0,0 +1,60 @@
+import { describe, it, expect, vi } from 'vitest'
+import { render, screen, fireEvent } from '../test/testUtils'
+import { AliasModal } from './AliasModal'
+import type { AliasMiningReport } from '../types'
+
+const report: AliasMiningReport = {
+  proposals: [
+    {
+      key: '1',
+      type: 'character',
+      canonical: 'Aria Starlight',
+      alias: 'aria star',
+      aliasNormalized: 'ariastar',
+      nearestKeyNormalized: 'ariastarlight',
+      score: 0.72,
+      occurrences: 3,
+      exampleFiles: ['/inbox/aria_star_01.jpg'],
+    },
+    {
+      key: '2',
+      type: 'character',
+      canonical: 'Leon Blaze',
+      alias: 'leon blz',
+      aliasNormalized: 'leonblz',
+      nearestKeyNormalized: 'leonblaze',
+      score: 0.7,
+      occurrences: 2,
+      exampleFiles: ['/inbox/leon_blz_01.jpg'],
+    },
+  ],
+  frequentTerms: [
+    { text: 'sketch', normalized: 'sketch', occurrences: 4, exampleFiles: [] },
+  ],
+}
+
+describe('AliasModal', () => {
+  it('reportがnullの場合何も表示されない', () => {
+    render(<AliasModal report={null} onAccept={() => {}} onClose={() => {}} />)
+
+    expect(screen.queryByText('別名候補')).not.toBeInTheDocument()
+  })
+
+  it('別名候補と頻出語が表示される', () => {
+    render(<AliasModal report={report} onAccept={() => {}} onClose={() => {}} />)
+
+    expect(screen.getByText('aria star')).toBeInTheDocument()
+    expect(screen.getByText('スコア 0.72 / 3件')).toBeInTheDocument()
+    expect(screen.getByText('sketch')).toBeInTheDocument()
+  })
+
+  it('選択した候補だけをonAcceptに渡す', () => {
+    const onAccept = vi.fn()
+    render(<AliasModal report={report} onAccept={onAccept} onClose={() => {}} />)
+
+    fireEvent.click(screen.getAllByRole('checkbox')[1])
+    fireEvent.click(screen.getByText('1件を追加'))
+
+    expect(onAccept).toHaveBeenCalledWith([report.proposals[0]])
+  })
+})
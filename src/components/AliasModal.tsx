@@ -0,0 +1,103 @@
+import { useState } from 'react'
+import { useLanguage } from '../contexts/LanguageContext'
+import type { AliasMiningReport, AliasProposal } from '../types'
+import { getFileName } from '../utils/path'
+
+interface AliasModalProps {
+  report: AliasMiningReport | null
+  onAccept: (proposals: AliasProposal[]) => void
+  onClose: () => void
+}
+
+/**
+ * 別名候補の確認モーダル
+ * マッチしなかったファイル名から抽出した別名候補を選んで単語リストに追加する
+ */
+export function AliasModal({ report, onAccept, onClose }: AliasModalProps) {
+  if (!report) return null
+
+  return <AliasModalContent report={report} onAccept={onAccept} onClose={onClose} />
+}
+
+function AliasModalContent({
+  report,
+  onAccept,
+  onClose,
+}: AliasModalProps & { report: AliasMiningReport }) {
+  const { t } = useLanguage()
+  // 初期状態ではすべての候補を選択
+  const [selected, setSelected] = useState(() => report.proposals.map(() => true))
+
+  const toggle = (index: number) => {
+    setSelected((prev) => prev.map((checked, i) => (i === index ? !checked : checked)))
+  }
+  const accepted = report.proposals.filter((_, i) => selected[i])
+
+  return (
+    <div className="modal-overlay" onClick={onClose}>
+      <div className="modal-content alias-modal" onClick={(e) => e.stopPropagation()}>
+        <h2>{t('aliases.title')}</h2>
+
+        {report.proposals.length === 0 ? (
+          <p>{t('aliases.empty')}</p>
+        ) : (
+          <ul className="auto-sort-list">
+            {report.proposals.map((proposal, index) => (
+              <li key={`${proposal.key}:${proposal.canonical}:${proposal.alias}`}>
+                <label className="alias-proposal">
+                  <input
+                    type="checkbox"
+                    checked={selected[index]}
+                    onChange={() => toggle(index)}
+                  />
+                  <strong>{proposal.alias}</strong>
+                  <span>
+                    → {proposal.canonical} ({proposal.key})
+                  </span>
+                </label>
+                <span
+                  className="auto-sort-detail"
+                  title={proposal.exampleFiles.map(getFileName).join('\n')}
+                >
+                  {t('aliases.detail', {
+                    score: proposal.score.toFixed(2),
+                    count: proposal.occurrences,
+                  })}
+                </span>
+              </li>
+            ))}
+          </ul>
+        )}
+
+        {report.frequentTerms.length > 0 && (
+          <>
+            <h3>{t('aliases.frequentTerms', { count: report.frequentTerms.length })}</h3>
+            <ul className="auto-sort-list">
+              {report.frequentTerms.map((term) => (
+                <li key={term.normalized}>
+                  <span className="auto-sort-file">{term.text}</span>
+                  <span className="auto-sort-detail">
+                    {t('aliases.occurrences', { count: term.occurrences })}
+                  </span>
+                </li>
+              ))}
+            </ul>
+          </>
+        )}
+
+        <div className="modal-actions">
+          <button onClick={onClose} className="btn-secondary">
+            {t('aliases.cancel')}
+          </button>
+          <button
+            onClick={() => onAccept(accepted)}
+            className="btn-primary"
+            disabled={accepted.length === 0}
+          >
+            {t('aliases.accept', { count: accepted.length })}
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
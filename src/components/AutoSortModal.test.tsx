@@ -0,0 +1,72 @@
+import { describe, it, expect, vi } from 'vitest'
+import { render, screen, fireEvent } from '../test/testUtils'
+import { AutoSortModal } from './AutoSortModal'
+import type { AutoSortPlan } from '../types'
+
+const plan: AutoSortPlan = {
+  moves: [
+    {
+      sourcePath: '/inbox/aria_starlight_01.jpg',
+      key: '1',
+      destination: '/dest/1',
+      canonical: 'Aria Starlight',
+      rule: 'B',
+      source: 'fileName',
+    },
+  ],
+  skipped: [
+    { path: '/inbox/aria_starlight_leon_blaze.jpg', reason: 'ambiguous', keys: ['1', '2'] },
+  ],
+}
+
+describe('AutoSortModal', () => {
+  it('planがnullの場合何も表示されない', () => {
+    render(<AutoSortModal plan={null} onRun={() => {}} onClose={() => {}} />)
+
+    expect(screen.queryByText('自動仕分け')).not.toBeInTheDocument()
+  })
+
+  it('移動予定とスキップ理由が表示される', () => {
+    render(<AutoSortModal plan={plan} onRun={() => {}} onClose={() => {}} />)
+
+    expect(screen.getByText('aria_starlight_01.jpg')).toBeInTheDocument()
+    expect(screen.getByText('移動予定（1件）')).toBeInTheDocument()
+    expect(screen.getByText('複数の分別先にマッチ (1, 2)')).toBeInTheDocument()
+  })
+
+  it('実行ボタンで計画を渡してonRunが呼ばれる', () => {
+    const onRun = vi.fn()
+    render(<AutoSortModal plan={plan} onRun={onRun} onClose={() => {}} />)
+
+    fireEvent.click(screen.getByText('1件を移動'))
+    expect(onRun).toHaveBeenCalledWith(plan)
+  })
+
+  it('キャンセルでonCloseが呼ばれる', () => {
+    const onClose = vi.fn()
+    render(<AutoSortModal plan={plan} onRun={() => {}} onClose={onClose} />)
+
+    fireEvent.click(screen.getByText('キャンセル'))
+    expect(onClose).toHaveBeenCalledTimes(1)
+  })
+
+  it('マッチしなかったファイルから別名候補を探せる', () => {
+    const onMineAliases = vi.fn()
+    const withUnmatched: AutoSortPlan = {
+      ...plan,
+      skipped: [...plan.skipped, { path: '/inbox/aria_star_01.jpg', reason: 'unmatched', keys: [] }],
+    }
+    render(
+      <AutoSortModal
+        plan={withUnmatched}
+        onRun={() => {}}
+        onClose={() => {}}
+        onMineAliases={onMineAliases}
+      />
+    )
+
+    fireEvent.click(screen.getByText('マッチしなかった1件から別名候補を探す'))
+
+    expect(onMineAliases).toHaveBeenCalledWith(['/inbox/aria_star_01.jpg'])
+  })
+})
@@ -0,0 +1,79 @@
+import { useLanguage } from '../contexts/LanguageContext'
+import type { AutoSortPlan } from '../types'
+import { getFileName } from '../utils/path'
+
+interface AutoSortModalProps {
+  plan: AutoSortPlan | null
+  onRun: (plan: AutoSortPlan) => void
+  onClose: () => void
+  onMineAliases?: (paths: string[]) => void
+}
+
+/**
+ * 自動仕分け計画の確認モーダル
+ * 移動予定とスキップ理由を表示し、確認後に実行する
+ * マッチしなかったファイルからは単語リストの別名候補を探せる
+ */
+export function AutoSortModal({ plan, onRun, onClose, onMineAliases }: AutoSortModalProps) {
+  const { t } = useLanguage()
+
+  if (!plan) return null
+
+  const unmatched = plan.skipped.filter((s) => s.reason === 'unmatched').map((s) => s.path)
+
+  return (
+    <div className="modal-overlay" onClick={onClose}>
+      <div className="modal-content auto-sort-modal" onClick={(e) => e.stopPropagation()}>
+        <h2>{t('autoSort.title')}</h2>
+
+        <h3>{t('autoSort.moves', { count: plan.moves.length })}</h3>
+        <ul className="auto-sort-list">
+          {plan.moves.map((move) => (
+            <li key={move.sourcePath}>
+              <span className="auto-sort-file">{getFileName(move.sourcePath)}</span>
+              <strong>
+                → {[move.key, ...(move.extraKeys ?? [])].join(', ')}
+              </strong>
+              <span className="auto-sort-detail">{move.canonical}</span>
+            </li>
+          ))}
+        </ul>
+
+        {plan.skipped.length > 0 && (
+          <>
+            <h3>{t('autoSort.skipped', { count: plan.skipped.length })}</h3>
+            <ul className="auto-sort-list">
+              {plan.skipped.map((skipped) => (
+                <li key={skipped.path}>
+                  <span className="auto-sort-file">{getFileName(skipped.path)}</span>
+                  <span className="auto-sort-detail">
+                    {t(`autoSort.reason.${skipped.reason}`)}
+                    {skipped.keys.length > 0 && ` (${skipped.keys.join(', ')})`}
+                  </span>
+                </li>
+              ))}
+            </ul>
+            {onMineAliases && unmatched.length > 0 && (
+              <button onClick={() => onMineAliases(unmatched)} className="btn-secondary">
+                {t('autoSort.mineAliases', { count: unmatched.length })}
+              </button>
+            )}
+          </>
+        )}
+
+        <div className="modal-actions">
+          <button onClick={onClose} className="btn-secondary">
+            {t('autoSort.cancel')}
+          </button>
+          <button
+            onClick={() => onRun(plan)}
+            className="btn-primary"
+            disabled={plan.moves.length === 0}
+          >
+            {t('autoSort.run', { count: plan.moves.length })}
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
@@ -89,4 +89,13 @@ describe('DestButton', () => {
     fireEvent.contextMenu(button)
     expect(onClear).not.toHaveBeenCalled()
   })
+
+  it('提案された分別先は根拠とともに強調表示される', () => {
+    render(
+      <DestButton keyNum="1" path="/dest/aria" onSelect={() => {}} suggestion="Aria Starlight" />
+    )
+
+    expect(screen.getByRole('button')).toHaveClass('suggested')
+    expect(screen.getByText('Aria Starlight')).toBeInTheDocument()
+  })
 })
@@ -12,6 +12,7 @@ interface DestButtonProps {
   active?: boolean
   lastUsed?: boolean
   matching?: boolean
+  suggestion?: string // 提案された分別先の場合の根拠（マッチした項目名・学習の確信度）
 }
 
 export function DestButton({
@@ -24,6 +25,7 @@ export function DestButton({
   active = false,
   lastUsed = false,
   matching = false,
+  suggestion,
 }: DestButtonProps) {
   const { t } = useLanguage()
   const folderName = path ? getFileName(path) : t('destButton.notSet')
@@ -57,11 +59,13 @@ export function DestButton({
   if (active) classNames.push('active')
   if (lastUsed) classNames.push('last-used')
   if (matching) classNames.push('matching')
+  if (suggestion !== undefined) classNames.push('suggested')
 
   // ツールチップテキスト
-  const tooltip = path
-    ? `${path}\n(${t('destButton.rightClickToClear')})`
-    : t('destButton.clickToSelect')
+  const tooltip = [
+    path ? `${path}\n(${t('destButton.rightClickToClear')})` : t('destButton.clickToSelect'),
+    ...(suggestion !== undefined ? [t('destButton.suggestedHint')] : []),
+  ].join('\n')
 
   return (
     <button
@@ -73,6 +77,7 @@ export function DestButton({
     >
       <span className="dest-button-key">{keyNum}</span>
       <span className="dest-button-name">{folderName}</span>
+      {suggestion && <span className="dest-button-suggestion">{suggestion}</span>}
       {lastUsed && <span className="dest-button-indicator">●</span>}
     </button>
   )
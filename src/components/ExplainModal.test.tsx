@@ -0,0 +1,106 @@
+import { describe, it, expect, vi } from 'vitest'
+import { render, screen, fireEvent } from '../test/testUtils'
+import { ExplainModal } from './ExplainModal'
+import type { MatchExplanation } from '../types'
+
+const explanation: MatchExplanation = {
+  filePath: '/inbox/aria_star_01.jpg',
+  targets: [
+    {
+      source: 'fileName',
+      rawText: 'aria_star_01',
+      stages: [{ name: 'nfkc', output: 'aria_star_01' }],
+      normalized: 'ariastar01',
+      entities: [
+        {
+          entityId: 'c1',
+          type: 'character',
+          canonical: 'Aria Starlight',
+          rules: [
+            {
+              rule: 'D',
+              matched: true,
+              keyNormalized: 'ariastarlight',
+              derived: false,
+              score: { ngramSimilarity: 0.7, lengthBonus: 0.05, score: 0.75 },
+            },
+          ],
+          appliedRule: 'D',
+        },
+      ],
+    },
+  ],
+  result: {
+    filePath: '/inbox/aria_star_01.jpg',
+    targetText: 'aria_star_01',
+    targetNormalized: 'ariastar01',
+    confirmed: [],
+    candidates: [
+      {
+        entityId: 'c1',
+        type: 'character',
+        canonical: 'Aria Starlight',
+        matchedKey: 'Aria Starlight',
+        matchedKeyNormalized: 'ariastarlight',
+        rule: 'D',
+        score: 0.75,
+        source: 'fileName',
+        derived: false,
+      },
+    ],
+    unmatched: false,
+  },
+  dropped: [],
+}
+
+describe('ExplainModal', () => {
+  it('explanationがnullの場合何も表示されない', () => {
+    render(
+      <ExplainModal
+        explanation={null}
+        slotKey="1"
+        slotKeys={['1']}
+        onSelectSlot={() => {}}
+        onClose={() => {}}
+      />
+    )
+
+    expect(screen.queryByText('閉じる')).not.toBeInTheDocument()
+  })
+
+  it('正規化の段階と適用されたルールのスコアが表示される', () => {
+    render(
+      <ExplainModal
+        explanation={explanation}
+        slotKey="1"
+        slotKeys={['1']}
+        onSelectSlot={() => {}}
+        onClose={() => {}}
+      />
+    )
+
+    expect(screen.getByText('マッチング過程: aria_star_01.jpg')).toBeInTheDocument()
+    expect(screen.getByText('ファイル名')).toBeInTheDocument()
+    expect(
+      screen.getByText('ルールD: ariastarlight （n-gram 0.70 + 長さ 0.05 = 0.75）')
+    ).toBeInTheDocument()
+    expect(screen.getByText('候補（1件）')).toBeInTheDocument()
+  })
+
+  it('分別先を切り替えるとonSelectSlotが呼ばれる', () => {
+    const onSelectSlot = vi.fn()
+    render(
+      <ExplainModal
+        explanation={explanation}
+        slotKey="1"
+        slotKeys={['1', '3']}
+        onSelectSlot={onSelectSlot}
+        onClose={() => {}}
+      />
+    )
+
+    fireEvent.click(screen.getByText('3'))
+
+    expect(onSelectSlot).toHaveBeenCalledWith('3')
+  })
+})
@@ -0,0 +1,121 @@
+import { useLanguage } from '../contexts/LanguageContext'
+import type { ExplainedMatchItem, MatchExplanation } from '../types'
+import { getFileName } from '../utils/path'
+
+interface ExplainModalProps {
+  explanation: MatchExplanation | null
+  slotKey: string
+  slotKeys: string[]
+  onSelectSlot: (key: string) => void
+  onClose: () => void
+}
+
+/**
+ * マッチング過程の確認モーダル（デバッグ用）
+ * ターゲット文字列の正規化の各段階、項目ごとに適用されたルールとスコア、
+ * 競合解決で除外された理由を表示する
+ */
+export function ExplainModal({
+  explanation,
+  slotKey,
+  slotKeys,
+  onSelectSlot,
+  onClose,
+}: ExplainModalProps) {
+  const { t } = useLanguage()
+
+  if (!explanation) return null
+
+  const renderItem = (item: ExplainedMatchItem) => (
+    <li key={`${item.entityId}:${item.matchedKeyNormalized}`}>
+      <span className="auto-sort-file">{item.canonical}</span>
+      <span className="auto-sort-detail">
+        {t('explain.item', {
+          rule: item.rule,
+          score: item.score.toFixed(2),
+          key: item.matchedKey,
+        })}
+        {item.debug?.resolution && ` — ${t(`explain.resolution.${item.debug.resolution}`)}`}
+      </span>
+    </li>
+  )
+
+  return (
+    <div className="modal-overlay" onClick={onClose}>
+      <div className="modal-content explain-modal" onClick={(e) => e.stopPropagation()}>
+        <h2>{t('explain.title', { file: getFileName(explanation.filePath) })}</h2>
+
+        <div className="explain-slots">
+          {slotKeys.map((key) => (
+            <button
+              key={key}
+              className={key === slotKey ? 'btn-primary' : 'btn-secondary'}
+              onClick={() => onSelectSlot(key)}
+            >
+              {key}
+            </button>
+          ))}
+        </div>
+
+        {explanation.targets.map((target, index) => (
+          <section key={index}>
+            <h3>{t(`explain.source.${target.source}`)}</h3>
+            <ol className="explain-stages">
+              <li>
+                <code>{target.rawText}</code>
+              </li>
+              {target.stages.map((stage) => (
+                <li key={stage.name}>
+                  {stage.name}: <code>{stage.output}</code>
+                </li>
+              ))}
+            </ol>
+            <ul className="auto-sort-list">
+              {target.entities
+                .filter((entity) => entity.appliedRule)
+                .map((entity) => {
+                  const applied = entity.rules.find((r) => r.rule === entity.appliedRule)
+                  return (
+                    <li key={entity.entityId}>
+                      <span className="auto-sort-file">{entity.canonical}</span>
+                      <span className="auto-sort-detail">
+                        {t('explain.rule', {
+                          rule: entity.appliedRule ?? '',
+                          key: applied?.keyNormalized ?? '',
+                        })}
+                        {applied?.score &&
+                          ` ${t('explain.score', {
+                            ngram: applied.score.ngramSimilarity.toFixed(2),
+                            bonus: applied.score.lengthBonus.toFixed(2),
+                            score: applied.score.score.toFixed(2),
+                          })}`}
+                      </span>
+                    </li>
+                  )
+                })}
+            </ul>
+          </section>
+        ))}
+
+        <h3>{t('explain.confirmed', { count: explanation.result.confirmed.length })}</h3>
+        <ul className="auto-sort-list">{explanation.result.confirmed.map(renderItem)}</ul>
+
+        <h3>{t('explain.candidates', { count: explanation.result.candidates.length })}</h3>
+        <ul className="auto-sort-list">{explanation.result.candidates.map(renderItem)}</ul>
+
+        {explanation.dropped.length > 0 && (
+          <>
+            <h3>{t('explain.dropped', { count: explanation.dropped.length })}</h3>
+            <ul className="auto-sort-list">{explanation.dropped.map(renderItem)}</ul>
+          </>
+        )}
+
+        <div className="modal-actions">
+          <button onClick={onClose} className="btn-secondary">
+            {t('explain.close')}
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
@@ -5,6 +5,9 @@ interface HeaderProps {
   sourcePath: string | null
   onSelectFolder: () => void
   onOpenSettings: () => void
+  onAutoSort: () => void
+  canAutoSort: boolean
+  autoSortHint?: string
   canUndo: boolean
   canRedo: boolean
   onUndo: () => void
@@ -26,6 +29,9 @@ export function Header({
   sourcePath,
   onSelectFolder,
   onOpenSettings,
+  onAutoSort,
+  canAutoSort,
+  autoSortHint,
   canUndo,
   canRedo,
   onUndo,
@@ -64,6 +70,14 @@ export function Header({
             ↷
           </button>
         </div>
+        <button
+          onClick={onAutoSort}
+          disabled={!canAutoSort}
+          className="btn-select-folder"
+          title={autoSortHint}
+        >
+          {t('header.autoSort')}
+        </button>
         <button onClick={onSelectFolder} className="btn-select-folder">
           {t('header.selectFolder')}
         </button>
@@ -0,0 +1,63 @@
+import { describe, it, expect } from 'vitest'
+import { render, screen } from '../test/testUtils'
+import { MetadataPanel } from './MetadataPanel'
+import type { AiMetadata, ExifMetadata } from '../types'
+
+const exif: ExifMetadata = {
+  make: 'Canon',
+  model: 'EOS R5',
+  lensMake: null,
+  lensModel: 'RF24-70mm F2.8 L IS USM',
+  exposureTime: '1/250',
+  fNumber: 2.8,
+  iso: 400,
+  focalLength: 50,
+  focalLength35mm: null,
+  capturedAt: '2024-05-01T10:20:30',
+  timeOffset: '+09:00',
+  orientation: 1,
+  width: 8192,
+  height: 5464,
+  gps: null,
+  software: null,
+}
+
+const ai: AiMetadata = {
+  generator: 'a1111',
+  prompt: 'aria starlight, 1girl',
+  negativePrompt: null,
+  model: 'animeModel_v3',
+  seed: 12345,
+  sampler: 'Euler a',
+  steps: 28,
+  cfgScale: 7,
+  loras: ['aria_v1'],
+}
+
+describe('MetadataPanel', () => {
+  it('値のある撮影情報だけが表示される', () => {
+    render(<MetadataPanel exif={exif} ai={null} />)
+
+    expect(screen.getByText('Canon EOS R5')).toBeInTheDocument()
+    expect(screen.getByText('1/250s')).toBeInTheDocument()
+    expect(screen.getByText('f/2.8')).toBeInTheDocument()
+    expect(screen.getByText('2024-05-01T10:20:30 +09:00')).toBeInTheDocument()
+    expect(screen.getByText('8192 × 5464')).toBeInTheDocument()
+    expect(screen.queryByText('位置')).not.toBeInTheDocument()
+  })
+
+  it('メタデータがない場合はその旨が表示される', () => {
+    render(<MetadataPanel exif={null} ai={null} />)
+
+    expect(screen.getByText('メタデータがありません')).toBeInTheDocument()
+  })
+
+  it('生成パラメータが表示される', () => {
+    render(<MetadataPanel exif={null} ai={ai} />)
+
+    expect(screen.getByText('Stable Diffusion web UI')).toBeInTheDocument()
+    expect(screen.getByText('aria starlight, 1girl')).toBeInTheDocument()
+    expect(screen.getByText('Euler a 28ステップ CFG 7 シード 12345')).toBeInTheDocument()
+    expect(screen.queryByText('ネガティブプロンプト')).not.toBeInTheDocument()
+  })
+})
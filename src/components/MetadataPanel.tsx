@@ -0,0 +1,81 @@
+import { useLanguage } from '../contexts/LanguageContext'
+import type { AiMetadata, ExifMetadata } from '../types'
+
+interface MetadataPanelProps {
+  exif: ExifMetadata | null
+  ai: AiMetadata | null
+}
+
+const join = (...parts: (string | null)[]) => parts.filter(Boolean).join(' ') || null
+
+/**
+ * 表示中の画像のメタデータ（EXIF の撮影情報と画像生成AIの生成パラメータ）
+ * 値のない項目は表示しない
+ */
+export function MetadataPanel({ exif, ai }: MetadataPanelProps) {
+  const { t } = useLanguage()
+
+  const rows: [string, string | null][] = exif
+    ? [
+        ['camera', join(exif.make, exif.model)],
+        ['lens', join(exif.lensMake, exif.lensModel)],
+        ['exposureTime', exif.exposureTime && `${exif.exposureTime}s`],
+        ['fNumber', exif.fNumber !== null ? `f/${exif.fNumber}` : null],
+        ['iso', exif.iso !== null ? `ISO ${exif.iso}` : null],
+        [
+          'focalLength',
+          exif.focalLength !== null
+            ? exif.focalLength35mm !== null
+              ? t('metadata.focalLength35mm', { mm: exif.focalLength, mm35: exif.focalLength35mm })
+              : `${exif.focalLength}mm`
+            : null,
+        ],
+        ['capturedAt', join(exif.capturedAt, exif.timeOffset)],
+        [
+          'size',
+          exif.width !== null && exif.height !== null ? `${exif.width} × ${exif.height}` : null,
+        ],
+        [
+          'gps',
+          exif.gps ? `${exif.gps.latitude.toFixed(5)}, ${exif.gps.longitude.toFixed(5)}` : null,
+        ],
+        ['software', exif.software],
+      ]
+    : []
+  const aiRows: [string, string | null][] = ai
+    ? [
+        ['generator', t(`metadata.generators.${ai.generator}`)],
+        ['model', ai.model],
+        ['prompt', ai.prompt],
+        ['negativePrompt', ai.negativePrompt],
+        ['loras', ai.loras.join(', ') || null],
+        [
+          'parameters',
+          join(
+            ai.sampler,
+            ai.steps !== null ? t('metadata.steps', { steps: ai.steps }) : null,
+            ai.cfgScale !== null ? `CFG ${ai.cfgScale}` : null,
+            ai.seed !== null ? t('metadata.seed', { seed: ai.seed }) : null
+          ),
+        ],
+      ]
+    : []
+  const shown = [...rows, ...aiRows].filter(([, value]) => value)
+
+  return (
+    <div className="metadata-panel">
+      {shown.length === 0 ? (
+        <p>{t('metadata.empty')}</p>
+      ) : (
+        <dl>
+          {shown.map(([field, value]) => (
+            <div key={field} className="metadata-row">
+              <dt>{t(`metadata.${field}`)}</dt>
+              <dd>{value}</dd>
+            </div>
+          ))}
+        </dl>
+      )}
+    </div>
+  )
+}
@@ -0,0 +1,36 @@
+import { describe, it, expect, vi } from 'vitest'
+import { render, screen, fireEvent } from '../test/testUtils'
+import { RatingBar } from './RatingBar'
+
+describe('RatingBar', () => {
+  it('現在の評価と色ラベルが選択状態で表示される', () => {
+    render(<RatingBar rating={3} label="green" onRate={() => {}} onLabel={() => {}} />)
+
+    expect(screen.getByRole('button', { name: '★3' })).toHaveAttribute('aria-pressed', 'true')
+    expect(screen.getByRole('button', { name: '緑' })).toHaveAttribute('aria-pressed', 'true')
+  })
+
+  it('クリックした評価と色ラベルが渡される', () => {
+    const onRate = vi.fn()
+    const onLabel = vi.fn()
+    render(<RatingBar rating={0} label={null} onRate={onRate} onLabel={onLabel} />)
+
+    fireEvent.click(screen.getByRole('button', { name: '★4' }))
+    fireEvent.click(screen.getByRole('button', { name: '赤' }))
+
+    expect(onRate).toHaveBeenCalledWith(4)
+    expect(onLabel).toHaveBeenCalledWith('red')
+  })
+
+  it('選択中の評価と色ラベルをクリックすると解除する', () => {
+    const onRate = vi.fn()
+    const onLabel = vi.fn()
+    render(<RatingBar rating={2} label="blue" onRate={onRate} onLabel={onLabel} />)
+
+    fireEvent.click(screen.getByRole('button', { name: '★2' }))
+    fireEvent.click(screen.getByRole('button', { name: '青' }))
+
+    expect(onRate).toHaveBeenCalledWith(0)
+    expect(onLabel).toHaveBeenCalledWith(null)
+  })
+})
@@ -0,0 +1,52 @@
+import { useLanguage } from '../contexts/LanguageContext'
+import type { ColorLabel } from '../types'
+
+const RATINGS = [1, 2, 3, 4, 5] as const
+const LABELS: ColorLabel[] = ['red', 'yellow', 'green', 'blue', 'purple']
+
+interface RatingBarProps {
+  rating: number
+  label: ColorLabel | null
+  onRate: (rating: number) => void
+  onLabel: (label: ColorLabel | null) => void
+  disabled?: boolean
+}
+
+/**
+ * 表示中の画像の評価と色ラベル
+ * 選択中の評価・色ラベルをもう一度クリックすると解除する
+ */
+export function RatingBar({ rating, label, onRate, onLabel, disabled = false }: RatingBarProps) {
+  const { t } = useLanguage()
+
+  return (
+    <div className="rating-bar">
+      <div className="rating-stars" role="group" aria-label={t('rating.title')}>
+        {RATINGS.map((value) => (
+          <button
+            key={value}
+            className={value <= rating ? 'rating-star active' : 'rating-star'}
+            onClick={() => onRate(value === rating ? 0 : value)}
+            disabled={disabled}
+            aria-label={t('rating.stars', { count: value })}
+            aria-pressed={value === rating}
+          >
+            {value <= rating ? '★' : '☆'}
+          </button>
+        ))}
+      </div>
+      <div className="rating-labels" role="group" aria-label={t('rating.labelTitle')}>
+        {LABELS.map((value) => (
+          <button
+            key={value}
+            className={`rating-label rating-label-${value}${value === label ? ' active' : ''}`}
+            onClick={() => onLabel(value === label ? null : value)}
+            disabled={disabled}
+            aria-label={t(`rating.labels.${value}`)}
+            aria-pressed={value === label}
+          />
+        ))}
+      </div>
+    </div>
+  )
+}
@@ -11,10 +11,16 @@ interface SettingsModalProps {
   onClearDestination: (key: string) => void
   onLoadWordList: (key: string) => void
   onClearWordList: (key: string) => void
+  onCheckWordList: (key: string) => void
+  onExportClassifier: () => void
+  onResetClassifier: () => void
 }
 
 const KEYBINDING_KEYS = [
   { key: '1-0', descKey: 'shortcuts.moveToDestination' },
+  { key: 'Enter', descKey: 'shortcuts.confirmSuggestion' },
+  { key: 'E', descKey: 'shortcuts.explainMatch' },
+  { key: 'I', descKey: 'shortcuts.toggleInfo' },
   { key: '← / A / P', descKey: 'shortcuts.prevImage' },
   { key: '→ / D / N', descKey: 'shortcuts.nextImage' },
   { key: 'Backspace', descKey: 'shortcuts.prevImage' },
@@ -47,6 +53,9 @@ export function SettingsModal({
   onClearDestination,
   onLoadWordList,
   onClearWordList,
+  onCheckWordList,
+  onExportClassifier,
+  onResetClassifier,
 }: SettingsModalProps) {
   const { theme, setTheme } = useTheme()
   const { language, setLanguage, t } = useLanguage()
@@ -141,6 +150,15 @@ export function SettingsModal({
                   >
                     📄
                   </button>
+                  {wordLists[key] && (
+                    <button
+                      className="btn-wordlist-check"
+                      onClick={() => onCheckWordList(key)}
+                      title={t('settings.checkWordList')}
+                    >
+                      🔍
+                    </button>
+                  )}
                   {wordLists[key] && (
                     <button
                       className="btn-wordlist-clear"
@@ -156,6 +174,19 @@ export function SettingsModal({
           </div>
         </section>
 
+        <section className="settings-section">
+          <h3>{t('settings.classifier')}</h3>
+          <div className="settings-classifier">
+            <button className="btn-secondary" onClick={onExportClassifier}>
+              {t('settings.exportClassifier')}
+            </button>
+            <button className="btn-secondary" onClick={onResetClassifier}>
+              {t('settings.resetClassifier')}
+            </button>
+          </div>
+          <p className="settings-hint">{t('settings.classifierHint')}</p>
+        </section>
+
         <section className="settings-section">
           <h3>{t('settings.shortcuts')}</h3>
           <div className="settings-keybindings">
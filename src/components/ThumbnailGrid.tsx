@@ -1,8 +1,8 @@
-import React, { useRef, useMemo, useState, useEffect } from 'react'
+import React, { useRef, useMemo, useState, useEffect, useCallback } from 'react'
 import { Grid as VirtualGrid, type CellComponentProps } from 'react-window'
 import { ThumbnailItem } from './ThumbnailItem'
 import { useThumbnails } from '../hooks/useThumbnails'
-import type { ImageInfo } from '../types'
+import type { AnimationLimits, ImageInfo, ThumbnailOptions } from '../types'
 
 interface ThumbnailGridProps {
   images: ImageInfo[]
@@ -10,11 +10,21 @@ interface ThumbnailGridProps {
   onSelect: (path: string, modifiers: { ctrl: boolean; shift: boolean }) => void
   onDoubleClick: (path: string) => void
   thumbnailSize?: number
+  thumbnailOptions?: ThumbnailOptions
+  animatedPreviews?: AnimationLimits | null
 }
 
 const GAP = 8
 const PADDING = 8
 
+// 描画中のセル範囲（overscan を含む）
+interface CellRange {
+  columnStartIndex: number
+  columnStopIndex: number
+  rowStartIndex: number
+  rowStopIndex: number
+}
+
 // cellProps で渡す追加データの型
 interface CellExtraProps {
   images: ImageInfo[]
@@ -25,6 +35,7 @@ interface CellExtraProps {
   columnCount: number
   requestThumbnail: (path: string) => Promise<string>
   getCachedThumbnail: (path: string) => string | null
+  requestAnimatedPreview: (path: string) => Promise<string | null>
 }
 
 // CellComponentProps<CellExtraProps> を使用した正しい型定義
@@ -42,6 +53,7 @@ function Cell({
   columnCount,
   requestThumbnail,
   getCachedThumbnail,
+  requestAnimatedPreview,
 }: CellComponentProps<CellExtraProps>): React.ReactElement {
   const index = rowIndex * columnCount + columnIndex
 
@@ -80,6 +92,7 @@ function Cell({
         thumbnailSize={thumbnailSize}
         requestThumbnail={requestThumbnail}
         getCachedThumbnail={getCachedThumbnail}
+        requestAnimatedPreview={requestAnimatedPreview}
         onClick={handleClick}
         onDoubleClick={handleDoubleClick}
       />
@@ -93,9 +106,12 @@ export function ThumbnailGrid({
   onSelect,
   onDoubleClick,
   thumbnailSize = 200,
+  thumbnailOptions,
+  animatedPreviews,
 }: ThumbnailGridProps) {
   const containerRef = useRef<HTMLDivElement>(null)
-  const { requestThumbnail, getCachedThumbnail } = useThumbnails(thumbnailSize)
+  const { requestThumbnail, getCachedThumbnail, requestAnimatedPreview, reprioritize } =
+    useThumbnails(thumbnailSize, thumbnailOptions, animatedPreviews)
 
   // 選択状態のSetを作成（高速ルックアップ）
   const selectedSet = useMemo(() => new Set(selectedPaths), [selectedPaths])
@@ -129,6 +145,21 @@ export function ThumbnailGrid({
 
   const rowCount = Math.ceil(images.length / columnCount)
 
+  // 描画中のセルのサムネイルを優先し、スクロールで外れたものの生成は取り消す
+  const handleCellsRendered = useCallback(
+    (_visibleCells: CellRange, allCells: CellRange) => {
+      const paths: string[] = []
+      for (let row = allCells.rowStartIndex; row <= allCells.rowStopIndex; row++) {
+        for (let col = allCells.columnStartIndex; col <= allCells.columnStopIndex; col++) {
+          const image = images[row * columnCount + col]
+          if (image) paths.push(image.path)
+        }
+      }
+      reprioritize(paths)
+    },
+    [images, columnCount, reprioritize]
+  )
+
   // cellProps に渡すデータ（予約propsは含めない）
   const cellProps: CellExtraProps = useMemo(() => ({
     images,
@@ -139,7 +170,8 @@ export function ThumbnailGrid({
     columnCount,
     requestThumbnail,
     getCachedThumbnail,
-  }), [images, selectedSet, onSelect, onDoubleClick, thumbnailSize, columnCount, requestThumbnail, getCachedThumbnail])
+    requestAnimatedPreview,
+  }), [images, selectedSet, onSelect, onDoubleClick, thumbnailSize, columnCount, requestThumbnail, getCachedThumbnail, requestAnimatedPreview])
 
   if (images.length === 0) {
     return (
@@ -161,6 +193,7 @@ export function ThumbnailGrid({
         overscanCount={2}
         cellComponent={Cell}
         cellProps={cellProps}
+        onCellsRendered={handleCellsRendered}
         style={{ width: gridSize.width, height: gridSize.height }}
       />
     </div>
@@ -7,6 +7,7 @@ interface ThumbnailItemProps {
   thumbnailSize: number
   requestThumbnail: (path: string) => Promise<string>
   getCachedThumbnail: (path: string) => string | null
+  requestAnimatedPreview: (path: string) => Promise<string | null>
   onClick: (e: React.MouseEvent) => void
   onDoubleClick: () => void
 }
@@ -17,6 +18,7 @@ export const ThumbnailItem = memo(function ThumbnailItem({
   thumbnailSize,
   requestThumbnail,
   getCachedThumbnail,
+  requestAnimatedPreview,
   onClick,
   onDoubleClick,
 }: ThumbnailItemProps) {
@@ -27,14 +29,33 @@ export const ThumbnailItem = memo(function ThumbnailItem({
   const [status, setStatus] = useState<'pending' | 'loading' | 'loaded' | 'error'>(
     thumbnailUrl ? 'loaded' : 'pending'
   )
+  // ホバー中に表示する動くプレビュー
+  const [hovered, setHovered] = useState(false)
+  const [animatedUrl, setAnimatedUrl] = useState<string | null>(null)
 
   // image.path が変わったら state をリセット（react-windowのセル再利用対策）
   useEffect(() => {
     const cached = getCachedThumbnail(image.path)
     setThumbnailUrl(cached)
     setStatus(cached ? 'loaded' : 'pending')
+    setHovered(false)
+    setAnimatedUrl(null)
   }, [image.path, getCachedThumbnail])
 
+  // ホバーされたときに初めて動くプレビューを要求する
+  useEffect(() => {
+    if (!hovered || animatedUrl) return
+
+    let cancelled = false
+    requestAnimatedPreview(image.path).then((url) => {
+      if (!cancelled) setAnimatedUrl(url)
+    })
+
+    return () => {
+      cancelled = true
+    }
+  }, [hovered, image.path, requestAnimatedPreview, animatedUrl])
+
   useEffect(() => {
     // 既にロード済みならスキップ
     if (thumbnailUrl) return
@@ -66,6 +87,8 @@ export const ThumbnailItem = memo(function ThumbnailItem({
       style={{ width: thumbnailSize, height: thumbnailSize }}
       onClick={onClick}
       onDoubleClick={onDoubleClick}
+      onMouseEnter={() => setHovered(true)}
+      onMouseLeave={() => setHovered(false)}
     >
       {/* 選択チェックボックス */}
       <div className="thumbnail-checkbox">
@@ -75,7 +98,7 @@ export const ThumbnailItem = memo(function ThumbnailItem({
       {/* サムネイル画像 */}
       {status === 'loaded' && thumbnailUrl ? (
         <img
-          src={thumbnailUrl}
+          src={hovered && animatedUrl ? animatedUrl : thumbnailUrl}
           alt={image.name}
           loading="lazy"
           draggable={false}
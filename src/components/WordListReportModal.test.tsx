@@ -0,0 +1,51 @@
+import { describe, it, expect, vi } from 'vitest'
+import { render, screen, fireEvent } from '../test/testUtils'
+import { WordListReportModal } from './WordListReportModal'
+import type { WordListReport } from '../types'
+
+const report: WordListReport = {
+  key: '1',
+  fileName: 'characters.csv',
+  issues: [
+    { kind: 'keyCollision', entityIds: ['character:Aria', 'character:Arianna'], key: 'aria' },
+    {
+      kind: 'duplicateCanonical',
+      entityIds: ['character:Leon'],
+      key: null,
+      resolution: 'unitedAliases',
+    },
+  ],
+}
+
+describe('WordListReportModal', () => {
+  it('reportがnullの場合何も表示されない', () => {
+    render(
+      <WordListReportModal report={null} onDedupe={() => {}} onMerge={() => {}} onClose={() => {}} />
+    )
+
+    expect(screen.queryByText('閉じる')).not.toBeInTheDocument()
+  })
+
+  it('問題の種類と解決方法が表示される', () => {
+    render(
+      <WordListReportModal report={report} onDedupe={() => {}} onMerge={() => {}} onClose={() => {}} />
+    )
+
+    expect(screen.getByText('単語リストの検査: 1 - characters.csv')).toBeInTheDocument()
+    expect(screen.getByText('検索キーの衝突')).toBeInTheDocument()
+    expect(screen.getByText('aria')).toBeInTheDocument()
+    expect(screen.getByText('character:Leon — 別名を合算')).toBeInTheDocument()
+  })
+
+  it('選んだ統合方法でonMergeが呼ばれる', () => {
+    const onMerge = vi.fn()
+    render(
+      <WordListReportModal report={report} onDedupe={() => {}} onMerge={onMerge} onClose={() => {}} />
+    )
+
+    fireEvent.change(screen.getByLabelText('統合方法'), { target: { value: 'keepBase' } })
+    fireEvent.click(screen.getByText('別のCSVと統合'))
+
+    expect(onMerge).toHaveBeenCalledWith('1', 'keepBase')
+  })
+})
@@ -0,0 +1,76 @@
+import { useState } from 'react'
+import { useLanguage } from '../contexts/LanguageContext'
+import type { MergeStrategy, WordListReport } from '../types'
+
+interface WordListReportModalProps {
+  report: WordListReport | null
+  onDedupe: (key: string) => void
+  onMerge: (key: string, strategy: MergeStrategy) => void
+  onClose: () => void
+}
+
+const MERGE_STRATEGIES: MergeStrategy[] = ['unionAliases', 'keepBase', 'preferIncoming']
+
+/**
+ * 単語リストの検査結果モーダル
+ * 検査・統合で見つかった問題を一覧し、重複の統合や別のCSVとの統合を行う
+ */
+export function WordListReportModal({
+  report,
+  onDedupe,
+  onMerge,
+  onClose,
+}: WordListReportModalProps) {
+  const { t } = useLanguage()
+  const [strategy, setStrategy] = useState<MergeStrategy>('unionAliases')
+
+  if (!report) return null
+
+  return (
+    <div className="modal-overlay" onClick={onClose}>
+      <div className="modal-content word-list-report" onClick={(e) => e.stopPropagation()}>
+        <h2>{t('wordListReport.title', { key: report.key, name: report.fileName })}</h2>
+
+        {report.issues.length === 0 ? (
+          <p>{t('wordListReport.empty')}</p>
+        ) : (
+          <ul className="auto-sort-list">
+            {report.issues.map((issue, index) => (
+              <li key={index}>
+                <span className="auto-sort-file">{t(`wordListReport.kind.${issue.kind}`)}</span>
+                <span className="auto-sort-detail">
+                  {issue.key && <code>{issue.key}</code>} {issue.entityIds.join(', ')}
+                  {'resolution' in issue &&
+                    ` — ${t(`wordListReport.resolution.${issue.resolution}`)}`}
+                </span>
+              </li>
+            ))}
+          </ul>
+        )}
+
+        <div className="modal-actions">
+          <button onClick={onClose} className="btn-secondary">
+            {t('wordListReport.close')}
+          </button>
+          <button onClick={() => onDedupe(report.key)} className="btn-secondary">
+            {t('wordListReport.dedupe')}
+          </button>
+          <select
+            value={strategy}
+            onChange={(e) => setStrategy(e.target.value as MergeStrategy)}
+            aria-label={t('wordListReport.strategy')}
+          >
+            {MERGE_STRATEGIES.map((s) => (
+              <option key={s} value={s}>
+                {t(`wordListReport.strategies.${s}`)}
+              </option>
+            ))}
+          </select>
+          <button onClick={() => onMerge(report.key, strategy)} className="btn-primary">
+            {t('wordListReport.merge')}
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
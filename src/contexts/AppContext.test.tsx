@@ -90,6 +90,34 @@ describe('AppContext', () => {
     expect(result.current.state.images[1].name).toBe('img3.jpg')
   })
 
+  it('SET_IMAGE_RATING で画像の評価と色ラベルが更新される', () => {
+    const { result } = renderHook(() => useApp(), { wrapper })
+
+    const images: ImageInfo[] = [
+      { path: '/path/img1.jpg', name: 'img1.jpg', rating: 2, label: 'red' },
+      { path: '/path/img2.jpg', name: 'img2.jpg' },
+    ]
+
+    act(() => {
+      result.current.dispatch({ type: 'SET_IMAGES', payload: images })
+    })
+
+    act(() => {
+      result.current.dispatch({
+        type: 'SET_IMAGE_RATING',
+        payload: { path: '/path/img1.jpg', rating: 5, label: null },
+      })
+    })
+
+    expect(result.current.state.images[0]).toEqual({
+      path: '/path/img1.jpg',
+      name: 'img1.jpg',
+      rating: 5,
+      label: undefined,
+    })
+    expect(result.current.state.images[1]).toEqual(images[1])
+  })
+
   it('SET_DESTINATION で分別先が設定される', () => {
     const { result } = renderHook(() => useApp(), { wrapper })
 
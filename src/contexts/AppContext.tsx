@@ -137,6 +137,16 @@ function appReducer(state: AppState, action: AppAction): AppState {
       }
     }
 
+    case 'SET_IMAGE_RATING': {
+      const { path, rating, label } = action.payload
+      return {
+        ...state,
+        images: state.images.map(img =>
+          img.path === path ? { ...img, rating, label: label ?? undefined } : img
+        ),
+      }
+    }
+
     case 'SET_DESTINATION':
       return {
         ...state,
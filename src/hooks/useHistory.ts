@@ -32,6 +32,7 @@ interface UseHistoryReturn {
   addToHistory: (item: Omit<MoveHistoryItem, 'id' | 'timestamp'>) => void
   undo: () => MoveHistoryItem | null
   redo: () => MoveHistoryItem | null
+  updateHistoryItem: (id: string, changes: Partial<Omit<MoveHistoryItem, 'id' | 'timestamp'>>) => void
   clearHistory: () => void
 }
 
@@ -109,6 +110,17 @@ export function useHistory(): UseHistoryReturn {
     return item
   }, [])
 
+  // Redo などで結果が変わった履歴を更新する
+  const updateHistoryItem = useCallback(
+    (id: string, changes: Partial<Omit<MoveHistoryItem, 'id' | 'timestamp'>>) => {
+      setState((prev) => ({
+        ...prev,
+        history: prev.history.map((item) => (item.id === id ? { ...item, ...changes } : item)),
+      }))
+    },
+    []
+  )
+
   const clearHistory = useCallback(() => {
     setState({ history: [], currentIndex: -1 })
   }, [])
@@ -123,6 +135,7 @@ export function useHistory(): UseHistoryReturn {
     addToHistory,
     undo,
     redo,
+    updateHistoryItem,
     clearHistory,
   }
 }
@@ -164,4 +164,59 @@ describe('useKeyboard', () => {
     expect(mockOnMove).not.toHaveBeenCalled()
     expect(mockOnNavigate).not.toHaveBeenCalled()
   })
+
+  it('EnterキーでonConfirmSuggestionが呼ばれる', () => {
+    const mockOnConfirmSuggestion = vi.fn()
+    renderHook(
+      () =>
+        useKeyboard({
+          onMove: mockOnMove,
+          onNavigate: mockOnNavigate,
+          onConfirmSuggestion: mockOnConfirmSuggestion,
+        }),
+      { wrapper }
+    )
+
+    window.dispatchEvent(new KeyboardEvent('keydown', { key: 'Enter' }))
+    window.dispatchEvent(new KeyboardEvent('keydown', { key: 'Enter', repeat: true }))
+
+    expect(mockOnConfirmSuggestion).toHaveBeenCalledTimes(1)
+    expect(mockOnMove).not.toHaveBeenCalled()
+  })
+
+  it('EキーでonExplainMatchが呼ばれる', () => {
+    const mockOnExplainMatch = vi.fn()
+    renderHook(
+      () =>
+        useKeyboard({
+          onMove: mockOnMove,
+          onNavigate: mockOnNavigate,
+          onExplainMatch: mockOnExplainMatch,
+        }),
+      { wrapper }
+    )
+
+    window.dispatchEvent(new KeyboardEvent('keydown', { key: 'e' }))
+    window.dispatchEvent(new KeyboardEvent('keydown', { key: 'E', repeat: true }))
+
+    expect(mockOnExplainMatch).toHaveBeenCalledTimes(1)
+  })
+
+  it('IキーでonToggleInfoが呼ばれる', () => {
+    const mockOnToggleInfo = vi.fn()
+    renderHook(
+      () =>
+        useKeyboard({
+          onMove: mockOnMove,
+          onNavigate: mockOnNavigate,
+          onToggleInfo: mockOnToggleInfo,
+        }),
+      { wrapper }
+    )
+
+    window.dispatchEvent(new KeyboardEvent('keydown', { key: 'i' }))
+    window.dispatchEvent(new KeyboardEvent('keydown', { key: 'I', repeat: true }))
+
+    expect(mockOnToggleInfo).toHaveBeenCalledTimes(1)
+  })
 })
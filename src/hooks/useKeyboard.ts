@@ -3,6 +3,9 @@ import type { ViewMode } from '../types'
 
 interface UseKeyboardOptions {
   onMove: (key: string) => void
+  onConfirmSuggestion?: () => void // 提案された分別先へ移動
+  onExplainMatch?: () => void // マッチング過程を表示
+  onToggleInfo?: () => void // メタデータの表示切替
   onNavigate: (direction: 'prev' | 'next') => void
   onToggleFullscreen?: () => void
   onOpenSettings?: () => void
@@ -20,6 +23,9 @@ interface UseKeyboardOptions {
 
 export function useKeyboard({
   onMove,
+  onConfirmSuggestion,
+  onExplainMatch,
+  onToggleInfo,
   onNavigate,
   onToggleFullscreen,
   onOpenSettings,
@@ -116,6 +122,27 @@ export function useKeyboard({
         return
       }
 
+      // Enter: 提案された分別先へ移動（リピート無効）
+      if (key === 'Enter') {
+        if (event.repeat) return
+        onConfirmSuggestion?.()
+        return
+      }
+
+      // E キー: マッチング過程を表示（リピート無効）
+      if (key === 'e' || key === 'E') {
+        if (event.repeat) return
+        onExplainMatch?.()
+        return
+      }
+
+      // I キー: メタデータの表示切替（リピート無効）
+      if (key === 'i' || key === 'I') {
+        if (event.repeat) return
+        onToggleInfo?.()
+        return
+      }
+
       // 矢印キー・A/D・スペース・Backspace: 画像移動
       // 動画再生中は矢印キーはMediaViewerが処理するのでスキップ
       if (key === 'ArrowLeft' || key === 'a' || key === 'A' || key === 'Backspace' || key === 'p' || key === 'P') {
@@ -147,7 +174,7 @@ export function useKeyboard({
         return
       }
     },
-    [onMove, onNavigate, onToggleFullscreen, onOpenSettings, onUndo, onRedo, isVideo, viewMode, selectedCount, onToggleViewMode, onSelectAll, onClearSelection, onMoveSelected]
+    [onMove, onConfirmSuggestion, onExplainMatch, onToggleInfo, onNavigate, onToggleFullscreen, onOpenSettings, onUndo, onRedo, isVideo, viewMode, selectedCount, onToggleViewMode, onSelectAll, onClearSelection, onMoveSelected]
   )
 
   useEffect(() => {
@@ -146,4 +146,19 @@ describe('useTauriCommands', () => {
       expect(logPath).toBe('/path/to/logs/picsort.log')
     })
   })
+
+  describe('importWordList', () => {
+    it('import_word_listコマンドを正しく呼び出す', async () => {
+      const wordList = { fileName: 'characters.csv', sourcePath: '/lists/characters.csv', entries: [] }
+      mockInvoke.mockResolvedValueOnce(wordList)
+
+      const { result } = renderHook(() => useTauriCommands())
+      const loaded = await result.current.importWordList('/lists/characters.csv')
+
+      expect(mockInvoke).toHaveBeenCalledWith('import_word_list', {
+        path: '/lists/characters.csv',
+      })
+      expect(loaded).toEqual(wordList)
+    })
+  })
 })
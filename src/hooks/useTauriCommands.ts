@@ -1,9 +1,45 @@
 import { invoke } from '@tauri-apps/api/core'
-import type { ImageInfo, Settings, ThumbnailResult, ThumbnailBatchResult } from '../types'
+import { listen } from '@tauri-apps/api/event'
+import type {
+  ImageInfo,
+  Settings,
+  ThumbnailResult,
+  ThumbnailBatchResult,
+  ThumbnailCacheStats,
+  ThumbnailBatchProgress,
+  ThumbnailOptions,
+  ThumbnailRequest,
+  AnimationLimits,
+  AnimatedPreview,
+  ReprioritizeSummary,
+  DestinationSuggestion,
+  AutoSortPlan,
+  AutoSortSummary,
+  AiMetadata,
+  LearnedSuggestion,
+  AliasMiningReport,
+  AliasAddition,
+  WordList,
+  LintIssue,
+  MergeStrategy,
+  MergeResult,
+  MatchExplanation,
+  FanOutMode,
+  FanOutResult,
+  TagResult,
+  XmpLocation,
+  FileTags,
+  TagChange,
+  ScanOptions,
+  ColorLabel,
+  RatingResult,
+  RatingLabel,
+  ExifMetadata,
+} from '../types'
 
 export function useTauriCommands() {
-  const scanImages = async (path: string): Promise<ImageInfo[]> => {
-    return await invoke<ImageInfo[]>('scan_images', { path })
+  const scanImages = async (path: string, options?: ScanOptions): Promise<ImageInfo[]> => {
+    return await invoke<ImageInfo[]>('scan_images', options ? { path, options } : { path })
   }
 
   const moveFile = async (src: string, destFolder: string): Promise<string> => {
@@ -14,6 +50,86 @@ export function useTauriCommands() {
     return await invoke<string>('undo_move', { currentPath, originalFolder })
   }
 
+  const fanOutFile = async (
+    src: string,
+    destFolder: string,
+    extraFolders: string[],
+    mode: FanOutMode
+  ): Promise<FanOutResult> => {
+    return await invoke<FanOutResult>('fan_out_file', { src, destFolder, extraFolders, mode })
+  }
+
+  const undoFanOut = async (
+    currentPath: string,
+    originalFolder: string,
+    extraPaths: string[]
+  ): Promise<string> => {
+    return await invoke<string>('undo_fan_out', { currentPath, originalFolder, extraPaths })
+  }
+
+  const tagFile = async (path: string, key: string, configPath: string): Promise<TagResult> => {
+    return await invoke<TagResult>('tag_file', { path, key, configPath })
+  }
+
+  const untagFile = async (
+    path: string,
+    keywords: string[],
+    location: XmpLocation,
+    sidecarCreated: boolean
+  ): Promise<string[]> => {
+    return await invoke<string[]>('untag_file', { path, keywords, location, sidecarCreated })
+  }
+
+  const readFileTags = async (path: string): Promise<FileTags> => {
+    return await invoke<FileTags>('read_file_tags', { path })
+  }
+
+  const writeFileTags = async (
+    path: string,
+    tags: string[],
+    attributes: Record<string, string>
+  ): Promise<TagChange> => {
+    return await invoke<TagChange>('write_file_tags', { path, tags, attributes })
+  }
+
+  const removeFileTags = async (
+    path: string,
+    tags: string[],
+    attributes: string[],
+    previous: Record<string, string> = {}
+  ): Promise<FileTags> => {
+    return await invoke<FileTags>('remove_file_tags', { path, tags, attributes, previous })
+  }
+
+  const recordSortTags = async (
+    path: string,
+    key: string,
+    sourceFolder: string,
+    configPath: string
+  ): Promise<TagChange> => {
+    return await invoke<TagChange>('record_sort_tags', { path, key, sourceFolder, configPath })
+  }
+
+  const setRating = async (
+    path: string,
+    rating: number,
+    location: XmpLocation
+  ): Promise<RatingResult> => {
+    return await invoke<RatingResult>('set_rating', { path, rating, location })
+  }
+
+  const setColorLabel = async (
+    path: string,
+    label: ColorLabel | null,
+    location: XmpLocation
+  ): Promise<RatingResult> => {
+    return await invoke<RatingResult>('set_color_label', { path, label, location })
+  }
+
+  const readRatingLabel = async (path: string): Promise<RatingLabel> => {
+    return await invoke<RatingLabel>('read_rating_label', { path })
+  }
+
   const loadSettings = async (configPath: string): Promise<Settings> => {
     return await invoke<Settings>('load_settings', { configPath })
   }
@@ -37,15 +153,71 @@ export function useTauriCommands() {
     return await invoke<string>('get_log_path')
   }
 
-  const generateThumbnail = async (path: string, size: number): Promise<ThumbnailResult> => {
-    return await invoke<ThumbnailResult>('generate_thumbnail', { path, size })
+  const generateThumbnail = async (
+    path: string,
+    size: number,
+    request: ThumbnailRequest = {}
+  ): Promise<ThumbnailResult> => {
+    return await invoke<ThumbnailResult>('generate_thumbnail', { path, size, ...request })
   }
 
   const generateThumbnailsBatch = async (
     paths: string[],
-    size: number
+    size: number,
+    onProgress?: (progress: ThumbnailBatchProgress) => void,
+    batchId?: string,
+    options?: ThumbnailOptions
   ): Promise<ThumbnailBatchResult> => {
-    return await invoke<ThumbnailBatchResult>('generate_thumbnails_batch', { paths, size })
+    // 1件完了するごとに進捗を受け取る（batchId を指定した場合はそのバッチの進捗のみ）
+    const unlisten = onProgress
+      ? await listen<ThumbnailBatchProgress>('thumbnail-batch-progress', (event) => {
+          if (!batchId || event.payload.batchId === batchId) {
+            onProgress(event.payload)
+          }
+        })
+      : null
+    try {
+      return await invoke<ThumbnailBatchResult>('generate_thumbnails_batch', {
+        paths,
+        size,
+        ...(batchId ? { batchId } : {}),
+        ...(options ? { options } : {}),
+      })
+    } finally {
+      unlisten?.()
+    }
+  }
+
+  const generateAnimatedPreview = async (
+    path: string,
+    size: number,
+    limits?: AnimationLimits,
+    requestId?: string
+  ): Promise<AnimatedPreview | null> => {
+    return await invoke<AnimatedPreview | null>('generate_animated_preview', {
+      path,
+      size,
+      ...(limits ? { limits } : {}),
+      ...(requestId ? { requestId } : {}),
+    })
+  }
+
+  const cancelThumbnailBatch = async (batchId: string): Promise<boolean> => {
+    return await invoke<boolean>('cancel_thumbnail_batch', { batchId })
+  }
+
+  const cancelThumbnailRequest = async (requestId: string): Promise<boolean> => {
+    return await invoke<boolean>('cancel_thumbnail_request', { requestId })
+  }
+
+  const reprioritizeThumbnails = async (
+    visiblePaths: string[],
+    dropHidden: boolean
+  ): Promise<ReprioritizeSummary> => {
+    return await invoke<ReprioritizeSummary>('reprioritize_thumbnails', {
+      visiblePaths,
+      dropHidden,
+    })
   }
 
   const moveFilesBatch = async (
@@ -62,10 +234,123 @@ export function useTauriCommands() {
     return await invoke<number>('cleanup_thumbnail_cache', { maxAgeDays, maxSizeMb })
   }
 
+  const thumbnailCacheStats = async (): Promise<ThumbnailCacheStats> => {
+    return await invoke<ThumbnailCacheStats>('thumbnail_cache_stats')
+  }
+
+  const suggestDestination = async (
+    path: string,
+    configPath: string
+  ): Promise<DestinationSuggestion[]> => {
+    return await invoke<DestinationSuggestion[]>('suggest_destination', { path, configPath })
+  }
+
+  const planAutoSort = async (paths: string[], configPath: string): Promise<AutoSortPlan> => {
+    return await invoke<AutoSortPlan>('plan_auto_sort', { paths, configPath })
+  }
+
+  const planFanOut = async (path: string, key: string, configPath: string): Promise<string[]> => {
+    return await invoke<string[]>('plan_fan_out', { path, key, configPath })
+  }
+
+  const runAutoSort = async (plan: AutoSortPlan): Promise<AutoSortSummary> => {
+    return await invoke<AutoSortSummary>('run_auto_sort', { plan })
+  }
+
+  const readAiMetadata = async (path: string): Promise<AiMetadata | null> => {
+    return await invoke<AiMetadata | null>('read_ai_metadata', { path })
+  }
+
+  const readMetadata = async (path: string): Promise<ExifMetadata | null> => {
+    return await invoke<ExifMetadata | null>('read_metadata', { path })
+  }
+
+  const trainClassifier = async (path: string, key: string, configPath: string): Promise<void> => {
+    await invoke('train_classifier', { path, key, configPath })
+  }
+
+  const untrainClassifier = async (path: string, key: string, configPath: string): Promise<void> => {
+    await invoke('untrain_classifier', { path, key, configPath })
+  }
+
+  const suggestLearnedDestination = async (
+    path: string,
+    configPath: string
+  ): Promise<LearnedSuggestion[]> => {
+    return await invoke<LearnedSuggestion[]>('suggest_learned_destination', { path, configPath })
+  }
+
+  const resetClassifier = async (configPath: string): Promise<void> => {
+    await invoke('reset_classifier', { configPath })
+  }
+
+  const exportClassifier = async (configPath: string, exportPath: string): Promise<void> => {
+    await invoke('export_classifier', { configPath, exportPath })
+  }
+
+  const importWordList = async (path: string): Promise<WordList> => {
+    return await invoke<WordList>('import_word_list', { path })
+  }
+
+  const mineAliases = async (paths: string[], configPath: string): Promise<AliasMiningReport> => {
+    return await invoke<AliasMiningReport>('mine_aliases', { paths, configPath })
+  }
+
+  const acceptAliases = async (
+    key: string,
+    aliases: AliasAddition[],
+    configPath: string
+  ): Promise<WordList> => {
+    return await invoke<WordList>('accept_aliases', { key, aliases, configPath })
+  }
+
+  const watchWordLists = async (wordLists: Record<string, WordList | null>): Promise<void> => {
+    await invoke('watch_word_lists', { wordLists })
+  }
+
+  const unwatchWordLists = async (): Promise<void> => {
+    await invoke('unwatch_word_lists')
+  }
+
+  const lintWordList = async (wordList: WordList): Promise<LintIssue[]> => {
+    return await invoke<LintIssue[]>('lint_word_list', { wordList })
+  }
+
+  const mergeWordLists = async (
+    base: WordList,
+    incoming: WordList,
+    strategy: MergeStrategy
+  ): Promise<MergeResult> => {
+    return await invoke<MergeResult>('merge_word_lists', { base, incoming, strategy })
+  }
+
+  const dedupeWordList = async (wordList: WordList): Promise<MergeResult> => {
+    return await invoke<MergeResult>('dedupe_word_list', { wordList })
+  }
+
+  const explainMatch = async (
+    path: string,
+    key: string,
+    configPath: string
+  ): Promise<MatchExplanation> => {
+    return await invoke<MatchExplanation>('explain_match', { path, key, configPath })
+  }
+
   return {
     scanImages,
     moveFile,
     undoMove,
+    fanOutFile,
+    undoFanOut,
+    tagFile,
+    untagFile,
+    readFileTags,
+    writeFileTags,
+    removeFileTags,
+    recordSortTags,
+    setRating,
+    setColorLabel,
+    readRatingLabel,
     loadSettings,
     saveSettings,
     startWatching,
@@ -73,7 +358,32 @@ export function useTauriCommands() {
     getLogPath,
     generateThumbnail,
     generateThumbnailsBatch,
+    generateAnimatedPreview,
+    cancelThumbnailBatch,
+    cancelThumbnailRequest,
+    reprioritizeThumbnails,
     moveFilesBatch,
     cleanupThumbnailCache,
+    thumbnailCacheStats,
+    suggestDestination,
+    planAutoSort,
+    planFanOut,
+    runAutoSort,
+    readAiMetadata,
+    readMetadata,
+    trainClassifier,
+    untrainClassifier,
+    suggestLearnedDestination,
+    resetClassifier,
+    exportClassifier,
+    importWordList,
+    mineAliases,
+    acceptAliases,
+    watchWordLists,
+    unwatchWordLists,
+    lintWordList,
+    mergeWordLists,
+    dedupeWordList,
+    explainMatch,
   }
 }
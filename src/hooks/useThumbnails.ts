@@ -1,6 +1,7 @@
 import { useRef, useCallback } from 'react'
 import { convertFileSrc } from '@tauri-apps/api/core'
 import { useTauriCommands } from './useTauriCommands'
+import type { AnimationLimits, ThumbnailOptions } from '../types'
 
 interface ThumbnailCacheEntry {
   thumbnailUrl: string
@@ -13,11 +14,18 @@ interface QueueResolver {
 }
 
 const MAX_CACHE_SIZE = 500
-const MAX_CONCURRENT = 6
-
-export function useThumbnails(thumbnailSize: number = 200) {
-  const { generateThumbnail } = useTauriCommands()
+// 実行順はバックエンドの待ち行列が優先度で決めるため、同時実行数より多めに送る
+const MAX_CONCURRENT = 32
+
+export function useThumbnails(
+  thumbnailSize: number = 200,
+  options?: ThumbnailOptions,
+  animationLimits?: AnimationLimits | null
+) {
+  const { generateThumbnail, generateAnimatedPreview, reprioritizeThumbnails } = useTauriCommands()
   const cacheRef = useRef<Map<string, ThumbnailCacheEntry>>(new Map())
+  // 動くプレビューの URL（動かない画像は null）
+  const animatedRef = useRef<Map<string, Promise<string | null>>>(new Map())
   const pendingRef = useRef<Map<string, Promise<string>>>(new Map())
   const queueRef = useRef<string[]>([])
   const activeCountRef = useRef(0)
@@ -50,7 +58,7 @@ export function useThumbnails(thumbnailSize: number = 200) {
     activeCountRef.current++
 
     try {
-      const result = await generateThumbnail(path, thumbnailSize)
+      const result = await generateThumbnail(path, thumbnailSize, { options })
       const thumbnailUrl = convertFileSrc(result.thumbnailPath)
 
       cacheRef.current.set(path, {
@@ -69,7 +77,7 @@ export function useThumbnails(thumbnailSize: number = 200) {
     }
 
     return true
-  }, [generateThumbnail, thumbnailSize, evictOldEntries])
+  }, [generateThumbnail, thumbnailSize, options, evictOldEntries])
 
   // キュー処理ループ（非同期で並列実行）
   const processQueue = useCallback(() => {
@@ -131,6 +139,41 @@ export function useThumbnails(thumbnailSize: number = 200) {
     return promise
   }, [processQueue])
 
+  // 表示中のサムネイルを優先し、表示外になった待機中の要求を取り消す
+  const reprioritize = useCallback((visiblePaths: string[]) => {
+    const visible = new Set(visiblePaths)
+    const dropped = queueRef.current.filter((path) => !visible.has(path))
+    queueRef.current = queueRef.current.filter((path) => visible.has(path))
+
+    for (const path of dropped) {
+      resolversRef.current.get(path)?.reject(new Error('Thumbnail request dropped'))
+      resolversRef.current.delete(path)
+      pendingRef.current.delete(path)
+    }
+
+    reprioritizeThumbnails(visiblePaths, true).catch((err) => {
+      console.warn('Failed to reprioritize thumbnails:', err)
+    })
+  }, [reprioritizeThumbnails])
+
+  // 動くプレビューを取得（無効な場合・動かない画像の場合は null）
+  const requestAnimatedPreview = useCallback((path: string): Promise<string | null> => {
+    if (!animationLimits) return Promise.resolve(null)
+
+    const existing = animatedRef.current.get(path)
+    if (existing) return existing
+
+    const promise = generateAnimatedPreview(path, thumbnailSize, animationLimits)
+      .then((preview) => (preview ? convertFileSrc(preview.previewPath) : null))
+      .catch((err) => {
+        console.warn('Failed to generate animated preview:', err)
+        animatedRef.current.delete(path)
+        return null
+      })
+    animatedRef.current.set(path, promise)
+    return promise
+  }, [generateAnimatedPreview, thumbnailSize, animationLimits])
+
   // キャッシュからサムネイルURLを取得（同期版、なければnull）
   const getCachedThumbnail = useCallback((path: string): string | null => {
     const cached = cacheRef.current.get(path)
@@ -144,6 +187,7 @@ export function useThumbnails(thumbnailSize: number = 200) {
   // キャッシュをクリア
   const clearCache = useCallback(() => {
     cacheRef.current.clear()
+    animatedRef.current.clear()
     pendingRef.current.clear()
     queueRef.current = []
     resolversRef.current.clear()
@@ -152,6 +196,8 @@ export function useThumbnails(thumbnailSize: number = 200) {
   return {
     requestThumbnail,
     getCachedThumbnail,
+    requestAnimatedPreview,
+    reprioritize,
     clearCache,
   }
 }
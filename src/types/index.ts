@@ -3,6 +3,8 @@ export interface ImageInfo {
   name: string
   size?: number
   modifiedAt?: number
+  rating?: number
+  label?: ColorLabel
 }
 
 export interface Settings {
@@ -18,6 +20,15 @@ export interface Settings {
     y: number | null
   }
   wordLists?: Record<string, WordList | null>
+  matchingSources?: MatchSource[]
+  fanOut?: FanOutMode
+  operationMode?: OperationMode
+  tagLocation?: XmpLocation
+  tagLabels?: Record<string, string>
+  xattrTagging?: boolean
+  scanOptions?: ScanOptions
+  thumbnailOptions?: ThumbnailOptions
+  animatedPreviews?: AnimationLimits | null
 }
 
 export type Status = 'idle' | 'loading' | 'success' | 'error' | 'warning'
@@ -44,6 +55,11 @@ export interface MoveHistoryItem {
   sourcePath: string      // 元のファイルパス（フルパス）
   sourceFolder: string    // 元のフォルダパス（Undo時に使用）
   destPath: string        // 移動先ファイルパス（フルパス）
+  extraPaths?: string[]   // 追加の分別先に作成したリンク・コピー（複数分別先への配置時）
+  fanOutMode?: FanOutMode // 追加の分別先への配置方法（Redo時に使用）
+  tag?: TagResult         // タグモードで書き込んだキーワード（移動はしない）
+  xattr?: TagChange       // 移動先で記録した拡張属性（Undo時に取り除き、上書きした値は戻す）
+  trainedKey?: string     // 分類器に学習させた分別先キー（Undo時に取り消す）
   timestamp: number
 }
 
@@ -51,11 +67,56 @@ export interface MoveHistoryItem {
 export interface ThumbnailResult {
   originalPath: string
   thumbnailPath: string
+  mimeType: 'image/jpeg' | 'image/png' | 'image/webp'
+}
+
+// 透過のある画像のサムネイル形式（透過のない画像は JPEG）
+export type AlphaFormat = 'webp' | 'png'
+
+export interface ThumbnailOptions {
+  quality?: number
+  alphaFormat?: AlphaFormat
+}
+
+// 動く画像（GIF / APNG / WebP）の縮小プレビューの上限
+export interface AnimationLimits {
+  maxFrames?: number
+  maxDurationMs?: number
+}
+
+// 動く画像の縮小プレビュー（GIF）と元画像の情報
+export interface AnimatedPreview {
+  originalPath: string
+  previewPath: string
+  mimeType: 'image/gif'
+  frameCount: number
+  durationMs: number
+  previewFrameCount: number
+  previewDurationMs: number
 }
 
 export interface ThumbnailBatchResult {
   results: ThumbnailResult[]
   errors: ThumbnailError[]
+  cancelled: boolean
+}
+
+// バッチ生成の進捗（1件完了するごとに送信）
+export interface ThumbnailBatchProgress {
+  batchId: string | null
+  completed: number
+  total: number
+  result: ThumbnailResult | null
+  error: ThumbnailError | null
+}
+
+export interface ThumbnailCacheStats {
+  entries: number
+  totalBytes: number
+  maxBytes: number
+  hits: number
+  misses: number
+  hitRate: number
 }
 
 export interface ThumbnailError {
@@ -63,6 +124,20 @@ export interface ThumbnailError {
   error: string
 }
 
+// サムネイル生成の優先度（高いものから順に処理）
+export type ThumbnailPriority = 'low' | 'normal' | 'visible'
+
+export interface ThumbnailRequest {
+  options?: ThumbnailOptions
+  priority?: ThumbnailPriority
+  requestId?: string
+}
+
+export interface ReprioritizeSummary {
+  promoted: number
+  dropped: number
+}
+
 // マッチング用単語リスト
 export interface WordListEntry {
   type: 'work' | 'character'
@@ -71,13 +146,88 @@ export interface WordListEntry {
   canonicalNormalized: string
   aliasesNormalized: string[]
   searchKeysNormalized: string[]
+  // ローマ字・読み等から生成した派生キー
+  derivedKeysNormalized?: string[]
+  // キャラクターの所属作品（"work:作品名"）
+  workId?: string
 }
 
 export interface WordList {
   fileName: string
+  // 読み込み元CSVのパス（変更を監視して再読み込みする）
+  sourcePath?: string
   entries: WordListEntry[]
 }
 
+// マッチング対象テキストの取得元
+export type MatchSource =
+  | 'fileName'
+  | 'exifDescription'
+  | 'xmpSubject'
+  | 'iptcKeywords'
+  | 'pngText'
+  | 'aiPrompt'
+
+// 分別先の提案の根拠
+export type SuggestionOrigin = 'wordList' | 'learned'
+
+// 分別先の提案（単語リストによるマッチングと学習結果を合わせてランク付け）
+export interface DestinationSuggestion {
+  key: string
+  destination: string
+  origin: SuggestionOrigin
+  confirmed: boolean
+  confidence?: number     // 学習による提案のみ
+  // 以下は単語リストによる提案のみ
+  entityId?: string
+  canonical?: string
+  matchedKey?: string
+  rule?: 'A' | 'B' | 'C' | 'D'
+  score?: number
+  source?: MatchSource
+  derived?: boolean
+}
+
+// 自動仕分け
+export interface PlannedMove {
+  sourcePath: string
+  key: string
+  destination: string
+  canonical: string
+  rule: 'A' | 'B' | 'C' | 'D'
+  source: MatchSource
+  extraKeys?: string[]
+  extraDestinations?: string[]
+}
+
+export type SkipReason = 'unmatched' | 'candidateOnly' | 'ambiguous' | 'moveFailed'
+
+export interface SkippedFile {
+  path: string
+  reason: SkipReason
+  keys: string[]
+  error?: string
+}
+
+export interface AutoSortPlan {
+  moves: PlannedMove[]
+  skipped: SkippedFile[]
+  fanOut?: FanOutMode
+}
+
+export interface AutoSortMove {
+  sourcePath: string
+  sourceFolder: string
+  destPath: string
+  key: string
+  extraPaths: string[]
+}
+
+export interface AutoSortSummary {
+  moved: AutoSortMove[]
+  skipped: SkippedFile[]
+}
+
 export type AppAction =
   | { type: 'SET_SOURCE_FOLDER'; payload: string }
   | { type: 'SET_IMAGES'; payload: ImageInfo[] }
@@ -86,6 +236,7 @@ export type AppAction =
   | { type: 'REMOVE_CURRENT_IMAGE' }
   | { type: 'REMOVE_IMAGE_BY_PATH'; payload: string }
   | { type: 'ADD_IMAGE_BY_PATH'; payload: ImageInfo }
+  | { type: 'SET_IMAGE_RATING'; payload: { path: string; rating: number; label: ColorLabel | null } }
   | { type: 'SET_DESTINATION'; payload: { key: string; path: string | null } }
   | { type: 'SET_LAST_USED_DESTINATION'; payload: string }
   | { type: 'SET_STATUS'; payload: { status: Status; message?: string } }
@@ -99,3 +250,257 @@ export type AppAction =
   | { type: 'CLEAR_SELECTION' }
   | { type: 'REMOVE_SELECTED_IMAGES' }
   | { type: 'SELECT_MATCHING_FILES'; payload: string[] }
+
+// 画像生成メタデータ
+export type AiGenerator = 'a1111' | 'comfyUi'
+
+export interface AiMetadata {
+  generator: AiGenerator
+  prompt: string | null
+  negativePrompt: string | null
+  model: string | null
+  seed: number | null
+  sampler: string | null
+  steps: number | null
+  cfgScale: number | null
+  loras: string[]
+}
+
+// 分別操作の学習による提案
+export interface LearnedSuggestion {
+  key: string
+  destination: string
+  confidence: number
+}
+
+// 別名候補の抽出
+export interface AliasProposal {
+  key: string
+  type: string
+  canonical: string
+  alias: string
+  aliasNormalized: string
+  nearestKeyNormalized: string
+  score: number
+  occurrences: number
+  exampleFiles: string[]
+}
+
+export interface FrequentTerm {
+  text: string
+  normalized: string
+  occurrences: number
+  exampleFiles: string[]
+}
+
+// 単語リストに追加する別名（AliasProposal をそのまま渡せる）
+export interface AliasAddition {
+  type: string
+  canonical: string
+  alias: string
+}
+
+export interface AliasMiningReport {
+  proposals: AliasProposal[]
+  frequentTerms: FrequentTerm[]
+}
+
+// 単語リストの再読み込みイベント
+export interface WordListChangeEvent {
+  key: string
+  filePath: string
+  wordList: WordList
+  added: string[]
+  removed: string[]
+  changed: string[]
+}
+
+// 単語リストの検査・統合
+export type LintKind =
+  | 'duplicateCanonical'
+  | 'keyCollision'
+  | 'shortKey'
+  | 'emptyAlias'
+  | 'missingWorkId'
+
+export interface LintIssue {
+  kind: LintKind
+  entityIds: string[]
+  key: string | null
+}
+
+export type MergeStrategy = 'keepBase' | 'preferIncoming' | 'unionAliases'
+
+export type MergeResolution = 'keptBase' | 'usedIncoming' | 'unitedAliases' | 'unresolved'
+
+export interface MergeConflict {
+  kind: LintKind
+  entityIds: string[]
+  key: string | null
+  resolution: MergeResolution
+}
+
+export interface MergeResult {
+  wordList: WordList
+  conflicts: MergeConflict[]
+}
+
+// 分別先ごとの単語リストの検査・統合結果
+export interface WordListReport {
+  key: string
+  fileName: string
+  issues: (LintIssue | MergeConflict)[]
+}
+
+// マッチングの評価過程（explain_match）
+export type ResolutionStep =
+  | 'duplicateEntity'
+  | 'alreadyConfirmed'
+  | 'workConflict'
+  | 'candidateLimit'
+
+export interface MatchDebug {
+  matchLength: number
+  resolution: ResolutionStep | null
+}
+
+export interface ScoreBreakdown {
+  ngramSimilarity: number
+  lengthBonus: number
+  score: number
+}
+
+export interface ExplainedMatchItem {
+  entityId: string
+  type: 'work' | 'character'
+  canonical: string
+  matchedKey: string
+  matchedKeyNormalized: string
+  rule: 'A' | 'B' | 'C' | 'D'
+  score: number
+  source: MatchSource
+  derived: boolean
+  debug?: MatchDebug
+}
+
+export interface RuleEvaluation {
+  rule: 'A' | 'B' | 'C' | 'D'
+  matched: boolean
+  keyNormalized: string | null
+  derived: boolean
+  score: ScoreBreakdown | null
+}
+
+export interface EntityEvaluation {
+  entityId: string
+  type: 'work' | 'character'
+  canonical: string
+  rules: RuleEvaluation[]
+  appliedRule: 'A' | 'B' | 'C' | 'D' | null
+}
+
+export interface TargetExplanation {
+  source: MatchSource
+  rawText: string
+  stages: { name: string; output: string }[]
+  normalized: string
+  entities: EntityEvaluation[]
+}
+
+export interface MatchExplanation {
+  filePath: string
+  targets: TargetExplanation[]
+  result: {
+    filePath: string
+    targetText: string
+    targetNormalized: string
+    confirmed: ExplainedMatchItem[]
+    candidates: ExplainedMatchItem[]
+    unmatched: boolean
+  }
+  dropped: ExplainedMatchItem[]
+}
+
+// 複数分別先への配置
+export type FanOutMode = 'hardLink' | 'copy'
+
+export interface FanOutResult {
+  destPath: string
+  extraPaths: string[]
+}
+
+// タグモード（移動せず XMP キーワードを書き込む）
+export type OperationMode = 'move' | 'tag'
+
+export type XmpLocation = 'sidecar' | 'embedded'
+
+export interface TagResult {
+  path: string
+  key: string
+  keywords: string[]
+  location: XmpLocation
+  sidecarCreated: boolean
+}
+
+// 拡張属性のタグ（user.xdg.tags / user.picsort.*）
+export interface FileTags {
+  tags: string[]
+  attributes: Record<string, string>
+}
+
+// 拡張属性の書き込み結果（previous は上書きした picSort 属性の以前の値）
+export interface TagChange extends FileTags {
+  previous: Record<string, string>
+}
+
+export interface ScanOptions {
+  hideSorted?: boolean
+  excludeTags?: string[]
+  minRating?: number
+  labels?: ColorLabel[]
+  sortBy?: ScanSort
+}
+
+// 評価と色ラベル（XMP の xmp:Rating / xmp:Label）
+export type ColorLabel = 'red' | 'yellow' | 'green' | 'blue' | 'purple'
+
+export type ScanSort = 'name' | 'rating' | 'label'
+
+// 評価・色ラベル（スキャン時に読み込まなかったファイルの表示用）
+export interface RatingLabel {
+  rating: number
+  label: ColorLabel | null
+}
+
+export interface RatingResult {
+  path: string
+  rating: number
+  label: ColorLabel | null
+  location: XmpLocation
+}
+
+// 撮影情報（EXIF）
+export interface GpsPosition {
+  latitude: number
+  longitude: number
+  altitude: number | null
+}
+
+export interface ExifMetadata {
+  make: string | null
+  model: string | null
+  lensMake: string | null
+  lensModel: string | null
+  exposureTime: string | null
+  fNumber: number | null
+  iso: number | null
+  focalLength: number | null
+  focalLength35mm: number | null
+  capturedAt: string | null
+  timeOffset: string | null
+  orientation: number | null
+  width: number | null
+  height: number | null
+  gps: GpsPosition | null
+  software: string | null
+}